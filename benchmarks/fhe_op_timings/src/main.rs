@@ -0,0 +1,137 @@
+//! Measures how long each FHE operation takes at the standard lattice
+//! dimensions and prints the results as an
+//! [`OperationTimings`](sunscreen_runtime::OperationTimings) JSON table.
+//!
+//! The runtime's default table holds rough estimates. To replace it with
+//! measurements, run
+//! `cargo run --release -p fhe_op_timings > sunscreen_runtime/src/op_timings.json`
+//! from a clean checkout. The table records the CPU and commit it was
+//! measured on.
+
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use seal_fhe::*;
+use sunscreen_runtime::{OperationTiming, OperationTimings, TimingsProvenance};
+
+const SAMPLES: u32 = 100;
+
+/**
+ * Returns the mean time `f` takes over [`SAMPLES`] runs.
+ */
+fn time<T>(mut f: impl FnMut() -> T) -> Duration {
+    // Warm up caches and SEAL's memory pool.
+    std::hint::black_box(f());
+
+    let start = Instant::now();
+
+    for _ in 0..SAMPLES {
+        std::hint::black_box(f());
+    }
+
+    start.elapsed() / SAMPLES
+}
+
+fn measure(lattice_dimension: u64) -> OperationTiming {
+    let security_level = SecurityLevel::TC128;
+    let coeff_modulus = CoefficientModulus::bfv_default(lattice_dimension, security_level).unwrap();
+    let coeff_modulus_count = coeff_modulus.len();
+
+    let params = BfvEncryptionParametersBuilder::new()
+        .set_poly_modulus_degree(lattice_dimension)
+        .set_coefficient_modulus(coeff_modulus)
+        .set_plain_modulus(PlainModulus::batching(lattice_dimension, 20).unwrap())
+        .build()
+        .unwrap();
+
+    let context = Context::new(&params, true, security_level).unwrap();
+
+    let keygen = KeyGenerator::new(&context).unwrap();
+    let public_key = keygen.create_public_key();
+    let encryptor = Encryptor::with_public_key(&context, &public_key).unwrap();
+    let evaluator = BFVEvaluator::new(&context).unwrap();
+    let encoder = BFVEncoder::new(&context).unwrap();
+
+    let data = (0..lattice_dimension).collect::<Vec<_>>();
+    let plaintext = encoder.encode_unsigned(&data).unwrap();
+    let a = encryptor.encrypt(&plaintext).unwrap();
+    let b = encryptor.encrypt(&plaintext).unwrap();
+
+    // Key switching needs a special modulus, so parameters with a single
+    // modulus can neither relinearize nor rotate.
+    let (relinearize, rotate) = if coeff_modulus_count > 1 {
+        let relin_keys = keygen.create_relinearization_keys().unwrap();
+        let galois_keys = keygen.create_galois_keys().unwrap();
+        let product = evaluator.multiply(&a, &b).unwrap();
+
+        (
+            time(|| evaluator.relinearize(&product, &relin_keys).unwrap()),
+            time(|| evaluator.rotate_rows(&a, 1, &galois_keys).unwrap()),
+        )
+    } else {
+        (Duration::ZERO, Duration::ZERO)
+    };
+
+    OperationTiming {
+        lattice_dimension,
+        coeff_modulus_count,
+        multiply: time(|| evaluator.multiply(&a, &b).unwrap()),
+        relinearize,
+        rotate,
+        multiply_plaintext: time(|| evaluator.multiply_plain(&a, &plaintext).unwrap()),
+        add_plaintext: time(|| evaluator.add_plain(&a, &plaintext).unwrap()),
+        add: time(|| evaluator.add(&a, &b).unwrap()),
+    }
+}
+
+/**
+ * The CPU's model name, falling back to its architecture where
+ * `/proc/cpuinfo` isn't available.
+ */
+fn machine() -> String {
+    std::fs::read_to_string("/proc/cpuinfo")
+        .ok()
+        .and_then(|info| {
+            info.lines()
+                .find(|x| x.starts_with("model name"))
+                .and_then(|x| x.split_once(':'))
+                .map(|(_, name)| name.trim().to_owned())
+        })
+        .unwrap_or_else(|| std::env::consts::ARCH.to_owned())
+}
+
+/**
+ * The commit the current checkout is at.
+ */
+fn commit() -> String {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .expect("Failed to run git");
+
+    assert!(output.status.success(), "Failed to find the current commit");
+
+    String::from_utf8(output.stdout).unwrap().trim().to_owned()
+}
+
+fn main() {
+    env_logger::init();
+
+    let timings = [1_024, 2_048, 4_096, 8_192, 16_384, 32_768]
+        .into_iter()
+        .map(|lattice_dimension| {
+            log::info!("Measuring lattice dimension {lattice_dimension}...");
+
+            measure(lattice_dimension)
+        })
+        .collect::<Vec<_>>();
+
+    let timings = OperationTimings::try_from(timings)
+        .unwrap()
+        .with_provenance(TimingsProvenance {
+            machine: machine(),
+            commit: commit(),
+        });
+
+    println!("{}", timings.to_json().unwrap());
+}
@@ -2,8 +2,8 @@ use std::ptr::null_mut;
 
 use crate::evaluator_base::EvaluatorBase;
 use crate::{
-    bindgen, error::convert_seal_error, Ciphertext, Context, Evaluator, GaloisKeys, Plaintext,
-    RelinearizationKeys, Result,
+    bindgen, error::convert_seal_error, Ciphertext, Context, Error, Evaluator, GaloisKeys,
+    KeySwitchingKeys, Plaintext, RelinearizationKeys, Result,
 };
 
 /**
@@ -174,6 +174,45 @@ impl Evaluator for BFVEvaluator {
         Ok(out)
     }
 
+    fn switch_key_inplace(&self, a: &mut Ciphertext, keys: &KeySwitchingKeys) -> Result<()> {
+        if a.num_polynomials() != 2 {
+            return Err(Error::InvalidArgument);
+        }
+
+        // SEAL doesn't expose key switching directly, but relinearization is
+        // key switching of the last polynomial. Given (c_0, c_1) under s,
+        // relinearizing (c_0, 0, c_1) with keys encrypting s under s' yields
+        // (c_0 + d_0, d_1) where d_0 + d_1 * s' ~= c_1 * s.
+        let poly_len = (a.poly_modulus_degree() * a.coeff_modulus_size()) as usize;
+
+        a.resize(3)?;
+
+        for i in 0..poly_len {
+            let c = a.get_data(poly_len + i)?;
+
+            a.set_data(2 * poly_len + i, c)?;
+            a.set_data(poly_len + i, 0)?;
+        }
+
+        convert_seal_error(unsafe {
+            bindgen::Evaluator_Relinearize(
+                self.get_handle(),
+                a.get_handle(),
+                keys.get_handle(),
+                a.get_handle(),
+                null_mut(),
+            )
+        })
+    }
+
+    fn switch_key(&self, a: &Ciphertext, keys: &KeySwitchingKeys) -> Result<Ciphertext> {
+        let mut out = a.clone();
+
+        self.switch_key_inplace(&mut out, keys)?;
+
+        Ok(out)
+    }
+
     fn rotate_rows(
         &self,
         a: &Ciphertext,
@@ -679,6 +718,58 @@ mod tests {
         });
     }
 
+    #[test]
+    fn can_switch_key() {
+        let params = BfvEncryptionParametersBuilder::new()
+            .set_poly_modulus_degree(8192)
+            .set_coefficient_modulus(
+                CoefficientModulus::create(8192, &[50, 30, 30, 50, 50]).unwrap(),
+            )
+            .set_plain_modulus(PlainModulus::batching(8192, 32).unwrap())
+            .build()
+            .unwrap();
+
+        let ctx = Context::new(&params, false, SecurityLevel::TC128).unwrap();
+        let encoder = BFVEncoder::new(&ctx).unwrap();
+        let evaluator = BFVEvaluator::new(&ctx).unwrap();
+
+        let old_gen = KeyGenerator::new(&ctx).unwrap();
+        let new_gen = KeyGenerator::new(&ctx).unwrap();
+
+        let old_key = old_gen.secret_key();
+        let new_key = new_gen.secret_key();
+
+        let encryptor = Encryptor::with_public_key(&ctx, &old_gen.create_public_key()).unwrap();
+        let old_decryptor = Decryptor::new(&ctx, &old_key).unwrap();
+        let new_decryptor = Decryptor::new(&ctx, &new_key).unwrap();
+
+        let keys = new_gen.create_key_switching_keys(&ctx, &old_key).unwrap();
+
+        let a = make_vec(&encoder);
+        let a_c = encryptor
+            .encrypt(&encoder.encode_signed(&a).unwrap())
+            .unwrap();
+
+        let b_c = evaluator.switch_key(&a_c, &keys).unwrap();
+
+        assert_eq!(b_c.num_polynomials(), 2);
+        assert!(new_decryptor.invariant_noise_budget(&b_c).unwrap() > 0);
+        assert_eq!(
+            encoder
+                .decode_signed(&new_decryptor.decrypt(&b_c).unwrap())
+                .unwrap(),
+            a
+        );
+
+        // The old key should no longer decrypt the ciphertext.
+        assert_ne!(
+            encoder
+                .decode_signed(&old_decryptor.decrypt(&b_c).unwrap())
+                .unwrap(),
+            a
+        );
+    }
+
     #[test]
     fn can_exponentiate() {
         run_bfv_test(|decryptor, encoder, encryptor, evaluator, keygen| {
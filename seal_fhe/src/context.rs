@@ -105,6 +105,28 @@ impl Context {
     pub fn get_handle(&self) -> *mut c_void {
         self.handle
     }
+
+    /**
+     * Returns the encryption parameters at the key level of the modulus
+     * switching chain. These are the parameters the context was created with,
+     * i.e. the coefficient modulus includes the special prime used during key
+     * switching.
+     */
+    pub fn get_key_parameters(&self) -> Result<EncryptionParameters> {
+        let mut context_data: *mut c_void = null_mut();
+
+        // The returned context data is owned by the context and must not be
+        // destroyed.
+        convert_seal_error(unsafe {
+            bindgen::SEALContext_KeyContextData(self.handle, &mut context_data)
+        })?;
+
+        let mut handle: *mut c_void = null_mut();
+
+        convert_seal_error(unsafe { bindgen::ContextData_Parms(context_data, &mut handle) })?;
+
+        Ok(EncryptionParameters { handle })
+    }
 }
 
 impl Drop for Context {
@@ -133,4 +155,25 @@ mod tests {
 
         std::mem::drop(ctx);
     }
+
+    #[test]
+    fn can_get_key_parameters() {
+        let params = BfvEncryptionParametersBuilder::new()
+            .set_poly_modulus_degree(8192)
+            .set_coefficient_modulus(
+                CoefficientModulus::create(8192, &[50, 30, 30, 50, 50]).unwrap(),
+            )
+            .set_plain_modulus_u64(1234)
+            .build()
+            .unwrap();
+
+        let ctx = Context::new(&params, false, SecurityLevel::TC128).unwrap();
+        let key_params = ctx.get_key_parameters().unwrap();
+
+        assert_eq!(key_params.get_poly_modulus_degree(), 8192);
+        assert_eq!(
+            key_params.get_coefficient_modulus(),
+            params.get_coefficient_modulus()
+        );
+    }
 }
@@ -66,7 +66,7 @@ impl SchemeType {
  * inexperienced users seem to most often make critical mistakes.
  */
 pub struct EncryptionParameters {
-    handle: *mut c_void,
+    pub(crate) handle: *mut c_void,
 }
 
 unsafe impl Sync for EncryptionParameters {}
@@ -1,5 +1,5 @@
 use crate::error::*;
-use crate::{Ciphertext, GaloisKeys, Plaintext, RelinearizationKeys};
+use crate::{Ciphertext, GaloisKeys, KeySwitchingKeys, Plaintext, RelinearizationKeys};
 
 /**
  * An interface for an evaluator.
@@ -214,6 +214,26 @@ pub trait Evaluator {
      */
     fn relinearize(&self, a: &Ciphertext, relin_keys: &RelinearizationKeys) -> Result<Ciphertext>;
 
+    /**
+     * Switches a ciphertext encrypted under one secret key to an encryption of the same
+     * message under another secret key without decrypting it. This variant does so in-place.
+     *
+     * * `a` - The ciphertext to switch. It must have 2 polynomials (i.e. be relinearized).
+     * * `keys` - Keys from the old to the new secret key created with
+     *   [`KeyGenerator::create_key_switching_keys`](crate::KeyGenerator::create_key_switching_keys).
+     */
+    fn switch_key_inplace(&self, a: &mut Ciphertext, keys: &KeySwitchingKeys) -> Result<()>;
+
+    /**
+     * Switches a ciphertext encrypted under one secret key to an encryption of the same
+     * message under another secret key without decrypting it.
+     *
+     * * `a` - The ciphertext to switch. It must have 2 polynomials (i.e. be relinearized).
+     * * `keys` - Keys from the old to the new secret key created with
+     *   [`KeyGenerator::create_key_switching_keys`](crate::KeyGenerator::create_key_switching_keys).
+     */
+    fn switch_key(&self, a: &Ciphertext, keys: &KeySwitchingKeys) -> Result<Ciphertext>;
+
     /**
      * Rotates plaintext matrix rows cyclically.
      *
@@ -4,7 +4,7 @@ use std::ptr::null_mut;
 use crate::bindgen;
 use crate::error::*;
 use crate::serialization::CompressionType;
-use crate::{Context, FromBytes, ToBytes};
+use crate::{Context, Error as SealError, FromBytes, ToBytes};
 
 use serde::ser::Error;
 use serde::{Serialize, Serializer};
@@ -180,6 +180,110 @@ impl KeyGenerator {
 
         Ok(GaloisKeys { handle })
     }
+
+    /**
+     * Generates keys for switching ciphertexts encrypted under `old_key` to
+     * this generator's secret key without decrypting them. See
+     * [`Evaluator::switch_key`](crate::Evaluator::switch_key).
+     *
+     * * `ctx` - The context this generator was created with. `old_key` must
+     *   have been created under the same context.
+     * * `old_key` - The secret key the ciphertexts to switch are currently
+     *   encrypted under.
+     *
+     * # Remarks
+     * The resulting keys have the same form as relinearization keys, but
+     * encrypt `old_key` rather than the square of this generator's secret key.
+     * Each RNS component `j` of the data-level coefficient modulus gets a fresh
+     * encryption of zero under this generator's key (i.e. a public key), to
+     * which `old_key * p mod q_j` is added in RNS component `j`, where `p` is
+     * the special prime.
+     */
+    pub fn create_key_switching_keys(
+        &self,
+        ctx: &Context,
+        old_key: &SecretKey,
+    ) -> Result<KeySwitchingKeys> {
+        let key_params = ctx.get_key_parameters()?;
+        let key_modulus = key_params.get_coefficient_modulus();
+        let degree = key_params.get_poly_modulus_degree() as usize;
+
+        // Key switching needs a special prime in addition to at least one
+        // data-level prime.
+        let (special_prime, decomp_modulus) = match key_modulus.split_last() {
+            Some((p, q)) if !q.is_empty() => (p.value(), q),
+            _ => return Err(SealError::InvalidArgument),
+        };
+
+        // The returned plaintext is owned by the secret key and must not be
+        // destroyed.
+        let mut old_key_data: *mut c_void = null_mut();
+
+        convert_seal_error(unsafe { bindgen::SecretKey_Data(old_key.handle, &mut old_key_data) })?;
+
+        let components = decomp_modulus
+            .iter()
+            .enumerate()
+            .map(|(j, q)| {
+                let q = q.value();
+                let factor = special_prime % q;
+                let component = self.create_public_key();
+
+                // The ciphertext is owned by the public key and must not be
+                // destroyed.
+                let mut data: *mut c_void = null_mut();
+
+                convert_seal_error(unsafe {
+                    bindgen::PublicKey_Data(component.handle, &mut data)
+                })?;
+
+                // Both the secret key and public key are in NTT form at the
+                // key level, so we can add them coefficient-wise. The first
+                // polynomial of the public key starts at index 0 and RNS
+                // component j starts at j * degree.
+                for i in (j * degree)..((j + 1) * degree) {
+                    let mut s: u64 = 0;
+                    let mut c: u64 = 0;
+
+                    unsafe {
+                        convert_seal_error(bindgen::Plaintext_CoeffAt(
+                            old_key_data,
+                            i as u64,
+                            &mut s,
+                        ))?;
+                        convert_seal_error(bindgen::Ciphertext_GetDataAt1(data, i as u64, &mut c))?;
+                    }
+
+                    let c = (c as u128 + factor as u128 * s as u128) % q as u128;
+
+                    convert_seal_error(unsafe {
+                        bindgen::Ciphertext_SetDataAt(data, i as u64, c as u64)
+                    })?;
+                }
+
+                Ok(component)
+            })
+            .collect::<Result<Vec<PublicKey>>>()?;
+
+        let mut parms_id = [0u64; 4];
+
+        convert_seal_error(unsafe {
+            bindgen::PublicKey_ParmsId(components[0].handle, parms_id.as_mut_ptr())
+        })?;
+
+        let keys = KeySwitchingKeys::new()?;
+        let mut handles = components.iter().map(|c| c.handle).collect::<Vec<_>>();
+
+        convert_seal_error(unsafe {
+            bindgen::KSwitchKeys_AddKeyList(keys.handle, handles.len() as u64, handles.as_mut_ptr())
+        })?;
+
+        convert_seal_error(unsafe {
+            bindgen::KSwitchKeys_SetParmsId(keys.handle, parms_id.as_mut_ptr())
+        })?;
+
+        Ok(keys)
+    }
 }
 
 impl Drop for KeyGenerator {
@@ -749,6 +853,115 @@ impl CompactGaloisKeys {
     }
 }
 
+/**
+ * Class to store key switching keys.
+ *
+ * Key switching keys allow converting a ciphertext encrypted under one secret
+ * key into an encryption of the same message under another secret key,
+ * without decrypting it. This is useful for e.g. rotating secret keys.
+ * Generate them with [`KeyGenerator::create_key_switching_keys`] and apply
+ * them with [`Evaluator::switch_key`](crate::Evaluator::switch_key).
+ *
+ * Since key switching keys are encryptions of the old secret key under the
+ * new one, they may be shared with the party performing the switch without
+ * revealing either key.
+ */
+pub struct KeySwitchingKeys {
+    handle: *mut c_void,
+}
+
+unsafe impl Sync for KeySwitchingKeys {}
+unsafe impl Send for KeySwitchingKeys {}
+
+impl KeySwitchingKeys {
+    /**
+     * Returns the handle to the underlying SEAL object.
+     */
+    pub fn get_handle(&self) -> *mut c_void {
+        self.handle
+    }
+
+    fn new() -> Result<KeySwitchingKeys> {
+        let mut handle: *mut c_void = null_mut();
+
+        convert_seal_error(unsafe { bindgen::KSwitchKeys_Create1(&mut handle) })?;
+
+        Ok(Self { handle })
+    }
+}
+
+impl PartialEq for KeySwitchingKeys {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl ToBytes for KeySwitchingKeys {
+    fn as_bytes(&self) -> Result<Vec<u8>> {
+        let mut num_bytes: i64 = 0;
+
+        convert_seal_error(unsafe {
+            bindgen::KSwitchKeys_SaveSize(self.handle, CompressionType::ZStd as u8, &mut num_bytes)
+        })?;
+
+        let mut data: Vec<u8> = Vec::with_capacity(num_bytes as usize);
+        let mut bytes_written: i64 = 0;
+
+        convert_seal_error(unsafe {
+            let data_ptr = data.as_mut_ptr();
+
+            bindgen::KSwitchKeys_Save(
+                self.handle,
+                data_ptr,
+                num_bytes as u64,
+                CompressionType::ZStd as u8,
+                &mut bytes_written,
+            )
+        })?;
+
+        unsafe { data.set_len(bytes_written as usize) };
+
+        Ok(data)
+    }
+}
+
+impl FromBytes for KeySwitchingKeys {
+    fn from_bytes(context: &Context, bytes: &[u8]) -> Result<Self> {
+        let keys = KeySwitchingKeys::new()?;
+        let mut write_bytes: i64 = 0;
+
+        convert_seal_error(unsafe {
+            bindgen::KSwitchKeys_Load(
+                keys.handle,
+                context.handle,
+                bytes.as_ptr() as *mut u8,
+                bytes.len() as u64,
+                &mut write_bytes,
+            )
+        })?;
+
+        Ok(keys)
+    }
+}
+
+impl Drop for KeySwitchingKeys {
+    fn drop(&mut self) {
+        convert_seal_error(unsafe { bindgen::KSwitchKeys_Destroy(self.handle) })
+            .expect("Fatal error in KeySwitchingKeys::drop()")
+    }
+}
+
+impl Clone for KeySwitchingKeys {
+    fn clone(&self) -> Self {
+        let mut handle: *mut c_void = null_mut();
+
+        convert_seal_error(unsafe { bindgen::KSwitchKeys_Create2(self.handle, &mut handle) })
+            .expect("Failed to clone key switching keys.");
+
+        Self { handle }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::*;
@@ -67,7 +67,9 @@ pub use encryptor_decryptor::{
 };
 pub use error::{Error, Result};
 pub use evaluator::Evaluator;
-pub use key_generator::{GaloisKeys, KeyGenerator, PublicKey, RelinearizationKeys, SecretKey};
+pub use key_generator::{
+    GaloisKeys, KeyGenerator, KeySwitchingKeys, PublicKey, RelinearizationKeys, SecretKey,
+};
 pub use modulus::{CoefficientModulus, Modulus, PlainModulus, SecurityLevel};
 pub use plaintext_ciphertext::{Ciphertext, Plaintext};
 
@@ -393,7 +393,6 @@ impl Ciphertext {
      * Returns the value at a specific point in the coefficient array. This is
      * not publically exported as it leaks the encoding of the array.
      */
-    #[allow(dead_code)]
     pub(crate) fn get_data(&self, index: usize) -> Result<u64> {
         let mut value: u64 = 0;
 
@@ -404,6 +403,37 @@ impl Ciphertext {
         Ok(value)
     }
 
+    /**
+     * Sets the value at a specific point in the coefficient array.
+     */
+    pub(crate) fn set_data(&mut self, index: usize, value: u64) -> Result<()> {
+        convert_seal_error(unsafe {
+            bindgen::Ciphertext_SetDataAt(self.handle, index as u64, value)
+        })
+    }
+
+    /**
+     * Returns the degree of the polynomials in this ciphertext.
+     */
+    pub(crate) fn poly_modulus_degree(&self) -> u64 {
+        let mut degree: u64 = 0;
+
+        convert_seal_error(unsafe {
+            bindgen::Ciphertext_PolyModulusDegree(self.handle, &mut degree)
+        })
+        .unwrap();
+
+        degree
+    }
+
+    /**
+     * Changes the number of polynomials in this ciphertext, keeping the
+     * existing ones. New polynomials are zero.
+     */
+    pub(crate) fn resize(&mut self, size: u64) -> Result<()> {
+        convert_seal_error(unsafe { bindgen::Ciphertext_Resize3(self.handle, size) })
+    }
+
     /**
      * Returns the coefficient in the form the ciphertext is currently in (NTT
      * form or not). For BFV, this will be the coefficient in the residual
@@ -440,6 +470,37 @@ impl Ciphertext {
 
         result
     }
+
+    /**
+     * Returns whether this ciphertext is valid for the given context. This
+     * checks the ciphertext's metadata (e.g. parameters and size) matches the
+     * context and that every coefficient is reduced modulo its coefficient
+     * modulus. Use this on ciphertexts from untrusted sources.
+     */
+    pub fn is_valid_for(&self, ctx: &Context) -> bool {
+        let mut result = false;
+
+        convert_seal_error(unsafe {
+            bindgen::ValCheck_Ciphertext_IsValidFor(self.handle, ctx.get_handle(), &mut result)
+        })
+        .expect("Fatal error in Ciphertext::is_valid_for().");
+
+        result
+    }
+
+    /**
+     * Returns whether this ciphertext is transparent, i.e. every polynomial
+     * but the first is zero. Transparent ciphertexts don't hide their
+     * message.
+     */
+    pub fn is_transparent(&self) -> bool {
+        let mut result = false;
+
+        convert_seal_error(unsafe { bindgen::Ciphertext_IsTransparent(self.handle, &mut result) })
+            .expect("Fatal error in Ciphertext::is_transparent().");
+
+        result
+    }
 }
 
 impl PartialEq for Ciphertext {
@@ -506,6 +567,9 @@ impl Drop for Ciphertext {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        BfvEncryptionParametersBuilder, CoefficientModulus, Encryptor, KeyGenerator, SecurityLevel,
+    };
 
     #[test]
     fn can_create_and_destroy_ciphertext() {
@@ -521,6 +585,46 @@ mod tests {
         std::mem::drop(plaintext);
     }
 
+    #[test]
+    fn can_validate_ciphertext() {
+        let params = BfvEncryptionParametersBuilder::new()
+            .set_poly_modulus_degree(8192)
+            .set_coefficient_modulus(
+                CoefficientModulus::create(8192, &[50, 30, 30, 50, 50]).unwrap(),
+            )
+            .set_plain_modulus_u64(1234)
+            .build()
+            .unwrap();
+
+        let ctx = Context::new(&params, false, SecurityLevel::TC128).unwrap();
+        let gen = KeyGenerator::new(&ctx).unwrap();
+
+        let encryptor = Encryptor::with_public_key(&ctx, &gen.create_public_key()).unwrap();
+
+        let mut ciphertext = encryptor
+            .encrypt(&Plaintext::from_hex_string("12x^2 + 34").unwrap())
+            .unwrap();
+
+        assert!(ciphertext.is_valid_for(&ctx));
+        assert!(!ciphertext.is_transparent());
+
+        // Coefficients must be reduced modulo their coefficient modulus.
+        let mut corrupted = ciphertext.clone();
+        corrupted.set_data(0, u64::MAX).unwrap();
+
+        assert!(!corrupted.is_valid_for(&ctx));
+
+        // Zeroing the second polynomial leaves the message in the clear.
+        let poly_len =
+            (ciphertext.poly_modulus_degree() * ciphertext.coeff_modulus_size()) as usize;
+
+        for i in poly_len..(2 * poly_len) {
+            ciphertext.set_data(i, 0).unwrap();
+        }
+
+        assert!(ciphertext.is_transparent());
+    }
+
     #[test]
     fn plaintext_coefficients_in_increasing_order() {
         let plaintext = Plaintext::from_hex_string("1234x^2 + 4321").unwrap();
@@ -0,0 +1,112 @@
+//! Renders the FHE programs in an application saved with
+//! [`Application::save`](sunscreen::Application::save) as Graphviz DOT or
+//! JSON.
+//!
+//! ```text
+//! sunscreen-render <application> [--format dot|json] [--program <name>]
+//! ```
+//!
+//! Each node is annotated with its operation, type, multiplicative depth
+//! and predicted noise budget. Pipe DOT output to e.g. `dot -Tsvg`.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::process::exit;
+
+use sunscreen::{export_fhe_program, ApplicationArtifact};
+use sunscreen_fhe_program::FheProgramTrait;
+
+const USAGE: &str = "usage: sunscreen-render <application> [--format dot|json] [--program <name>]";
+
+enum Format {
+    Dot,
+    Json,
+}
+
+struct Args {
+    path: String,
+    format: Format,
+    program: Option<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = std::env::args().skip(1);
+
+    let mut path = None;
+    let mut format = Format::Dot;
+    let mut program = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                format = match args.next().as_deref() {
+                    Some("dot") => Format::Dot,
+                    Some("json") => Format::Json,
+                    _ => return Err("--format must be dot or json".to_owned()),
+                };
+            }
+            "--program" => {
+                program = Some(args.next().ok_or("--program requires a name")?);
+            }
+            "-h" | "--help" => return Err(USAGE.to_owned()),
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(format!("unexpected argument {arg}")),
+        }
+    }
+
+    Ok(Args {
+        path: path.ok_or(USAGE)?,
+        format,
+        program,
+    })
+}
+
+fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    let artifact = ApplicationArtifact::load(&args.path)?;
+
+    let mut graphs = BTreeMap::new();
+
+    for (name, program) in artifact.get_fhe_programs() {
+        if args.program.as_ref().map_or(false, |p| p != name) {
+            continue;
+        }
+
+        // Exporting predicts noise, which requires a well-formed program.
+        program.fhe_program_fn.validate()?;
+
+        graphs.insert(name.clone(), export_fhe_program(program));
+    }
+
+    if let Some(name) = &args.program {
+        if graphs.is_empty() {
+            return Err(format!("no FHE program named {name}").into());
+        }
+    }
+
+    match args.format {
+        Format::Dot => {
+            for (name, graph) in &graphs {
+                println!("// {name}");
+                print!("{}", graph.to_dot());
+            }
+        }
+        Format::Json => println!("{}", serde_json::to_string_pretty(&graphs)?),
+    }
+
+    Ok(())
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}");
+            exit(2);
+        }
+    };
+
+    if let Err(e) = run(args) {
+        eprintln!("error: {e}");
+        exit(1);
+    }
+}
@@ -2,8 +2,8 @@ use crate::fhe::{FheCompile, FheFrontendCompilation};
 use crate::params::{determine_params, PlainModulusConstraint};
 use crate::zkp::{Linked, NotLinked};
 use crate::{
-    zkp, Application, CallSignature, Error, FheProgramMetadata, Params, RequiredKeys, Result,
-    SchemeType, SecurityLevel, ZkpProgramFn,
+    zkp, Application, CallSignature, Error, FheProgramMetadata, Params, ParamsReport, RequiredKeys,
+    Result, SchemeType, SecurityLevel, ZkpProgramFn,
 };
 use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
@@ -47,7 +47,9 @@ pub trait FheProgramFn {
     fn name(&self) -> &str;
 
     /**
-     * The number of times to chain this FHE program.
+     * The number of times to chain this FHE program. If any program in an application
+     * chains more than once, the compiler assumes every program's outputs may be fed to
+     * any program in the application.
      */
     fn chain_count(&self) -> usize;
 }
@@ -357,11 +359,11 @@ impl<T: marker::Fhe, B> GenericCompiler<T, B> {
         self
     }
 
-    fn compile_fhe(&self) -> Result<HashMap<String, CompiledFheProgram>> {
+    fn compile_fhe(&self) -> Result<(HashMap<String, CompiledFheProgram>, Option<ParamsReport>)> {
         let fhe_data: &FheCompilerData = self.data.fhe_data();
 
         if fhe_data.fhe_program_fns.is_empty() {
-            return Ok(HashMap::new());
+            return Ok((HashMap::new(), None));
         }
 
         // Check that all programs use the same scheme type.
@@ -396,31 +398,21 @@ impl<T: marker::Fhe, B> GenericCompiler<T, B> {
             return Err(Error::unsupported("Chain count must be greater than zero."));
         }
 
-        // Check that either the max chain count is 1, or that only
-        // one FHE program is specified in the application.
-        // This restriction will be removed in the future.
-        let max_chain = fhe_data
-            .fhe_program_fns
-            .iter()
-            .fold(0, |max, p| usize::max(p.chain_count(), max));
-
-        if max_chain > 1 && fhe_data.fhe_program_fns.len() > 1 {
-            return Err(Error::unsupported(
-                "Cannot chain programs and specify more than one program in the same app.",
-            ));
-        }
-
         let scheme = fhe_data.fhe_program_fns.first().unwrap().scheme_type();
 
-        let params = match &fhe_data.params_mode {
-            ParamsMode::Manual(p) => p.clone(),
-            ParamsMode::Search => determine_params(
-                &fhe_data.fhe_program_fns,
-                fhe_data.plain_modulus_constraint,
-                fhe_data.security_level,
-                fhe_data.noise_margin,
-                scheme,
-            )?,
+        let (params, params_report) = match &fhe_data.params_mode {
+            ParamsMode::Manual(p) => (p.clone(), None),
+            ParamsMode::Search => {
+                let (params, report) = determine_params(
+                    &fhe_data.fhe_program_fns,
+                    fhe_data.plain_modulus_constraint,
+                    fhe_data.security_level,
+                    fhe_data.noise_margin,
+                    scheme,
+                )?;
+
+                (params, Some(report))
+            }
         };
 
         let fhe_programs = fhe_data
@@ -429,7 +421,7 @@ impl<T: marker::Fhe, B> GenericCompiler<T, B> {
             .map(|prog| {
                 let execution_graph = prog.build(&params);
                 let mut required_keys = vec![];
-                let fhe_program_fn = execution_graph?.compile();
+                let fhe_program_fn = execution_graph?.compile_with_params(&params);
 
                 if fhe_program_fn.requires_relin_keys() {
                     required_keys.push(RequiredKeys::Relin);
@@ -454,7 +446,7 @@ impl<T: marker::Fhe, B> GenericCompiler<T, B> {
             })
             .collect::<Result<HashMap<_, _>>>()?;
 
-        Ok(fhe_programs)
+        Ok((fhe_programs, params_report))
     }
 }
 
@@ -622,7 +614,9 @@ impl FheCompiler {
      * will return a [`Error::NameCollision`] error.
      */
     pub fn compile(self) -> Result<Application<Fhe>> {
-        Application::new(self.compile_fhe()?, HashMap::new())
+        let (fhe_programs, params_report) = self.compile_fhe()?;
+
+        Ok(Application::new(fhe_programs, HashMap::new())?.with_params_report(params_report))
     }
 }
 
@@ -695,7 +689,7 @@ where
      * will return a [`Error::NameCollision`] error.
      */
     pub fn compile(self) -> Result<Application<FheZkp>> {
-        let fhe_programs = self.compile_fhe()?;
+        let (fhe_programs, params_report) = self.compile_fhe()?;
         let params = fhe_programs
             .values()
             .next()
@@ -705,7 +699,7 @@ where
                 ParamsMode::Manual(p) => Some(p),
             });
         let zkp_programs = self.compile_zkp(params)?;
-        Application::new(fhe_programs, zkp_programs)
+        Ok(Application::new(fhe_programs, zkp_programs)?.with_params_report(params_report))
     }
 }
 
@@ -0,0 +1,48 @@
+use sunscreen_backend::noise_model::{predict_noise_budgets, CanonicalEmbeddingNormModel};
+use sunscreen_runtime::{
+    marker, CompiledFheProgram, FheProgramInput, GenericRuntime, PrivateKey, PublicKey, RunOptions,
+    RunOutput,
+};
+
+use crate::{Error, Result};
+
+/**
+ * The same as [`run_debug`](GenericRuntime::run_debug), but also fills in
+ * the noise budget the [`CanonicalEmbeddingNormModel`] predicts for each
+ * node of the returned [`NoiseReport`](crate::NoiseReport), so you can see
+ * where the measured noise departs from the compiler's expectations.
+ *
+ * # Remarks
+ * Like [`run_debug`](GenericRuntime::run_debug), this needs the private
+ * key and is for debugging only. Returns
+ * [`Error::SealEncryptionParameterError`] if the noise model can't be built
+ * under the program's parameters.
+ */
+pub fn run_debug_with_predictions<T, B, I>(
+    runtime: &GenericRuntime<T, B>,
+    fhe_program: &CompiledFheProgram,
+    arguments: Vec<I>,
+    public_key: &PublicKey,
+    private_key: &PrivateKey,
+    options: &RunOptions,
+) -> Result<RunOutput>
+where
+    T: marker::Fhe,
+    I: Into<FheProgramInput>,
+{
+    // Build the model first so bad parameters fail before the slow run
+    // rather than silently leaving the report without predictions.
+    let model = CanonicalEmbeddingNormModel::new(&fhe_program.metadata.params)
+        .map_err(|_| Error::SealEncryptionParameterError)?;
+
+    let mut output = runtime.run_debug(fhe_program, arguments, public_key, private_key, options)?;
+
+    if let Some(report) = output.noise_report.as_mut() {
+        report.set_predicted_noise_budgets(&predict_noise_budgets(
+            &model,
+            &fhe_program.fhe_program_fn,
+        ));
+    }
+
+    Ok(output)
+}
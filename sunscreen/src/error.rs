@@ -1,4 +1,7 @@
 use static_assertions::const_assert;
+use sunscreen_compiler_common::SourceLocation;
+
+use crate::ParamsCandidate;
 
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 /**
@@ -14,10 +17,35 @@ pub enum Error {
 
     /**
      * No parameters were found that satisfy the given FHE program.
+     *
+     * # Remarks
+     * The parameter search reports failures as [`Error::AllParamsRejected`]
+     * or [`Error::NoiseBudgetExceeded`], which include the rejected
+     * candidates.
      */
     #[error("Failed to find satisfying parameters")]
     NoParams,
 
+    /**
+     * The parameter search rejected every candidate for reasons other than
+     * noise. Contains every candidate and why it was rejected.
+     */
+    #[error("Failed to find satisfying parameters")]
+    AllParamsRejected(Box<Vec<ParamsCandidate>>),
+
+    /**
+     * No parameters give an FHE program enough noise budget. Contains the
+     * program's name, where in its source the noise budget first runs out
+     * under the largest parameters tried (if known), and every candidate the
+     * parameter search rejected.
+     */
+    #[error(
+        "Failed to find parameters with enough noise budget for {}{}",
+        .0 .0,
+        at_source(&.0 .1)
+    )]
+    NoiseBudgetExceeded(Box<(String, Option<SourceLocation>, Vec<ParamsCandidate>)>),
+
     /**
      * Attempted to compile the given FHE program with the wrong scheme.
      */
@@ -88,6 +116,42 @@ impl Error {
     pub fn unsupported(msg: &str) -> Self {
         Self::Unsupported(Box::new(msg.to_owned()))
     }
+
+    /**
+     * Create an [`Error::AllParamsRejected`]
+     */
+    pub fn all_params_rejected(candidates: Vec<ParamsCandidate>) -> Self {
+        Self::AllParamsRejected(Box::new(candidates))
+    }
+
+    /**
+     * Create an [`Error::NoiseBudgetExceeded`]
+     */
+    pub fn noise_budget_exceeded(
+        program: &str,
+        source: Option<SourceLocation>,
+        candidates: Vec<ParamsCandidate>,
+    ) -> Self {
+        Self::NoiseBudgetExceeded(Box::new((program.to_owned(), source, candidates)))
+    }
+
+    /**
+     * The candidates the parameter search rejected, if this error means
+     * no parameters suit the FHE programs.
+     */
+    pub fn params_candidates(&self) -> Option<&[ParamsCandidate]> {
+        match self {
+            Self::AllParamsRejected(x) => Some(x.as_slice()),
+            Self::NoiseBudgetExceeded(x) => Some(x.2.as_slice()),
+            _ => None,
+        }
+    }
+}
+
+fn at_source(source: &Option<SourceLocation>) -> String {
+    source
+        .map(|x| format!(" (exceeded at {x})"))
+        .unwrap_or_default()
 }
 
 /**
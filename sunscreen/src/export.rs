@@ -0,0 +1,38 @@
+use petgraph::stable_graph::NodeIndex;
+use sunscreen_backend::noise_model::{predict_noise_budgets, CanonicalEmbeddingNormModel};
+use sunscreen_compiler_common::ExportedGraph;
+use sunscreen_fhe_program::{OutputType, OutputTypeTrait};
+use sunscreen_runtime::CompiledFheProgram;
+
+/**
+ * Exports the given compiled FHE program's graph for viewing in external
+ * tooling. In addition to the annotations
+ * [`export`](sunscreen_compiler_common::CompilationResult::export) makes,
+ * each ciphertext node gets the noise budget the
+ * [`CanonicalEmbeddingNormModel`] predicts for it under the program's
+ * parameters.
+ *
+ * # Panics
+ * Panics if the FHE program is not well formed. You should call
+ * [`validate`](sunscreen_fhe_program::FheProgramTrait::validate) on
+ * programs you didn't compile yourself.
+ */
+pub fn export_fhe_program(fhe_program: &CompiledFheProgram) -> ExportedGraph {
+    let ir = &fhe_program.fhe_program_fn;
+    let mut exported = ir.export();
+
+    // The model only rejects degenerate parameters, which a compiled
+    // program can't have. Skip the predictions rather than fail the export
+    // if it does anyway.
+    if let Ok(model) = CanonicalEmbeddingNormModel::new(&fhe_program.metadata.params) {
+        let budgets = predict_noise_budgets(&model, ir);
+
+        for n in exported.nodes.iter_mut() {
+            if ir.graph[NodeIndex::new(n.id)].output_type() == OutputType::Ciphertext {
+                n.predicted_noise_budget = Some(budgets[n.id]);
+            }
+        }
+    }
+
+    exported
+}
@@ -1,6 +1,6 @@
 use petgraph::stable_graph::NodeIndex;
 use serde::{Deserialize, Serialize};
-use sunscreen_backend::compile_inplace;
+use sunscreen_backend::{compile_inplace, compile_inplace_with_params};
 use sunscreen_compiler_common::{
     CompilationResult, Context, EdgeInfo, NodeInfo, Operation as OperationTrait,
 };
@@ -101,6 +101,12 @@ pub enum FheOperation {
      * This node indicates the previous node's result should be a result of the [`fhe_program`](crate::fhe_program).
      */
     Output,
+
+    /**
+     * This node indicates the previous node's plaintext result should be an unencrypted
+     * result of the [`fhe_program`](crate::fhe_program).
+     */
+    PlaintextOutput,
 }
 
 impl OperationTrait for FheOperation {
@@ -259,6 +265,11 @@ pub trait FheContextOps {
      * Add a node that captures the previous node as an output.
      */
     fn add_output(&mut self, i: NodeIndex) -> NodeIndex;
+
+    /**
+     * Add a node that captures the previous node as an unencrypted output.
+     */
+    fn add_plaintext_output(&mut self, i: NodeIndex) -> NodeIndex;
 }
 
 impl FheContextOps for FheContext {
@@ -334,6 +345,10 @@ impl FheContextOps for FheContext {
     fn add_output(&mut self, i: NodeIndex) -> NodeIndex {
         self.add_unary_operation(FheOperation::Output, i)
     }
+
+    fn add_plaintext_output(&mut self, i: NodeIndex) -> NodeIndex {
+        self.add_unary_operation(FheOperation::PlaintextOutput, i)
+    }
 }
 
 /**
@@ -345,59 +360,84 @@ pub trait FheCompile {
      * then perform backend compilation and return the result.
      */
     fn compile(&self) -> FheProgram;
+
+    /**
+     * The same as [`compile`](FheCompile::compile), but lets backend
+     * compilation consult the noise model under the given [`Params`].
+     */
+    fn compile_with_params(&self, params: &Params) -> FheProgram;
 }
 
 impl FheCompile for FheFrontendCompilation {
     fn compile(&self) -> FheProgram {
-        let mut fhe_program = FheProgram::new(SchemeType::Bfv);
-
-        let mapped_graph = self.0.map(
-            |id, n| match &n.operation {
-                FheOperation::Add => NodeInfo::new(FheProgramOperation::Add),
-                FheOperation::InputCiphertext => {
-                    // HACKHACK: Input nodes are always added first to the graph in the order
-                    // they're specified as function arguments. We should not depend on this.
-                    NodeInfo::new(FheProgramOperation::InputCiphertext(id.index()))
-                }
-                FheOperation::InputPlaintext => {
-                    // HACKHACK: Input nodes are always added first to the graph in the order
-                    // they're specified as function arguments. We should not depend on this.
-                    NodeInfo::new(FheProgramOperation::InputPlaintext(id.index()))
-                }
-                FheOperation::Literal(Literal::U64(x)) => {
-                    NodeInfo::new(FheProgramOperation::Literal(FheProgramLiteral::U64(*x)))
-                }
-                FheOperation::Literal(Literal::Plaintext(x)) => {
-                    // It's okay to unwrap here because fhe_program compilation will
-                    // catch the panic and return a compilation error.
-                    NodeInfo::new(FheProgramOperation::Literal(FheProgramLiteral::Plaintext(
-                        x.to_bytes().expect("Failed to serialize plaintext."),
-                    )))
-                }
-                FheOperation::Sub => NodeInfo::new(FheProgramOperation::Sub),
-                FheOperation::SubPlaintext => NodeInfo::new(FheProgramOperation::SubPlaintext),
-                FheOperation::Negate => NodeInfo::new(FheProgramOperation::Negate),
-                FheOperation::Multiply => NodeInfo::new(FheProgramOperation::Multiply),
-                FheOperation::MultiplyPlaintext => {
-                    NodeInfo::new(FheProgramOperation::MultiplyPlaintext)
-                }
-                FheOperation::Output => NodeInfo::new(FheProgramOperation::OutputCiphertext),
-                FheOperation::RotateLeft => NodeInfo::new(FheProgramOperation::ShiftLeft),
-                FheOperation::RotateRight => NodeInfo::new(FheProgramOperation::ShiftRight),
-                FheOperation::SwapRows => NodeInfo::new(FheProgramOperation::SwapRows),
-                FheOperation::AddPlaintext => NodeInfo::new(FheProgramOperation::AddPlaintext),
-            },
-            |_, e| match e {
-                EdgeInfo::Left => EdgeInfo::Left,
-                EdgeInfo::Right => EdgeInfo::Right,
-                EdgeInfo::Unary => EdgeInfo::Unary,
-                EdgeInfo::Unordered => unreachable!("FHE programs have no unordered edges."),
-                EdgeInfo::Ordered(_) => unreachable!("FHE programs have no ordered edges."),
-            },
-        );
-
-        fhe_program.graph = CompilationResult(mapped_graph);
-
-        compile_inplace(fhe_program)
+        compile_inplace(to_fhe_program(self))
+    }
+
+    fn compile_with_params(&self, params: &Params) -> FheProgram {
+        compile_inplace_with_params(to_fhe_program(self), params)
+    }
+}
+
+/**
+ * Lowers the given intermediate representation into a backend [`FheProgram`]
+ * without running any backend transformations.
+ */
+fn to_fhe_program(ir: &FheFrontendCompilation) -> FheProgram {
+    let mut fhe_program = FheProgram::new(SchemeType::Bfv);
+
+    let mut mapped_graph = ir.0.map(
+        |id, n| match &n.operation {
+            FheOperation::Add => NodeInfo::new(FheProgramOperation::Add),
+            FheOperation::InputCiphertext => {
+                // HACKHACK: Input nodes are always added first to the graph in the order
+                // they're specified as function arguments. We should not depend on this.
+                NodeInfo::new(FheProgramOperation::InputCiphertext(id.index()))
+            }
+            FheOperation::InputPlaintext => {
+                // HACKHACK: Input nodes are always added first to the graph in the order
+                // they're specified as function arguments. We should not depend on this.
+                NodeInfo::new(FheProgramOperation::InputPlaintext(id.index()))
+            }
+            FheOperation::Literal(Literal::U64(x)) => {
+                NodeInfo::new(FheProgramOperation::Literal(FheProgramLiteral::U64(*x)))
+            }
+            FheOperation::Literal(Literal::Plaintext(x)) => {
+                // It's okay to unwrap here because fhe_program compilation will
+                // catch the panic and return a compilation error.
+                NodeInfo::new(FheProgramOperation::Literal(FheProgramLiteral::Plaintext(
+                    x.to_bytes().expect("Failed to serialize plaintext."),
+                )))
+            }
+            FheOperation::Sub => NodeInfo::new(FheProgramOperation::Sub),
+            FheOperation::SubPlaintext => NodeInfo::new(FheProgramOperation::SubPlaintext),
+            FheOperation::Negate => NodeInfo::new(FheProgramOperation::Negate),
+            FheOperation::Multiply => NodeInfo::new(FheProgramOperation::Multiply),
+            FheOperation::MultiplyPlaintext => {
+                NodeInfo::new(FheProgramOperation::MultiplyPlaintext)
+            }
+            FheOperation::Output => NodeInfo::new(FheProgramOperation::OutputCiphertext),
+            FheOperation::PlaintextOutput => NodeInfo::new(FheProgramOperation::OutputPlaintext),
+            FheOperation::RotateLeft => NodeInfo::new(FheProgramOperation::ShiftLeft),
+            FheOperation::RotateRight => NodeInfo::new(FheProgramOperation::ShiftRight),
+            FheOperation::SwapRows => NodeInfo::new(FheProgramOperation::SwapRows),
+            FheOperation::AddPlaintext => NodeInfo::new(FheProgramOperation::AddPlaintext),
+        },
+        |_, e| match e {
+            EdgeInfo::Left => EdgeInfo::Left,
+            EdgeInfo::Right => EdgeInfo::Right,
+            EdgeInfo::Unary => EdgeInfo::Unary,
+            EdgeInfo::Unordered => unreachable!("FHE programs have no unordered edges."),
+            EdgeInfo::Ordered(_) => unreachable!("FHE programs have no ordered edges."),
+        },
+    );
+
+    // Mapping preserves node indices, so carry each node's source location
+    // over for diagnostics.
+    for id in ir.0.node_indices() {
+        mapped_graph[id].source = ir.0[id].source;
     }
+
+    fhe_program.graph = CompilationResult(mapped_graph);
+
+    fhe_program
 }
@@ -37,8 +37,11 @@
 //!
 
 mod compiler;
+mod debug;
 mod error;
+mod export;
 mod params;
+mod params_report;
 
 #[cfg(feature = "linkedproofs")]
 pub mod linked;
@@ -58,18 +61,26 @@ use sunscreen_runtime::{marker, Fhe, FheZkp, Zkp};
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::marker::PhantomData;
+use std::path::Path;
 
 pub use compiler::{Compiler, FheProgramFn, FheProgramFnExt, GenericCompiler};
+pub use debug::run_debug_with_predictions;
 pub use error::{Error, Result};
+pub use export::export_fhe_program;
 pub use params::PlainModulusConstraint;
+pub use params_report::*;
 pub use seal_fhe::Plaintext as SealPlaintext;
+pub use sunscreen_compiler_common::{ExportedEdge, ExportedGraph, ExportedNode, SourceLocation};
 pub use sunscreen_compiler_macros::*;
 pub use sunscreen_fhe_program::{SchemeType, SecurityLevel};
 pub use sunscreen_runtime::{
-    CallSignature, Ciphertext, CompiledFheProgram, CompiledZkpProgram, Error as RuntimeError,
-    FheProgramInput, FheProgramInputTrait, FheProgramMetadata, FheRuntime, FheZkpRuntime,
-    InnerCiphertext, InnerPlaintext, Params, Plaintext, PrivateKey, ProofBuilder, PublicKey,
-    RequiredKeys, Runtime, VerificationBuilder, WithContext, ZkpProgramInput, ZkpRuntime,
+    ApplicationArtifact, CallSignature, CancellationToken, Ciphertext, CompiledFheProgram,
+    CompiledZkpProgram, CostEstimate, Error as RuntimeError, FheProgramInput, FheProgramInputTrait,
+    FheProgramMetadata, FheProgramOutput, FheRunHandle, FheRuntime, FheZkpRuntime, InnerCiphertext,
+    InnerPlaintext, KeySwitchingKey, NodeNoiseBudget, NodeTiming, NoiseReport, OperationCounts,
+    OperationTiming, OperationTimings, Params, Plaintext, PrivateKey, ProofBuilder, PublicKey,
+    RequiredKeys, RunOptions, RunOutput, RunProgress, RunTrace, Runtime, TimingsProvenance,
+    VerificationBuilder, WithContext, ZkpProgramInput, ZkpRuntime,
 };
 #[cfg(feature = "bulletproofs")]
 pub use sunscreen_zkp_backend::bulletproofs;
@@ -83,6 +94,7 @@ pub use zkp::{invoke_gadget, ZkpProgramFn, ZkpProgramFnExt};
 pub struct Application<T> {
     fhe_programs: HashMap<String, CompiledFheProgram>,
     zkp_programs: HashMap<String, CompiledZkpProgram>,
+    params_report: Option<ParamsReport>,
     _phantom: PhantomData<T>,
 }
 
@@ -110,9 +122,15 @@ impl<T> Application<T> {
         Ok(Self {
             fhe_programs,
             zkp_programs,
+            params_report: None,
             _phantom: PhantomData,
         })
     }
+
+    fn with_params_report(mut self, params_report: Option<ParamsReport>) -> Self {
+        self.params_report = params_report;
+        self
+    }
 }
 
 impl<T> Application<T>
@@ -130,6 +148,20 @@ where
         &self.fhe_programs.values().next().unwrap().metadata.params
     }
 
+    /**
+     * Returns a [`ParamsReport`] explaining how the compiler chose this
+     * application's [`Params`].
+     *
+     * # Remarks
+     * Returns [`None`] if the parameters weren't searched for, i.e. they
+     * were given with
+     * [`with_params`](crate::GenericCompiler::with_params) or this
+     * application was deserialized.
+     */
+    pub fn params_report(&self) -> Option<&ParamsReport> {
+        self.params_report.as_ref()
+    }
+
     #[deprecated]
     /**
      * Gets the [`CompiledFheProgram`] with the given name or [`None`] if not present.
@@ -169,6 +201,63 @@ where
         self.fhe_programs.iter()
     }
 
+    /**
+     * Serialize this application into a versioned [`ApplicationArtifact`] that can be loaded
+     * with [`from_bytes`](Self::from_bytes) or
+     * [`ApplicationArtifact::from_bytes`] without the compiler.
+     *
+     * # Remarks
+     * ZKP programs may contain gadgets, which can't be serialized. As such, this function
+     * returns [`Error::Unsupported`] if this application contains any ZKP programs.
+     */
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(self.to_artifact()?.to_bytes()?)
+    }
+
+    /**
+     * Deserialize an application serialized with [`to_bytes`](Self::to_bytes).
+     *
+     * # Remarks
+     * Fails if the artifact's format version doesn't match this version of Sunscreen, if any
+     * program's graph doesn't match the hash stored alongside it, or if any program's
+     * parameters differ from the application's.
+     */
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::from_artifact(ApplicationArtifact::from_bytes(bytes)?)
+    }
+
+    /**
+     * Serialize this application with [`to_bytes`](Self::to_bytes) and write it to the
+     * given file.
+     */
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        Ok(self.to_artifact()?.save(path)?)
+    }
+
+    /**
+     * Read an application written with [`save`](Self::save) from the given file.
+     */
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_artifact(ApplicationArtifact::load(path)?)
+    }
+
+    fn to_artifact(&self) -> Result<ApplicationArtifact> {
+        if !self.zkp_programs.is_empty() {
+            return Err(Error::unsupported(
+                "Serializing applications containing ZKP programs",
+            ));
+        }
+
+        Ok(ApplicationArtifact::new(
+            self.params(),
+            self.fhe_programs.clone(),
+        )?)
+    }
+
+    fn from_artifact(artifact: ApplicationArtifact) -> Result<Self> {
+        Self::new(artifact.into_fhe_programs(), HashMap::new())
+    }
+
     /// Take ownership of a compiled program with the given name, removing it from this
     /// `Application`.
     ///
@@ -1,14 +1,23 @@
-use crate::{fhe::FheCompile, Error, FheProgramFn, Result, SecurityLevel};
+use crate::{
+    fhe::FheCompile,
+    params_report::{critical_path, ParamsCandidate, ParamsRejection, ProgramParamsReport},
+    Error, FheProgramFn, ParamsReport, Result, SecurityLevel,
+};
 
 use log::{debug, trace};
 
+use std::collections::{hash_map::Entry, HashMap};
+
+use petgraph::{stable_graph::NodeIndex, Direction};
 use seal_fhe::{
     BfvEncryptionParametersBuilder, CoefficientModulus, Context, KeyGenerator, Modulus,
     PlainModulus,
 };
 use sunscreen_backend::noise_model::{
-    noise_budget_to_noise, predict_noise, MeasuredModel, TargetNoiseLevel,
+    noise_budget_to_noise, noise_to_noise_budget, predict_noise, predict_noise_budgets,
+    MeasuredModel, NoiseModel, TargetNoiseLevel,
 };
+use sunscreen_compiler_common::SourceLocation;
 use sunscreen_fhe_program::{FheProgram, FheProgramTrait, Operation, SchemeType};
 pub use sunscreen_runtime::Params;
 
@@ -37,6 +46,12 @@ pub enum PlainModulusConstraint {
 const LATTICE_DIMENSIONS: &[u64] = &[1024, 2048, 4096, 8192, 16384, 32768];
 const BATCHING_MIN_BITS: &[u32] = &[14, 14, 16, 17, 17, 17];
 
+/**
+ * The most combinations of run counts [`determine_params`] will consider
+ * when chaining, i.e. the product of each program's `chain_count` plus one.
+ */
+const MAX_CHAIN_COMBINATIONS: usize = 4096;
+
 /**
  * Returns a plaintext modulus that satisfies the given
  * PlainModulusConstraint and lattice dimension.
@@ -112,9 +127,90 @@ fn can_make_required_keys(fhe_program: &FheProgram, params: &Params) -> Result<b
     Ok(create_galois && create_relin)
 }
 
+/**
+ * Returns where in its source the given FHE program first runs out of
+ * noise budget, i.e. the earliest node whose predicted noise budget falls
+ * below `noise_margin_bits` while its operands' budgets don't.
+ */
+fn find_noise_budget_overrun(
+    model: &(dyn NoiseModel + Sync),
+    ir: &FheProgram,
+    noise_margin_bits: u32,
+) -> Option<SourceLocation> {
+    let budgets = predict_noise_budgets(model, ir);
+    let exceeded = |id: NodeIndex| budgets[id.index()] < noise_margin_bits as f64;
+
+    ir.graph
+        .node_indices()
+        .filter(|id| {
+            exceeded(*id)
+                && !ir
+                    .graph
+                    .neighbors_directed(*id, Direction::Incoming)
+                    .any(exceeded)
+        })
+        .find_map(|id| ir.graph[id].source)
+}
+
+/**
+ * Returns the noise level of each of the given FHE program's inputs when its
+ * ciphertext inputs have `chain_noise_level` noise, or are fresh if this is
+ * zero.
+ */
+fn noise_targets(ir: &FheProgram, chain_noise_level: f64) -> Vec<TargetNoiseLevel> {
+    ir.graph
+        .node_weights()
+        .filter(|n| {
+            matches!(
+                n.operation,
+                Operation::InputCiphertext(_) | Operation::InputPlaintext(_)
+            )
+        })
+        .map(|n| match n.operation {
+            Operation::InputCiphertext(_) => {
+                if chain_noise_level == 0f64 {
+                    TargetNoiseLevel::Fresh
+                } else {
+                    TargetNoiseLevel::InvariantNoise(chain_noise_level)
+                }
+            }
+            Operation::InputPlaintext(_) => TargetNoiseLevel::NotApplicable,
+            _ => unreachable!(),
+        })
+        .collect()
+}
+
 /**
  * Determines the minimal parameters required to satisfy the noise constraint for
  * the given FHE program and plaintext modulo and security level.
+ *
+ * # Remarks
+ * If no program declares a `chain_count` greater than 1, each program only needs to
+ * support freshly encrypted inputs. Otherwise, any program's outputs may be fed to any
+ * program in the application, so the returned parameters support any chain of runs in
+ * which each program runs at most its `chain_count` times.
+ *
+ * To find the noisiest inputs each program may receive, we track the most noise a
+ * ciphertext can have after each combination of run counts. As noise grows with the
+ * noise in a program's inputs, this is the most noise any program's outputs have after
+ * one fewer run of that program. The number of combinations is the product of each
+ * program's `chain_count` plus one; when chaining, this returns [`Error::Unsupported`]
+ * if there are more than `MAX_CHAIN_COMBINATIONS`. Combinations with the same noise
+ * share one noise model.
+ *
+ * Alongside the parameters, this returns a [`ParamsReport`] explaining why
+ * smaller parameters were rejected.
+ *
+ * When noise is why the largest parameters don't work, this returns
+ * [`Error::NoiseBudgetExceeded`] rather than [`Error::AllParamsRejected`].
+ * Either way, the error contains the rejected candidates.
+ *
+ * This doesn't take a cost objective. Candidates are tried in order of
+ * increasing lattice dimension, each with SEAL's default coefficient modulus,
+ * and every operation costs more at a larger dimension, so the first
+ * candidate that works is also the cheapest under any
+ * [`OperationTimings`](crate::OperationTimings). An objective would only
+ * matter if the search tried several coefficient moduli per dimension.
  */
 pub fn determine_params(
     fhe_program_fns: &[Box<dyn FheProgramFn>],
@@ -122,17 +218,60 @@ pub fn determine_params(
     security_level: SecurityLevel,
     noise_margin_bits: u32,
     scheme_type: SchemeType,
-) -> Result<Params> {
+) -> Result<(Params, ParamsReport)> {
+    let max_chain = fhe_program_fns
+        .iter()
+        .fold(0, |max, p| usize::max(p.chain_count(), max));
+
+    // The most times each program may run in a chain.
+    let chain_counts = fhe_program_fns
+        .iter()
+        .map(|p| if max_chain > 1 { p.chain_count() } else { 1 })
+        .collect::<Vec<_>>();
+
+    let chain_length = if max_chain > 1 {
+        chain_counts.iter().sum()
+    } else {
+        1
+    };
+
+    let chain_combinations = chain_counts
+        .iter()
+        .try_fold(1usize, |product, c| product.checked_mul(c + 1));
+
+    // Without chaining, each program only runs once on fresh inputs, so
+    // there are only ever as many combinations as programs.
+    if max_chain > 1 && !matches!(chain_combinations, Some(x) if x <= MAX_CHAIN_COMBINATIONS) {
+        return Err(Error::unsupported(
+            "Too many combinations of chain counts to search.",
+        ));
+    }
+
+    let mut noise_overrun = None;
+    let mut candidates = vec![];
+
     'params_loop: for (i, n) in LATTICE_DIMENSIONS.iter().enumerate() {
         // Select a plain modulus that meets needs of the passed
         // constraint.
         let plaintext_modulus = match plaintext_constraint_to_modulus(plaintext_constraint, i) {
             Ok(v) => v,
             Err(_) => {
+                candidates.push(ParamsCandidate {
+                    lattice_dimension: *n,
+                    plain_modulus: None,
+                    rejection: Some(ParamsRejection::PlainModulus),
+                });
+
                 continue 'params_loop;
             }
         };
 
+        let candidate = |rejection| ParamsCandidate {
+            lattice_dimension: *n,
+            plain_modulus: Some(plaintext_modulus.value()),
+            rejection,
+        };
+
         // Tell SEAL to give us whatever modulus chain it finds suitable.
         let coeff = CoefficientModulus::bfv_default(*n, security_level).unwrap();
 
@@ -152,85 +291,155 @@ pub fn determine_params(
             n
         );
 
+        let mut irs = Vec::with_capacity(fhe_program_fns.len());
+
         for program in fhe_program_fns {
             trace!("Successfully created parameters.");
             trace!("Running backend compilation for {}", program.name());
-            let ir = program.build(&params)?.compile();
+            let ir = program.build(&params)?.compile_with_params(&params);
 
             ir.validate().map_err(Error::FheProgramError)?;
             trace!("Built and validated {}", program.name());
 
-            match can_make_required_keys(&ir, &params) {
-                Ok(can_make_keys) => {
-                    if !can_make_keys {
-                        continue 'params_loop;
-                    }
-                }
-                Err(_) => {
-                    continue 'params_loop;
-                }
-            };
+            if !can_make_required_keys(&ir, &params).unwrap_or(false) {
+                candidates.push(candidate(Some(ParamsRejection::KeyGeneration(
+                    program.name().to_owned(),
+                ))));
 
-            let mut chain_noise_level = 0f64;
+                continue 'params_loop;
+            }
 
-            for _ in 0..program.chain_count() {
-                let noise_targets = ir
-                    .graph
-                    .node_weights()
-                    .filter(|n| {
-                        matches!(
-                            n.operation,
-                            Operation::InputCiphertext(_) | Operation::InputPlaintext(_)
-                        )
-                    })
-                    .map(|n| match n.operation {
-                        Operation::InputCiphertext(_) => {
-                            if chain_noise_level == 0f64 {
-                                TargetNoiseLevel::Fresh
-                            } else {
-                                TargetNoiseLevel::InvariantNoise(chain_noise_level)
+            irs.push((program, ir));
+        }
+
+        // The most noise a ciphertext may have after each combination of the
+        // number of times each program has run, starting with fresh ciphertexts.
+        let mut chain_noise_levels = HashMap::from([(vec![0; irs.len()], 0f64)]);
+
+        // The output noise budgets of each program given the noisiest inputs it
+        // may receive, along with that input noise.
+        let mut output_noise_budgets = vec![(0f64, vec![]); irs.len()];
+
+        // Predicted output noise of each program by input noise level, as many
+        // combinations share the same noise.
+        let mut output_noise_cache = HashMap::new();
+
+        for _ in 0..chain_length {
+            let mut next_noise_levels = HashMap::new();
+
+            for (runs, chain_noise_level) in chain_noise_levels {
+                for (i, (program, ir)) in irs.iter().enumerate() {
+                    if runs[i] >= chain_counts[i] {
+                        continue;
+                    }
+
+                    let output_noises = match output_noise_cache
+                        .entry((i, chain_noise_level.to_bits()))
+                    {
+                        Entry::Occupied(e) => e.into_mut(),
+                        Entry::Vacant(e) => {
+                            let noise_targets = noise_targets(ir, chain_noise_level);
+
+                            let model = match MeasuredModel::new(ir, &params, &noise_targets) {
+                                Ok(v) => v,
+                                Err(_) => {
+                                    trace!(
+                                        "Failed to construct noise model for {} with lattice_dimension={}",
+                                        program.name(),
+                                        n
+                                    );
+                                    candidates.push(candidate(Some(ParamsRejection::NoiseModel(
+                                        program.name().to_owned(),
+                                    ))));
+
+                                    continue 'params_loop;
+                                }
+                            };
+
+                            let output_noises = predict_noise(&model, ir);
+
+                            let target_noise = noise_budget_to_noise(noise_margin_bits as f64);
+
+                            if let Some(output_noise) =
+                                output_noises.iter().find(|x| **x > target_noise)
+                            {
+                                trace!(
+                                    "Failed to meet noise constraints with lattice dimension {} for program {}",
+                                    n,
+                                    program.name()
+                                );
+
+                                let source =
+                                    find_noise_budget_overrun(&model, ir, noise_margin_bits);
+                                let output_noise_budget =
+                                    noise_to_noise_budget(*output_noise).floor() as i32;
+                                noise_overrun = Some((program.name().to_owned(), source));
+
+                                candidates.push(candidate(Some(ParamsRejection::NoiseBudget {
+                                    program: program.name().to_owned(),
+                                    output_noise_budget,
+                                    source,
+                                })));
+
+                                continue 'params_loop;
                             }
+
+                            e.insert(output_noises)
                         }
-                        Operation::InputPlaintext(_) => TargetNoiseLevel::NotApplicable,
-                        _ => unreachable!(),
-                    })
-                    .collect::<Vec<TargetNoiseLevel>>();
-
-                let model = match MeasuredModel::new(&ir, &params, &noise_targets) {
-                    Ok(v) => v,
-                    Err(_) => {
-                        trace!(
-                            "Failed to construct noise model for {} with lattice_dimension={}",
-                            program.name(),
-                            n
+                    };
+
+                    if chain_noise_level >= output_noise_budgets[i].0 {
+                        output_noise_budgets[i] = (
+                            chain_noise_level,
+                            output_noises
+                                .iter()
+                                .map(|x| noise_to_noise_budget(*x))
+                                .collect(),
                         );
-                        continue 'params_loop;
                     }
-                };
 
-                let output_noises = predict_noise(&model, &ir);
+                    let output_noise = output_noises
+                        .iter()
+                        .fold(chain_noise_level, |max, x| f64::max(max, *x));
 
-                let target_noise = noise_budget_to_noise(noise_margin_bits as f64);
+                    let mut next_runs = runs.clone();
+                    next_runs[i] += 1;
 
-                for output_noise in output_noises {
-                    if output_noise > target_noise {
-                        trace!(
-                            "Failed to meet noise constraints with lattice dimension {} for program {}",
-                            n,
-                            program.name()
-                        );
-                        continue 'params_loop;
-                    } else if output_noise > chain_noise_level {
-                        chain_noise_level = output_noise
-                    }
+                    let next_noise_level = next_noise_levels.entry(next_runs).or_insert(0f64);
+                    *next_noise_level = f64::max(*next_noise_level, output_noise);
                 }
             }
 
-            debug!("Using params lattice_dimension={} and ={:#?}", n, coeff);
+            chain_noise_levels = next_noise_levels;
         }
 
-        return Ok(params);
+        debug!("Using params lattice_dimension={} and ={:#?}", n, coeff);
+
+        candidates.push(candidate(None));
+
+        let programs = irs
+            .iter()
+            .zip(output_noise_budgets)
+            .map(
+                |((program, ir), (_, output_noise_budgets))| ProgramParamsReport {
+                    name: program.name().to_owned(),
+                    output_noise_budgets,
+                    critical_path: critical_path(ir),
+                },
+            )
+            .collect();
+
+        return Ok((
+            params,
+            ParamsReport {
+                candidates,
+                programs,
+            },
+        ));
     }
 
-    Err(Error::NoParams)
+    Err(match noise_overrun {
+        Some((program, source)) => Error::noise_budget_exceeded(&program, source, candidates),
+        None => Error::all_params_rejected(candidates),
+    })
 }
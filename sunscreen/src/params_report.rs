@@ -0,0 +1,320 @@
+use std::fmt::Display;
+
+use petgraph::{algo::toposort, stable_graph::NodeIndex, visit::NodeIndexable, Direction};
+use serde::Serialize;
+use sunscreen_compiler_common::SourceLocation;
+use sunscreen_fhe_program::{FheProgram, Operation};
+
+use crate::{Result, RuntimeError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+/**
+ * Why the parameter search rejected a candidate lattice dimension.
+ */
+pub enum ParamsRejection {
+    /**
+     * No plaintext modulus satisfying the
+     * [`PlainModulusConstraint`](crate::PlainModulusConstraint) exists for
+     * this lattice dimension. Batching, for example, needs a plaintext
+     * modulus larger than the smallest lattice dimensions allow.
+     */
+    PlainModulus,
+
+    /**
+     * The keys the named FHE program requires (e.g. relinearization or
+     * Galois keys) can't be generated under these parameters.
+     */
+    KeyGeneration(String),
+
+    /**
+     * The noise model couldn't be built for the named FHE program under
+     * these parameters.
+     */
+    NoiseModel(String),
+
+    /**
+     * An output of an FHE program has less noise budget than the
+     * requested margin.
+     */
+    NoiseBudget {
+        /**
+         * The FHE program's name.
+         */
+        program: String,
+
+        /**
+         * The predicted noise budget of the offending output, in whole
+         * bits rounded down.
+         */
+        output_noise_budget: i32,
+
+        /**
+         * Where in the program's source the noise budget first runs out,
+         * if known.
+         */
+        source: Option<SourceLocation>,
+    },
+}
+
+impl Display for ParamsRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PlainModulus => write!(f, "no plaintext modulus satisfies the constraint"),
+            Self::KeyGeneration(p) => write!(f, "can't generate the keys {p} requires"),
+            Self::NoiseModel(p) => write!(f, "can't build a noise model for {p}"),
+            Self::NoiseBudget {
+                program,
+                output_noise_budget,
+                source,
+            } => {
+                write!(
+                    f,
+                    "an output of {program} has {output_noise_budget} bits of noise budget"
+                )?;
+
+                if let Some(source) = source {
+                    write!(f, " (exceeded at {source})")?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+/**
+ * A set of parameters the parameter search considered.
+ */
+pub struct ParamsCandidate {
+    /**
+     * The candidate's lattice dimension.
+     */
+    pub lattice_dimension: u64,
+
+    /**
+     * The candidate's plaintext modulus, or [`None`] if none satisfies
+     * the plaintext modulus constraint.
+     */
+    pub plain_modulus: Option<u64>,
+
+    /**
+     * Why the candidate was rejected, or [`None`] if it was chosen.
+     */
+    pub rejection: Option<ParamsRejection>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+/**
+ * A multiplication on an FHE program's critical path.
+ */
+pub struct CriticalPathStep {
+    /**
+     * The index of the multiplication's node in the compiled FHE program.
+     */
+    pub node: usize,
+
+    /**
+     * Where in the program's source the multiplication was written, if
+     * known.
+     */
+    pub source: Option<SourceLocation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+/**
+ * How an FHE program fares under the chosen parameters.
+ */
+pub struct ProgramParamsReport {
+    /**
+     * The FHE program's name.
+     */
+    pub name: String,
+
+    /**
+     * The predicted noise budget (in bits) of each of the program's
+     * outputs. When programs are chained, this is the budget given the
+     * noisiest inputs the program may receive within its `chain_count`.
+     */
+    pub output_noise_budgets: Vec<f64>,
+
+    /**
+     * The ciphertext multiplications on the program's deepest path, in
+     * program order.
+     */
+    pub critical_path: Vec<CriticalPathStep>,
+}
+
+impl ProgramParamsReport {
+    /**
+     * The program's multiplicative depth, i.e. the length of its critical
+     * path.
+     */
+    pub fn multiplicative_depth(&self) -> usize {
+        self.critical_path.len()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+/**
+ * Explains how the compiler chose an application's [`Params`](crate::Params).
+ * Retrieve one with
+ * [`Application::params_report`](crate::Application::params_report).
+ *
+ * # Remarks
+ * Use this to reason about performance trade-offs: shortening a program's
+ * critical path or loosening the noise margin may allow a smaller lattice
+ * dimension.
+ */
+pub struct ParamsReport {
+    /**
+     * Each set of parameters considered, in the order tried. The last is
+     * the one chosen.
+     */
+    pub candidates: Vec<ParamsCandidate>,
+
+    /**
+     * A report for each FHE program under the chosen parameters.
+     */
+    pub programs: Vec<ProgramParamsReport>,
+}
+
+impl ParamsReport {
+    /**
+     * Serializes this report as pretty-printed JSON.
+     */
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self).map_err(RuntimeError::from)?)
+    }
+}
+
+impl Display for ParamsReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Candidates:")?;
+
+        for c in &self.candidates {
+            write!(f, "  n={}", c.lattice_dimension)?;
+
+            if let Some(p) = c.plain_modulus {
+                write!(f, " p={p}")?;
+            }
+
+            match &c.rejection {
+                Some(r) => writeln!(f, ": rejected, {r}")?,
+                None => writeln!(f, ": chosen")?,
+            };
+        }
+
+        writeln!(f, "Programs:")?;
+
+        for p in &self.programs {
+            let budgets = p
+                .output_noise_budgets
+                .iter()
+                .map(|x| format!("{x:.1}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            writeln!(
+                f,
+                "  {}: multiplicative depth {}, output noise budgets [{budgets}] bits",
+                p.name,
+                p.multiplicative_depth()
+            )?;
+
+            for step in &p.critical_path {
+                match step.source {
+                    Some(source) => writeln!(f, "    multiply at {source}")?,
+                    None => writeln!(f, "    multiply (node {})", step.node)?,
+                };
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/**
+ * Returns the ciphertext multiplications on the given FHE program's
+ * deepest path, in program order.
+ */
+pub(crate) fn critical_path(ir: &FheProgram) -> Vec<CriticalPathStep> {
+    let graph = &ir.graph.0;
+
+    // The multiplicative depth after each node and the operand that
+    // determines it.
+    let mut depths = vec![(0usize, None::<NodeIndex>); graph.node_bound()];
+
+    for id in toposort(graph, None).expect("FHE programs are acyclic") {
+        let deepest = graph
+            .neighbors_directed(id, Direction::Incoming)
+            .max_by_key(|x| (depths[x.index()].0, std::cmp::Reverse(x.index())));
+
+        let depth = deepest.map(|x| depths[x.index()].0).unwrap_or(0);
+        let is_multiply = matches!(graph[id].operation, Operation::Multiply);
+
+        depths[id.index()] = (depth + usize::from(is_multiply), deepest);
+    }
+
+    let mut node = graph
+        .node_indices()
+        .min_by_key(|x| (std::cmp::Reverse(depths[x.index()].0), x.index()));
+
+    let mut path = vec![];
+
+    while let Some(id) = node {
+        if matches!(graph[id].operation, Operation::Multiply) {
+            path.push(CriticalPathStep {
+                node: id.index(),
+                source: graph[id].source,
+            });
+        }
+
+        node = depths[id.index()].1;
+    }
+
+    path.reverse();
+
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sunscreen_fhe_program::{FheProgramTrait, SchemeType};
+
+    #[test]
+    fn finds_deepest_multiplications() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let a = ir.add_input_ciphertext(0);
+        let b = ir.add_input_ciphertext(1);
+
+        // (a * b) * (a + b) + a * a
+        let ab = ir.add_multiply(a, b);
+        let sum = ir.add_add(a, b);
+        let abs = ir.add_multiply(ab, sum);
+        let aa = ir.add_multiply(a, a);
+        let out = ir.add_add(abs, aa);
+        ir.add_output_ciphertext(out);
+
+        let path = critical_path(&ir)
+            .into_iter()
+            .map(|x| x.node)
+            .collect::<Vec<_>>();
+
+        assert_eq!(path, vec![ab.index(), abs.index()]);
+    }
+
+    #[test]
+    fn empty_path_without_multiplications() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let a = ir.add_input_ciphertext(0);
+        let b = ir.add_input_ciphertext(1);
+        let sum = ir.add_add(a, b);
+        ir.add_output_ciphertext(sum);
+
+        assert!(critical_path(&ir).is_empty());
+    }
+}
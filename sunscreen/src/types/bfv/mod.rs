@@ -1,11 +1,13 @@
 mod batched;
 mod fractional;
 mod rational;
+mod select;
 mod signed;
 mod unsigned;
 
 pub use batched::*;
 pub use fractional::*;
 pub use rational::*;
+pub use select::*;
 pub use signed::*;
 pub use unsigned::*;
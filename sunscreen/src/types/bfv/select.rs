@@ -0,0 +1,75 @@
+use crate::{
+    fhe::{with_fhe_ctx, FheContextOps},
+    types::{bfv::Signed, intern::FheProgramNode, BfvType, Cipher},
+};
+use sunscreen_compiler_common::with_source_location;
+
+/**
+ * The multiplicative depth [`select`] adds to its result, i.e. how many
+ * more multiplications its result's critical path has than the deepest of
+ * its arguments'.
+ */
+pub const SELECT_DEPTH: usize = 1;
+
+/**
+ * Returns `a` if `cond` encrypts 1 and `b` if `cond` encrypts 0. Use this
+ * for branching on encrypted data in an [`fhe_program`](crate::fhe_program).
+ *
+ * This computes `cond * a + (1 - cond) * b` as `b + cond * (a - b)` on each
+ * of `T`'s ciphertexts, which is valid for every BFV type: an encrypted
+ * [`Signed`] 0 or 1 is the constant polynomial 0 or 1, which selects whole
+ * plaintext polynomials regardless of their encoding. In particular,
+ * [`Rational`](crate::types::bfv::Rational) values are selected per
+ * component (numerator and denominator) rather than with rational
+ * arithmetic, and every lane of a [`Batched`](crate::types::bfv::Batched)
+ * value is selected.
+ *
+ * # Cost
+ * One multiplication, addition and subtraction per ciphertext in `T`.
+ * The result's multiplicative depth is [`SELECT_DEPTH`] more than the
+ * deepest of `cond`, `a` and `b`.
+ *
+ * # Remarks
+ * The result is garbage if `cond` encrypts anything other than a freshly
+ * encoded 0 or 1, including other encodings of the values 0 or 1. When
+ * `cond` comes from an untrusted party, they can prove it is well formed
+ * with a linked ZKP using `sunscreen::types::zkp::ConstrainBinary` (requires
+ * the `linkedproofs` feature).
+ *
+ * # Examples
+ * ```rust,ignore
+ * # use sunscreen::{fhe_program, types::{bfv::{select, Rational, Signed}, Cipher}};
+ *
+ * #[fhe_program(scheme = "bfv")]
+ * fn choose(cond: Cipher<Signed>, a: Cipher<Rational>, b: Cipher<Rational>) -> Cipher<Rational> {
+ *   select(cond, a, b)
+ * }
+ * ```
+ */
+#[track_caller]
+pub fn select<T>(
+    cond: FheProgramNode<Cipher<Signed>>,
+    a: FheProgramNode<Cipher<T>>,
+    b: FheProgramNode<Cipher<T>>,
+) -> FheProgramNode<Cipher<T>>
+where
+    T: BfvType,
+{
+    with_source_location(|| {
+        with_fhe_ctx(|ctx| {
+            let ids = a
+                .ids
+                .iter()
+                .zip(b.ids.iter())
+                .map(|(a, b)| {
+                    let diff = ctx.add_subtraction(*a, *b);
+                    let selected = ctx.add_multiplication(cond.ids[0], diff);
+
+                    ctx.add_addition(*b, selected)
+                })
+                .collect::<Vec<_>>();
+
+            FheProgramNode::new(&ids)
+        })
+    })
+}
@@ -8,6 +8,7 @@ use crate::{
 };
 use paste::paste;
 use petgraph::stable_graph::NodeIndex;
+use sunscreen_compiler_common::with_source_location;
 use sunscreen_runtime::TypeNameInstance;
 
 use std::ops::{Add, Div, Mul, Neg, Shl, Shr, Sub};
@@ -116,8 +117,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn add(self, rhs: Self) -> Self::Output {
-        T::graph_cipher_add(self, rhs)
+        with_source_location(|| T::graph_cipher_add(self, rhs))
     }
 }
 
@@ -128,8 +130,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn add(self, rhs: FheProgramNode<T>) -> Self::Output {
-        T::graph_cipher_plain_add(self, rhs)
+        with_source_location(|| T::graph_cipher_plain_add(self, rhs))
     }
 }
 
@@ -140,8 +143,9 @@ where
 {
     type Output = FheProgramNode<Cipher<T>>;
 
+    #[track_caller]
     fn add(self, rhs: FheProgramNode<Cipher<T>>) -> Self::Output {
-        T::graph_cipher_plain_add(rhs, self)
+        with_source_location(|| T::graph_cipher_plain_add(rhs, self))
     }
 }
 
@@ -153,8 +157,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn add(self, rhs: T) -> Self::Output {
-        U::graph_cipher_const_add(self, rhs)
+        with_source_location(|| U::graph_cipher_const_add(self, rhs))
     }
 }
 
@@ -167,8 +172,9 @@ where
 {
     type Output = FheProgramNode<Cipher<T>>;
 
+    #[track_caller]
     fn add(self, rhs: FheProgramNode<Cipher<T>>) -> Self::Output {
-        T::graph_cipher_const_add(rhs, self)
+        with_source_location(|| T::graph_cipher_const_add(rhs, self))
     }
 }
 
@@ -179,8 +185,9 @@ where
 {
     type Output = FheProgramNode<Cipher<T>>;
 
+    #[track_caller]
     fn add(self, rhs: FheProgramNode<Cipher<T>>) -> Self::Output {
-        T::graph_cipher_const_add(rhs, self)
+        with_source_location(|| T::graph_cipher_const_add(rhs, self))
     }
 }
 
@@ -191,8 +198,9 @@ where
 {
     type Output = FheProgramNode<Cipher<T>>;
 
+    #[track_caller]
     fn add(self, rhs: FheProgramNode<Cipher<T>>) -> Self::Output {
-        T::graph_cipher_const_add(rhs, self)
+        with_source_location(|| T::graph_cipher_const_add(rhs, self))
     }
 }
 
@@ -203,8 +211,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn sub(self, rhs: Self) -> Self::Output {
-        T::graph_cipher_sub(self, rhs)
+        with_source_location(|| T::graph_cipher_sub(self, rhs))
     }
 }
 
@@ -215,8 +224,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn sub(self, rhs: FheProgramNode<T>) -> Self::Output {
-        T::graph_cipher_plain_sub(self, rhs)
+        with_source_location(|| T::graph_cipher_plain_sub(self, rhs))
     }
 }
 
@@ -227,8 +237,9 @@ where
 {
     type Output = FheProgramNode<Cipher<T>>;
 
+    #[track_caller]
     fn sub(self, rhs: FheProgramNode<Cipher<T>>) -> Self::Output {
-        T::graph_plain_cipher_sub(self, rhs)
+        with_source_location(|| T::graph_plain_cipher_sub(self, rhs))
     }
 }
 
@@ -240,8 +251,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn sub(self, rhs: T) -> Self::Output {
-        U::graph_cipher_const_sub(self, rhs)
+        with_source_location(|| U::graph_cipher_const_sub(self, rhs))
     }
 }
 
@@ -252,8 +264,9 @@ where
 {
     type Output = FheProgramNode<Cipher<T>>;
 
+    #[track_caller]
     fn sub(self, rhs: FheProgramNode<Cipher<T>>) -> Self::Output {
-        T::graph_const_cipher_sub(self, rhs)
+        with_source_location(|| T::graph_const_cipher_sub(self, rhs))
     }
 }
 
@@ -264,8 +277,9 @@ where
 {
     type Output = FheProgramNode<Cipher<T>>;
 
+    #[track_caller]
     fn sub(self, rhs: FheProgramNode<Cipher<T>>) -> Self::Output {
-        T::graph_const_cipher_sub(self, rhs)
+        with_source_location(|| T::graph_const_cipher_sub(self, rhs))
     }
 }
 
@@ -276,8 +290,9 @@ where
 {
     type Output = FheProgramNode<Cipher<T>>;
 
+    #[track_caller]
     fn sub(self, rhs: FheProgramNode<Cipher<T>>) -> Self::Output {
-        T::graph_const_cipher_sub(self, rhs)
+        with_source_location(|| T::graph_const_cipher_sub(self, rhs))
     }
 }
 
@@ -288,8 +303,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn mul(self, rhs: Self) -> Self::Output {
-        T::graph_cipher_mul(self, rhs)
+        with_source_location(|| T::graph_cipher_mul(self, rhs))
     }
 }
 
@@ -300,8 +316,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn mul(self, rhs: FheProgramNode<T>) -> Self::Output {
-        T::graph_cipher_plain_mul(self, rhs)
+        with_source_location(|| T::graph_cipher_plain_mul(self, rhs))
     }
 }
 
@@ -312,8 +329,9 @@ where
 {
     type Output = FheProgramNode<Cipher<T>>;
 
+    #[track_caller]
     fn mul(self, rhs: FheProgramNode<Cipher<T>>) -> Self::Output {
-        T::graph_cipher_plain_mul(rhs, self)
+        with_source_location(|| T::graph_cipher_plain_mul(rhs, self))
     }
 }
 
@@ -325,8 +343,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn mul(self, rhs: T) -> Self::Output {
-        U::graph_cipher_const_mul(self, rhs)
+        with_source_location(|| U::graph_cipher_const_mul(self, rhs))
     }
 }
 
@@ -337,8 +356,9 @@ where
 {
     type Output = FheProgramNode<Cipher<T>>;
 
+    #[track_caller]
     fn mul(self, rhs: FheProgramNode<Cipher<T>>) -> Self::Output {
-        T::graph_cipher_const_mul(rhs, self)
+        with_source_location(|| T::graph_cipher_const_mul(rhs, self))
     }
 }
 
@@ -349,8 +369,9 @@ where
 {
     type Output = FheProgramNode<Cipher<T>>;
 
+    #[track_caller]
     fn mul(self, rhs: FheProgramNode<Cipher<T>>) -> Self::Output {
-        T::graph_cipher_const_mul(rhs, self)
+        with_source_location(|| T::graph_cipher_const_mul(rhs, self))
     }
 }
 
@@ -361,8 +382,9 @@ where
 {
     type Output = FheProgramNode<Cipher<T>>;
 
+    #[track_caller]
     fn mul(self, rhs: FheProgramNode<Cipher<T>>) -> Self::Output {
-        T::graph_cipher_const_mul(rhs, self)
+        with_source_location(|| T::graph_cipher_const_mul(rhs, self))
     }
 }
 
@@ -373,8 +395,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn div(self, rhs: Self) -> Self::Output {
-        T::graph_cipher_div(self, rhs)
+        with_source_location(|| T::graph_cipher_div(self, rhs))
     }
 }
 
@@ -385,8 +408,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn div(self, rhs: FheProgramNode<T>) -> Self::Output {
-        T::graph_cipher_plain_div(self, rhs)
+        with_source_location(|| T::graph_cipher_plain_div(self, rhs))
     }
 }
 
@@ -397,8 +421,9 @@ where
 {
     type Output = FheProgramNode<Cipher<T>>;
 
+    #[track_caller]
     fn div(self, rhs: FheProgramNode<Cipher<T>>) -> Self::Output {
-        T::graph_plain_cipher_div(self, rhs)
+        with_source_location(|| T::graph_plain_cipher_div(self, rhs))
     }
 }
 
@@ -410,8 +435,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn div(self, rhs: U) -> Self::Output {
-        T::graph_cipher_const_div(self, rhs)
+        with_source_location(|| T::graph_cipher_const_div(self, rhs))
     }
 }
 
@@ -422,8 +448,9 @@ where
 {
     type Output = FheProgramNode<Cipher<T>>;
 
+    #[track_caller]
     fn div(self, rhs: FheProgramNode<Cipher<T>>) -> Self::Output {
-        T::graph_const_cipher_div(self, rhs)
+        with_source_location(|| T::graph_const_cipher_div(self, rhs))
     }
 }
 
@@ -434,8 +461,9 @@ where
 {
     type Output = FheProgramNode<Cipher<T>>;
 
+    #[track_caller]
     fn div(self, rhs: FheProgramNode<Cipher<T>>) -> Self::Output {
-        T::graph_const_cipher_div(self, rhs)
+        with_source_location(|| T::graph_const_cipher_div(self, rhs))
     }
 }
 
@@ -446,8 +474,9 @@ where
 {
     type Output = FheProgramNode<Cipher<T>>;
 
+    #[track_caller]
     fn div(self, rhs: FheProgramNode<Cipher<T>>) -> Self::Output {
-        T::graph_const_cipher_div(self, rhs)
+        with_source_location(|| T::graph_const_cipher_div(self, rhs))
     }
 }
 
@@ -458,8 +487,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn neg(self) -> Self::Output {
-        T::graph_cipher_neg(self)
+        with_source_location(|| T::graph_cipher_neg(self))
     }
 }
 
@@ -470,8 +500,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn swap_rows(self) -> Self::Output {
-        T::graph_cipher_swap_rows(self)
+        with_source_location(|| T::graph_cipher_swap_rows(self))
     }
 }
 
@@ -490,8 +521,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn shl(self, x: u64) -> Self {
-        T::graph_cipher_rotate_left(self, x)
+        with_source_location(|| T::graph_cipher_rotate_left(self, x))
     }
 }
 
@@ -501,8 +533,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn shr(self, x: u64) -> Self {
-        T::graph_cipher_rotate_right(self, x)
+        with_source_location(|| T::graph_cipher_rotate_right(self, x))
     }
 }
 
@@ -526,7 +559,8 @@ where
 /// ciphertexts; e.g. `let sum = 0; sum = sum + cipher`.
 ///
 /// # Warning
-/// It is illegal to output an `FheProgramNode<Indeterminate, S>` with `S == Stage::Literal`.
+/// It is illegal to output an `FheProgramNode<Indeterminate, S>` as a ciphertext with
+/// `S == Stage::Literal` or as a plaintext with `S == Stage::Cipher`.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Stage {
     /// Initial stage of indeterminate type: literal/plaintext
@@ -537,7 +571,7 @@ pub enum Stage {
 
 /// Used in tandem with `Stage`. Ultimately, the purpose is to allow a single type to span
 /// plaintexts and ciphertexts. The only requirement is that, upon output, the type must resolve to
-/// a ciphertext.
+/// a ciphertext, or to a plaintext if it never interacted with one.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct Indeterminate<L: FheLiteral, T: FheType> {
     _lit: std::marker::PhantomData<L>,
@@ -563,12 +597,13 @@ where
 }
 
 /// Create a new fhe program node from any supported literal type.
+#[track_caller]
 pub fn fhe_node<L, T>(lit: L) -> FheProgramNode<Indeterminate<L, T>, Stage>
 where
     L: FheLiteral,
     T: FheType + GraphCipherInsert<Lit = L, Val = T>,
 {
-    let node = T::graph_cipher_insert(lit);
+    let node = with_source_location(|| T::graph_cipher_insert(lit));
     reinterpret_cast(node, Stage::Literal)
 }
 
@@ -627,6 +662,21 @@ where
     }
 }
 
+// Allow `Indeterminate` -> `T` so literals can be returned unencrypted, but panic if at
+// `Stage::Cipher`
+impl<L, T> Coerce<FheProgramNode<T>> for FheProgramNode<Indeterminate<L, T>, Stage>
+where
+    L: FheLiteral,
+    T: FheType,
+{
+    fn coerce(self) -> FheProgramNode<T> {
+        match self.stage {
+            Stage::Literal => reinterpret_cast(self, ()),
+            Stage::Cipher => panic!("FHE variables that have undergone arithmetic operations with ciphertexts can't be returned as plaintexts."),
+        }
+    }
+}
+
 // This is such a common one, let the user call `var.into()` in their programs.
 // We unfortunately can't make a very generic impl here, as it would conflict with the blanket
 // `From<T> for T`.
@@ -665,7 +715,9 @@ macro_rules! impl_indeterminate_arithmetic_op {
                 {
                     type Output = Self;
 
+                    #[track_caller]
                     fn [<$op:lower>](self, rhs: FheProgramNode<Cipher<T>>) -> Self::Output {
+                        with_source_location(|| {
                         let node = match self.stage {
                             Stage::Literal => {
                                 let lit_node = reinterpret_cast(self, ());
@@ -680,6 +732,7 @@ macro_rules! impl_indeterminate_arithmetic_op {
                         // No matter what `self.stage` currently is, it is being operated on with a
                         // ciphertext, so its next stage is cipher.
                         reinterpret_cast(node, Stage::Cipher)
+                        })
                     }
                 }
 
@@ -694,7 +747,9 @@ macro_rules! impl_indeterminate_arithmetic_op {
                     // `.into()` on the resulting node.
                     type Output = FheProgramNode<Indeterminate<L, T>, Stage>;
 
+                    #[track_caller]
                     fn [<$op:lower>](self, rhs: FheProgramNode<Indeterminate<L, T>, Stage>) -> Self::Output {
+                        with_source_location(|| {
                         let node = match rhs.stage {
                             Stage::Literal => {
                                 let lit_node = reinterpret_cast(rhs, ());
@@ -709,6 +764,7 @@ macro_rules! impl_indeterminate_arithmetic_op {
                         // No matter what `rhs.stage` currently is, it is being added to a ciphertext, so its next
                         // stage is cipher.
                         reinterpret_cast(node, Stage::Cipher)
+                        })
                     }
                 }
             }
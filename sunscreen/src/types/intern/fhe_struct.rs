@@ -0,0 +1,94 @@
+use petgraph::stable_graph::NodeIndex;
+
+use crate::types::{intern::FheProgramNode, Cipher, FheType, NumCiphertexts};
+
+/**
+ * A struct whose fields are FHE types. Implement this with
+ * `#[derive(FheType)]` rather than by hand.
+ *
+ * # Remarks
+ * An encrypted struct is a ciphertext for each of its fields, concatenated
+ * in declaration order. In an [`fhe_program`](crate::fhe_program), use
+ * [`FheProgramNode::fields`] to access them. The derived fields type has a
+ * `join` method to build a struct from them.
+ */
+pub trait FheStruct: FheType {
+    /**
+     * The struct's fields as encrypted values in an FHE program.
+     */
+    type Fields: Copy;
+
+    /**
+     * Splits the given encrypted struct into its fields.
+     */
+    fn split(node: FheProgramNode<Cipher<Self>>) -> Self::Fields;
+
+    /**
+     * Combines the given fields into an encrypted struct.
+     */
+    fn join(fields: Self::Fields) -> FheProgramNode<Cipher<Self>>;
+}
+
+impl<T> FheProgramNode<Cipher<T>>
+where
+    T: FheStruct,
+{
+    /**
+     * Returns the fields of this encrypted struct.
+     */
+    pub fn fields(self) -> T::Fields {
+        T::split(self)
+    }
+}
+
+/**
+ * A field of a struct's [`FheStruct::Fields`]: either an encrypted value or
+ * an array of them. An implementation detail of `#[derive(FheType)]`.
+ */
+pub trait FheStructField: Copy {
+    /**
+     * Creates this field from its ciphertexts' node ids.
+     */
+    fn from_ids(ids: &[NodeIndex]) -> Self;
+
+    /**
+     * Appends this field's ciphertexts' node ids to `ids`.
+     */
+    fn append_ids(&self, ids: &mut Vec<NodeIndex>);
+}
+
+impl<T> FheStructField for FheProgramNode<Cipher<T>>
+where
+    T: FheType + Copy,
+{
+    fn from_ids(ids: &[NodeIndex]) -> Self {
+        FheProgramNode::new(ids)
+    }
+
+    fn append_ids(&self, ids: &mut Vec<NodeIndex>) {
+        ids.extend_from_slice(self.ids);
+    }
+}
+
+impl<T, const N: usize> FheStructField for [T; N]
+where
+    T: FheStructField + NumCiphertexts,
+{
+    fn from_ids(ids: &[NodeIndex]) -> Self {
+        let elements = ids
+            .chunks(T::NUM_CIPHERTEXTS)
+            .map(T::from_ids)
+            .collect::<Vec<_>>();
+
+        match elements.try_into() {
+            Ok(v) => v,
+            Err(_) => panic!("Internal error: vec to array length mismatch"),
+        }
+    }
+
+    fn append_ids(&self, ids: &mut Vec<NodeIndex>) {
+        for x in self {
+            x.append_ids(ids);
+        }
+    }
+}
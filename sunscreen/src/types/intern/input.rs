@@ -1,5 +1,6 @@
 use crate::fhe::{with_fhe_ctx, FheContextOps};
 pub use crate::types::{intern::FheProgramNode, Cipher, FheType, NumCiphertexts, TypeName};
+use sunscreen_compiler_common::with_source_location;
 
 /**
  * Create an input node from an Fhe Program input argument.
@@ -30,18 +31,21 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn input() -> Self {
-        let mut ids = Vec::with_capacity(T::NUM_CIPHERTEXTS);
-
-        for _ in 0..T::NUM_CIPHERTEXTS {
-            if T::type_name().is_encrypted {
-                ids.push(with_fhe_ctx(|ctx| ctx.add_ciphertext_input()));
-            } else {
-                ids.push(with_fhe_ctx(|ctx| ctx.add_plaintext_input()));
+        with_source_location(|| {
+            let mut ids = Vec::with_capacity(T::NUM_CIPHERTEXTS);
+
+            for _ in 0..T::NUM_CIPHERTEXTS {
+                if T::type_name().is_encrypted {
+                    ids.push(with_fhe_ctx(|ctx| ctx.add_ciphertext_input()));
+                } else {
+                    ids.push(with_fhe_ctx(|ctx| ctx.add_plaintext_input()));
+                }
             }
-        }
 
-        FheProgramNode::new(&ids)
+            FheProgramNode::new(&ids)
+        })
     }
 }
 
@@ -51,6 +55,7 @@ where
 {
     type Output = [T::Output; N];
 
+    #[track_caller]
     fn input() -> Self::Output {
         let mut output = Vec::with_capacity(N);
 
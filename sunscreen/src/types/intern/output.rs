@@ -1,7 +1,8 @@
 use crate::{
     fhe::{with_fhe_ctx, FheContextOps},
-    types::{intern::FheProgramNode, NumCiphertexts},
+    types::{intern::FheProgramNode, NumCiphertexts, TypeName},
 };
+use sunscreen_compiler_common::with_source_location;
 
 /**
  * Captures an output for an FHE program.
@@ -28,18 +29,29 @@ pub trait Output {
 
 impl<T> Output for FheProgramNode<T>
 where
-    T: NumCiphertexts,
+    T: NumCiphertexts + TypeName,
 {
     type Output = FheProgramNode<T>;
 
+    #[track_caller]
     fn output(&self) -> Self::Output {
-        let mut ids = Vec::with_capacity(self.ids.len());
+        with_source_location(|| {
+            // Unencrypted types are returned as plaintexts.
+            let is_encrypted = T::type_name().is_encrypted;
+            let mut ids = Vec::with_capacity(self.ids.len());
 
-        for i in 0..self.ids.len() {
-            ids.push(with_fhe_ctx(|ctx| ctx.add_output(self.ids[i])));
-        }
+            for i in 0..self.ids.len() {
+                ids.push(with_fhe_ctx(|ctx| {
+                    if is_encrypted {
+                        ctx.add_output(self.ids[i])
+                    } else {
+                        ctx.add_plaintext_output(self.ids[i])
+                    }
+                }));
+            }
 
-        FheProgramNode::new(&ids)
+            FheProgramNode::new(&ids)
+        })
     }
 }
 
@@ -49,7 +61,8 @@ where
 {
     type Output = [T::Output; N];
 
+    #[track_caller]
     fn output(&self) -> Self::Output {
-        self.map(|i| i.output())
+        with_source_location(|| self.map(|i| i.output()))
     }
 }
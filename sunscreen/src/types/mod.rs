@@ -66,9 +66,10 @@ pub mod intern;
 
 /**
  * Contains the set of ops traits that dictate legal operations
- * for FHE data types.
+ * for FHE data types. Generic [`fhe_program`](crate::fhe_program)s can use
+ * these to bound their type parameters.
  */
-mod ops;
+pub mod ops;
 
 /**
  * Contains types used in creating zero-knowledge proof R1CS circuits.
@@ -60,6 +60,17 @@ pub trait ConstrainFresh {
     fn constrain_fresh_encoding(&self);
 }
 
+/// Constrain a binary condition.
+pub trait ConstrainBinary {
+    /// Add a constraint that the plaintext polynomial is the constant 0 or 1.
+    ///
+    /// This proves an encrypted value is a valid condition for
+    /// [`select`](crate::types::bfv::select). Note that decoding with
+    /// [`AsFieldElement::into_field_elem`] and constraining the result isn't sufficient, as
+    /// other polynomials also decode to 0 or 1.
+    fn constrain_binary(&self);
+}
+
 impl<const N: usize, const M: usize, F: FieldSpec> ProgramNode<BfvSignedEncoding<F, N, M>> {
     fn extract_coefficients(&self) -> Vec<NodeIndex> {
         let bound = self.ids.len() / N;
@@ -154,6 +165,27 @@ impl<const N: usize, const M: usize, F: FieldSpec> ConstrainFresh
     }
 }
 
+impl<const N: usize, const M: usize, F: FieldSpec> ConstrainBinary
+    for ProgramNode<BfvSignedEncoding<F, N, M>>
+{
+    fn constrain_binary(&self) {
+        let coeffs = self.extract_coefficients();
+
+        with_zkp_ctx(|ctx| {
+            let one = ctx.add_constant(&BigInt::ONE);
+
+            // Constrain the constant coefficient to 0 or 1 and the rest to zero
+            let c_minus_1 = ctx.add_subtraction(coeffs[0], one);
+            let poly = ctx.add_multiplication(coeffs[0], c_minus_1);
+            ctx.add_constraint(poly, &BigInt::ZERO);
+
+            for c in coeffs.iter().skip(1) {
+                ctx.add_constraint(*c, &BigInt::ZERO);
+            }
+        });
+    }
+}
+
 /// A [BFV signed integer](Signed) that has been linked to a ZKP program.
 ///
 /// Use the [`AsFieldElement::into_field_elem`] method to decode the value into a field
@@ -222,6 +254,14 @@ macro_rules! impl_linked_zkp_type {
                         cast.constrain_fresh_encoding()
                     }
                 }
+
+                impl<F: FieldSpec> ConstrainBinary for ProgramNode<[<Bfv $int_type>]<F>> {
+                    fn constrain_binary(&self) {
+                        let cast: ProgramNode<BfvSignedEncoding<F, { <$int_type as LinkWithZkp>::DEGREE_BOUND }, $m>> =
+                            ProgramNode::new(&self.ids);
+                        cast.constrain_binary()
+                    }
+                }
             }
         )+
     };
@@ -280,6 +320,11 @@ mod tests {
         x.into_field_elem().constrain_eq(y);
     }
 
+    #[zkp_program]
+    fn is_binary<F: FieldSpec>(#[linked] x: BfvSigned<F>) {
+        x.constrain_binary();
+    }
+
     #[fhe_program(scheme = "bfv")]
     fn doggie() {}
 
@@ -351,6 +396,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn can_constrain_binary() {
+        let plain_modulus = 1024_u64;
+        let is_binary_zkp = Compiler::new()
+            .fhe_program(doggie)
+            .plain_modulus_constraint(PlainModulusConstraint::Raw(plain_modulus))
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(is_binary)
+            .compile()
+            .unwrap()
+            .take_zkp_program(is_binary)
+            .unwrap();
+
+        let runtime = ZkpRuntime::new(BulletproofsBackend::new()).unwrap();
+
+        let log_p = plain_modulus.ilog2() as usize + 1;
+
+        // -1 + 2x decodes to 1, but isn't the constant polynomial 1.
+        for (c_0, c_1, is_valid) in [
+            (0, 0, true),
+            (1, 0, true),
+            (2, 0, false),
+            (plain_modulus - 1, 2, false),
+        ] {
+            let mut signed_encoding = [0; <Signed as LinkWithZkp>::DEGREE_BOUND];
+            signed_encoding[0] = c_0;
+            signed_encoding[1] = c_1;
+
+            let coeffs = signed_encoding.map(|c| {
+                let mut bits = vec![0; log_p];
+                for (i, b) in bits.iter_mut().enumerate() {
+                    let bit = (c & (0x1 << i)) >> i;
+                    *b = bit;
+                }
+                bits
+            });
+
+            let encoded = BfvSigned(BfvSignedEncoding {
+                data: coeffs
+                    .into_iter()
+                    .flat_map(|c| c.into_iter().map(BulletproofsField::from))
+                    .collect(),
+            });
+
+            let proof = runtime
+                .proof_builder(&is_binary_zkp)
+                .private_input(encoded)
+                .prove();
+
+            assert_eq!(proof.is_ok(), is_valid);
+        }
+    }
+
     fn test_plain_modulus(plain_modulus: u64) {
         let is_eq_zkp = Compiler::new()
             .fhe_program(doggie)
@@ -1,4 +1,5 @@
 use petgraph::stable_graph::NodeIndex;
+use sunscreen_compiler_common::with_source_location;
 use sunscreen_zkp_backend::FieldSpec;
 
 use std::{
@@ -182,8 +183,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn add(self, rhs: Self) -> Self::Output {
-        <T as AddVar>::add(self, rhs)
+        with_source_location(|| <T as AddVar>::add(self, rhs))
     }
 }
 
@@ -193,8 +195,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn mul(self, rhs: Self) -> Self::Output {
-        <T as MulVar>::mul(self, rhs)
+        with_source_location(|| <T as MulVar>::mul(self, rhs))
     }
 }
 
@@ -204,8 +207,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn div(self, rhs: Self) -> Self::Output {
-        <T as DivVar>::div(self, rhs)
+        with_source_location(|| <T as DivVar>::div(self, rhs))
     }
 }
 
@@ -215,8 +219,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn rem(self, rhs: Self) -> Self::Output {
-        <T as RemVar>::rem(self, rhs)
+        with_source_location(|| <T as RemVar>::rem(self, rhs))
     }
 }
 
@@ -226,8 +231,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn sub(self, rhs: Self) -> Self::Output {
-        <T as SubVar>::sub(self, rhs)
+        with_source_location(|| <T as SubVar>::sub(self, rhs))
     }
 }
 
@@ -237,8 +243,9 @@ where
 {
     type Output = Self;
 
+    #[track_caller]
     fn neg(self) -> Self::Output {
-        <T as NegVar>::neg(self)
+        with_source_location(|| <T as NegVar>::neg(self))
     }
 }
 
@@ -279,8 +286,9 @@ where
     /**
      * Constrains this native field to equal the right hand side
      */
+    #[track_caller]
     fn constrain_eq(self, rhs: T) -> ProgramNode<Self::Output> {
-        V::constrain_eq(self.into_program_node(), rhs.into_program_node())
+        with_source_location(|| V::constrain_eq(self.into_program_node(), rhs.into_program_node()))
     }
 }
 
@@ -351,19 +359,31 @@ where
     U: IntoProgramNode<Output = V> + Sized,
     V: ZkpType + Sized + ConstrainCmpVarVar,
 {
+    #[track_caller]
     fn constrain_le_bounded(self, rhs: T, bits: usize) {
-        V::constrain_le_bounded(self.into_program_node(), rhs.into_program_node(), bits);
+        with_source_location(|| {
+            V::constrain_le_bounded(self.into_program_node(), rhs.into_program_node(), bits)
+        });
     }
 
+    #[track_caller]
     fn constrain_lt_bounded(self, rhs: T, bits: usize) {
-        V::constrain_lt_bounded(self.into_program_node(), rhs.into_program_node(), bits);
+        with_source_location(|| {
+            V::constrain_lt_bounded(self.into_program_node(), rhs.into_program_node(), bits)
+        });
     }
 
+    #[track_caller]
     fn constrain_ge_bounded(self, rhs: T, bits: usize) {
-        V::constrain_ge_bounded(self.into_program_node(), rhs.into_program_node(), bits);
+        with_source_location(|| {
+            V::constrain_ge_bounded(self.into_program_node(), rhs.into_program_node(), bits)
+        });
     }
 
+    #[track_caller]
     fn constrain_gt_bounded(self, rhs: T, bits: usize) {
-        V::constrain_gt_bounded(self.into_program_node(), rhs.into_program_node(), bits);
+        with_source_location(|| {
+            V::constrain_gt_bounded(self.into_program_node(), rhs.into_program_node(), bits)
+        });
     }
 }
@@ -194,7 +194,8 @@ use std::fmt::Debug;
 
 use petgraph::stable_graph::NodeIndex;
 use sunscreen_compiler_common::{
-    CompilationResult, Context, EdgeInfo, NodeInfo, Operation as OperationTrait, Render,
+    CompilationResult, Context, EdgeInfo, ExportOperation, NodeInfo, Operation as OperationTrait,
+    Render,
 };
 
 #[derive(Clone)]
@@ -323,6 +324,19 @@ impl OperationTrait for Operation {
     }
 }
 
+impl ExportOperation for Operation {
+    fn data_type(&self) -> Option<String> {
+        match self {
+            Self::Constraint(_) | Self::InvokeGadget(_) => None,
+            _ => Some("Field".to_owned()),
+        }
+    }
+
+    fn is_multiplication(&self) -> bool {
+        matches!(self, Operation::Mul)
+    }
+}
+
 impl Operation {
     /// Whether or not this operation is addition.
     pub fn is_add(&self) -> bool {
@@ -591,7 +605,7 @@ pub(crate) fn compile(program: &ZkpFrontendCompilation) -> CompiledZkpProgram {
                 Operation::Constant(x) => JitOperation::Constant(x),
             };
 
-            NodeInfo { operation }
+            NodeInfo::with_source(operation, n.source)
         },
         |_, e| *e,
     );
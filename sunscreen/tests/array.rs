@@ -286,3 +286,75 @@ fn can_return_array() {
 
     assert_eq!(c, expected);
 }
+
+#[test]
+fn can_multiply_many_array_elements() {
+    #[fhe_program(scheme = "bfv")]
+    fn product(x: [Cipher<Signed>; 8]) -> Cipher<Signed> {
+        let mut acc = x[0];
+
+        for i in 1..8 {
+            acc = acc * x[i];
+        }
+
+        acc
+    }
+
+    let app = Compiler::new()
+        .fhe_program(product)
+        .additional_noise_budget(5)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile()
+        .unwrap();
+
+    // The product chain is rebalanced into a tree of depth 3.
+    assert!(app.params().lattice_dimension <= 8192);
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let x = [2, 1, 3, 1, 2, 1, 1, -2].map(Signed::from);
+    let x_c = runtime.encrypt(x, &public_key).unwrap();
+
+    let result = runtime
+        .run(
+            app.get_fhe_program(product).unwrap(),
+            vec![x_c],
+            &public_key,
+        )
+        .unwrap();
+
+    let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, (-24).into());
+}
+
+#[test]
+fn can_add_many_array_elements() {
+    #[fhe_program(scheme = "bfv")]
+    fn sum(x: [Cipher<Signed>; 4]) -> Cipher<Signed> {
+        x[0] + x[1] + x[2] + x[3]
+    }
+
+    let app = Compiler::new()
+        .fhe_program(sum)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let x = [2, 1, 3, -4].map(Signed::from);
+    let x_c = runtime.encrypt(x, &public_key).unwrap();
+
+    let result = runtime
+        .run(app.get_fhe_program(sum).unwrap(), vec![x_c], &public_key)
+        .unwrap();
+
+    let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, 2.into());
+}
@@ -20,22 +20,56 @@ fn chain_count_is_overridable() {
 }
 
 #[test]
-fn cant_chain_multiple_program() {
-    #[fhe_program(scheme = "bfv", chain_count = 42)]
-    fn program_1() {}
+fn can_chain_multiple_programs() {
+    #[fhe_program(scheme = "bfv", chain_count = 3)]
+    fn update(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+        a * b
+    }
 
     #[fhe_program(scheme = "bfv")]
-    fn program_2() {}
+    fn query(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+        a + b
+    }
 
-    let result = Compiler::new()
-        .fhe_program(program_1)
-        .fhe_program(program_2)
-        .compile();
+    let app = Compiler::new()
+        .fhe_program(update)
+        .fhe_program(query)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(256))
+        .compile()
+        .unwrap();
+
+    // Chaining update 3 times alone needs 8192, and the query may run on
+    // its outputs afterwards.
+    assert!(app.params().lattice_dimension >= 8192);
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let mut state = runtime.encrypt(Signed::from(1), &public_key).unwrap();
+    let two = runtime.encrypt(Signed::from(2), &public_key).unwrap();
+
+    for _ in 0..3 {
+        state = runtime
+            .run(
+                app.get_fhe_program(update).unwrap(),
+                vec![state, two.clone()],
+                &public_key,
+            )
+            .unwrap()
+            .remove(0);
+    }
+
+    let result = runtime
+        .run(
+            app.get_fhe_program(query).unwrap(),
+            vec![state, two],
+            &public_key,
+        )
+        .unwrap();
+
+    let result: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
 
-    match result {
-        Err(Error::Unsupported(_)) => {}
-        _ => panic!("Expected compilation to fail with UnsupportedOperation."),
-    };
+    assert_eq!(result, 10.into());
 }
 
 #[test]
@@ -66,3 +100,93 @@ fn chaining_increases_parameters() {
 
     assert_eq!(app.params().lattice_dimension, 8192);
 }
+
+#[test]
+fn cheap_program_does_not_increase_chained_parameters() {
+    #[fhe_program(scheme = "bfv", chain_count = 3)]
+    fn update(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+        a * b
+    }
+
+    #[fhe_program(scheme = "bfv")]
+    fn query(a: Cipher<Signed>, b: Signed) -> Cipher<Signed> {
+        a + b
+    }
+
+    let app = Compiler::new()
+        .fhe_program(update)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(256))
+        .compile()
+        .unwrap();
+
+    let lattice_dimension = app.params().lattice_dimension;
+
+    let app = Compiler::new()
+        .fhe_program(update)
+        .fhe_program(query)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(256))
+        .compile()
+        .unwrap();
+
+    assert_eq!(app.params().lattice_dimension, lattice_dimension);
+}
+
+#[test]
+fn chaining_different_programs_increases_parameters() {
+    #[fhe_program(scheme = "bfv", chain_count = 3)]
+    fn mul_3(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+        a * b
+    }
+
+    #[fhe_program(scheme = "bfv", chain_count = 4)]
+    fn mul_4(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+        a * b
+    }
+
+    let alone_3 = Compiler::new()
+        .fhe_program(mul_3)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(256))
+        .compile()
+        .unwrap();
+
+    let alone_4 = Compiler::new()
+        .fhe_program(mul_4)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(256))
+        .compile()
+        .unwrap();
+
+    let both = Compiler::new()
+        .fhe_program(mul_3)
+        .fhe_program(mul_4)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(256))
+        .compile()
+        .unwrap();
+
+    let alone_3 = alone_3.params().lattice_dimension;
+    let alone_4 = alone_4.params().lattice_dimension;
+    let both = both.params().lattice_dimension;
+
+    assert!(both > alone_3);
+    assert!(both > alone_4);
+}
+
+#[test]
+fn too_many_chain_combinations_is_unsupported() {
+    #[fhe_program(scheme = "bfv", chain_count = 100)]
+    fn add_100(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+        a + b
+    }
+
+    #[fhe_program(scheme = "bfv", chain_count = 100)]
+    fn sub_100(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+        a - b
+    }
+
+    let result = Compiler::new()
+        .fhe_program(add_100)
+        .fhe_program(sub_100)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(256))
+        .compile();
+
+    assert!(matches!(result, Err(Error::Unsupported(_))));
+}
@@ -0,0 +1,42 @@
+use sunscreen::{
+    fhe_program,
+    types::{bfv::Signed, Cipher},
+    Compiler, Params, PlainModulusConstraint,
+};
+
+#[fhe_program(scheme = "bfv")]
+fn square_plus(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+    a * a + b
+}
+
+#[test]
+fn estimates_grow_with_lattice_dimension() {
+    let app = Compiler::new()
+        .fhe_program(square_plus)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(64))
+        .compile()
+        .unwrap();
+
+    let program = app.get_fhe_program(square_plus).unwrap();
+    let params = app.params();
+
+    let cost = program.estimate_cost(params);
+
+    assert_eq!(cost.operation_counts.multiplies, 1);
+    assert_eq!(cost.operation_counts.relinearizations, 1);
+    assert_eq!(cost.operation_counts.additions, 1);
+    assert!(cost.relinearization_keys_size.is_some());
+    assert_eq!(cost.galois_keys_size, None);
+    assert!(cost.latency > std::time::Duration::ZERO);
+    assert!(cost.critical_path_latency <= cost.latency);
+
+    let larger = Params {
+        lattice_dimension: 2 * params.lattice_dimension,
+        ..params.clone()
+    };
+
+    let larger_cost = program.estimate_cost(&larger);
+
+    assert!(larger_cost.latency > cost.latency);
+    assert!(larger_cost.memory > cost.memory);
+}
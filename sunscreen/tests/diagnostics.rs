@@ -0,0 +1,92 @@
+use sunscreen::{
+    fhe_program,
+    types::{bfv::Signed, zkp::Field, Cipher},
+    zkp_program, Compiler, Error, ParamsRejection, PlainModulusConstraint, Runtime, SourceLocation,
+};
+use sunscreen_runtime::Error as RuntimeError;
+use sunscreen_zkp_backend::{
+    bulletproofs::BulletproofsBackend, Error as ZkpError, FieldSpec, ZkpBackend,
+};
+
+type BPField = Field<<BulletproofsBackend as ZkpBackend>::Field>;
+
+/**
+ * Returns the line in this file containing the given code.
+ */
+fn line_of(code: &str) -> u32 {
+    let line = include_str!("diagnostics.rs")
+        .lines()
+        .position(|x| x.contains(code) && !x.contains("line_of"))
+        .unwrap();
+
+    line as u32 + 1
+}
+
+fn assert_points_at(source: Option<SourceLocation>, code: &str) {
+    let source = source.unwrap();
+
+    assert!(source.file.ends_with("diagnostics.rs"));
+    assert_eq!(source.line, line_of(code));
+}
+
+#[fhe_program(scheme = "bfv")]
+fn too_deep(x: Cipher<Signed>) -> Cipher<Signed> {
+    let mut y = x;
+
+    for _ in 0..32 {
+        y = y * y;
+    }
+
+    y
+}
+
+#[test]
+fn noise_budget_errors_point_at_expression() {
+    let result = Compiler::new()
+        .fhe_program(too_deep)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(64))
+        .compile();
+
+    match result {
+        Err(Error::NoiseBudgetExceeded(x)) => {
+            let (program, source, candidates) = *x;
+
+            assert_eq!(program, "too_deep");
+            assert_points_at(source, "y = y * y;");
+
+            // Every candidate was rejected, the largest for lack of noise budget.
+            assert!(candidates.iter().all(|x| x.rejection.is_some()));
+            assert!(matches!(
+                candidates.last().unwrap().rejection,
+                Some(ParamsRejection::NoiseBudget { .. })
+            ));
+        }
+        _ => panic!("Expected a noise budget error."),
+    }
+}
+
+#[zkp_program]
+fn is_42<F: FieldSpec>(x: Field<F>) {
+    x.constrain_eq(Field::from(42u32));
+}
+
+#[test]
+fn unsatisfied_constraints_point_at_expression() {
+    let app = Compiler::new()
+        .zkp_backend::<BulletproofsBackend>()
+        .zkp_program(is_42)
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+    let program = app.get_zkp_program(is_42).unwrap();
+
+    let result = runtime.prove(program, vec![BPField::from(41u8)], vec![], vec![]);
+
+    match result {
+        Err(RuntimeError::ZkpError(ZkpError::UnsatisfiableConstraint(x))) => {
+            assert_points_at(x.1, "x.constrain_eq(");
+        }
+        _ => panic!("Expected an unsatisfiable constraint error."),
+    }
+}
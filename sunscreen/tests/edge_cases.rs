@@ -2,6 +2,7 @@ use sunscreen::{
     types::{bfv::Signed, Cipher},
     *,
 };
+use sunscreen_fhe_program::Operation;
 
 #[test]
 fn unused_cipher_parameter_1() {
@@ -194,3 +195,79 @@ fn unused_plain_parameter_3() {
 
     assert_eq!(c, 20.into());
 }
+
+#[test]
+fn dead_intermediate_value() {
+    #[fhe_program(scheme = "bfv")]
+    fn add(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+        let _unused = a * b;
+        a + b
+    }
+
+    let app = Compiler::new()
+        .fhe_program(add)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(64))
+        .compile()
+        .unwrap();
+
+    // Only the inputs, the addition and the output survive.
+    let graph = &app.get_fhe_program(add).unwrap().fhe_program_fn.graph;
+    assert_eq!(graph.node_count(), 4);
+    assert!(graph
+        .node_weights()
+        .all(|n| n.operation != Operation::Multiply));
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = runtime.encrypt(Signed::from(15), &public_key).unwrap();
+    let b = runtime.encrypt(Signed::from(5), &public_key).unwrap();
+
+    let result = runtime
+        .run(app.get_fhe_program(add).unwrap(), vec![a, b], &public_key)
+        .unwrap();
+
+    let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, 20.into());
+}
+
+#[test]
+fn unused_parameter_and_dead_intermediate_value() {
+    #[fhe_program(scheme = "bfv")]
+    fn sub(a: Cipher<Signed>, _b: Cipher<Signed>, c: Cipher<Signed>) -> Cipher<Signed> {
+        let _unused = a + c;
+        a - c
+    }
+
+    let app = Compiler::new()
+        .fhe_program(sub)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(64))
+        .compile()
+        .unwrap();
+
+    // Only the inputs, the subtraction and the output survive.
+    let graph = &app.get_fhe_program(sub).unwrap().fhe_program_fn.graph;
+    assert_eq!(graph.node_count(), 5);
+    assert!(graph.node_weights().all(|n| n.operation != Operation::Add));
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = runtime.encrypt(Signed::from(15), &public_key).unwrap();
+    let b = runtime.encrypt(Signed::from(5), &public_key).unwrap();
+
+    let result = runtime
+        .run(
+            app.get_fhe_program(sub).unwrap(),
+            vec![a.clone(), a, b],
+            &public_key,
+        )
+        .unwrap();
+
+    let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, 10.into());
+}
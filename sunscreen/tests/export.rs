@@ -0,0 +1,76 @@
+use sunscreen::{
+    export_fhe_program,
+    types::{bfv::Signed, Cipher},
+    Compiler, ExportedGraph,
+};
+use sunscreen_compiler_macros::fhe_program;
+
+#[fhe_program(scheme = "bfv")]
+fn poly(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+    a * b * a + b
+}
+
+#[fhe_program(scheme = "bfv")]
+fn scale(a: Cipher<Signed>, factor: Signed) -> (Cipher<Signed>, Signed) {
+    (a * factor, factor)
+}
+
+#[test]
+fn can_export_compiled_fhe_program() {
+    let app = Compiler::new().fhe_program(poly).compile().unwrap();
+    let program = app.get_fhe_program(poly).unwrap();
+
+    let exported = export_fhe_program(program);
+
+    assert_eq!(
+        exported.nodes.len(),
+        program.fhe_program_fn.graph.node_count()
+    );
+
+    let output = exported
+        .nodes
+        .iter()
+        .find(|n| n.operation == "OutputCiphertext")
+        .unwrap();
+
+    assert_eq!(output.multiplicative_depth, 2);
+    assert_eq!(output.data_type.as_deref(), Some("Ciphertext"));
+
+    // Noise budgets should only ever decrease along the program.
+    let input_budget = exported.nodes[0].predicted_noise_budget.unwrap();
+    let output_budget = output.predicted_noise_budget.unwrap();
+
+    assert!(output_budget > 0.);
+    assert!(output_budget < input_budget);
+
+    let dot = exported.to_dot();
+
+    assert!(dot.starts_with("digraph {"));
+    assert!(dot.contains("Relinearize"));
+
+    let json = exported.to_json().unwrap();
+
+    assert_eq!(
+        serde_json::from_str::<ExportedGraph>(&json).unwrap(),
+        exported
+    );
+}
+
+#[test]
+fn plaintext_outputs_have_no_predicted_noise_budget() {
+    let app = Compiler::new().fhe_program(scale).compile().unwrap();
+    let program = app.get_fhe_program(scale).unwrap();
+
+    let exported = export_fhe_program(program);
+
+    let find = |operation: &str| {
+        exported
+            .nodes
+            .iter()
+            .find(|n| n.operation == operation)
+            .unwrap()
+    };
+
+    assert!(find("OutputCiphertext").predicted_noise_budget.is_some());
+    assert!(find("OutputPlaintext").predicted_noise_budget.is_none());
+}
@@ -0,0 +1,122 @@
+use sunscreen::{
+    fhe_program,
+    types::{
+        bfv::{Fractional, Signed},
+        Cipher, NumCiphertexts, TypeName,
+    },
+    Compiler, FheProgramFn, FheType, PlainModulusConstraint, Runtime,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, FheType)]
+struct Account {
+    balance: Signed,
+    rate: Fractional<64>,
+    history: [Signed; 3],
+}
+
+#[fhe_program(scheme = "bfv")]
+fn deposit(account: Cipher<Account>, amount: Cipher<Signed>) -> Cipher<Account> {
+    let mut fields = account.fields();
+
+    fields.history = [fields.history[1], fields.history[2], amount];
+    fields.balance = fields.balance + amount;
+    fields.rate = fields.rate * 2.0;
+
+    fields.join()
+}
+
+#[fhe_program(scheme = "bfv")]
+fn get_balance(account: Cipher<Account>) -> Cipher<Signed> {
+    account.fields().balance
+}
+
+#[test]
+fn derived_struct_is_sum_of_fields() {
+    assert_eq!(Account::NUM_CIPHERTEXTS, 5);
+
+    let name = Account::type_name();
+
+    assert!(name.name.ends_with("::Account"));
+    assert!(!name.is_encrypted);
+    assert!(Cipher::<Account>::type_name().is_encrypted);
+
+    let signature = deposit.signature();
+
+    assert_eq!(signature.arguments[0], Cipher::<Account>::type_name());
+    assert_eq!(signature.num_ciphertexts, vec![5]);
+}
+
+#[test]
+fn can_encrypt_and_decrypt_struct() {
+    let app = Compiler::new().fhe_program(get_balance).compile().unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let account = Account {
+        balance: Signed::from(-7),
+        rate: Fractional::from(0.25),
+        history: [1, 2, 3].map(Signed::from),
+    };
+
+    let ciphertext = runtime.encrypt(account, &public_key).unwrap();
+
+    let decrypted: Account = runtime.decrypt(&ciphertext, &private_key).unwrap();
+
+    assert_eq!(decrypted, account);
+}
+
+#[test]
+fn can_access_struct_fields() {
+    let app = Compiler::new()
+        .fhe_program(deposit)
+        .fhe_program(get_balance)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let account = Account {
+        balance: Signed::from(10),
+        rate: Fractional::from(0.5),
+        history: [1, 2, 3].map(Signed::from),
+    };
+
+    let account_c = runtime.encrypt(account, &public_key).unwrap();
+    let amount_c = runtime.encrypt(Signed::from(5), &public_key).unwrap();
+
+    let result = runtime
+        .run(
+            app.get_fhe_program(deposit).unwrap(),
+            vec![account_c, amount_c],
+            &public_key,
+        )
+        .unwrap();
+
+    let updated: Account = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(
+        updated,
+        Account {
+            balance: Signed::from(15),
+            rate: Fractional::from(1.0),
+            history: [2, 3, 5].map(Signed::from),
+        }
+    );
+
+    let result = runtime
+        .run(
+            app.get_fhe_program(get_balance).unwrap(),
+            vec![result[0].clone()],
+            &public_key,
+        )
+        .unwrap();
+
+    let balance: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(balance, 15.into());
+}
@@ -0,0 +1,182 @@
+use sunscreen::{
+    fhe_program,
+    types::{
+        bfv::{Fractional, Signed},
+        ops::{GraphCipherAdd, GraphCipherMul},
+        zkp::Field,
+        Cipher, FheType, TypeName,
+    },
+    zkp_program, Compiler, FheProgramFn, FheProgramFnExt, PlainModulusConstraint, Runtime,
+    ZkpProgramFnExt,
+};
+use sunscreen_zkp_backend::{bulletproofs::BulletproofsBackend, FieldSpec, ZkpBackend};
+
+#[fhe_program(scheme = "bfv")]
+fn dot<const N: usize>(a: [Cipher<Signed>; N], b: [Cipher<Signed>; N]) -> Cipher<Signed> {
+    let mut sum = a[0] * b[0];
+
+    for i in 1..N {
+        sum = sum + a[i] * b[i];
+    }
+
+    sum
+}
+
+#[fhe_program(scheme = "bfv")]
+fn square_sum<T>(a: Cipher<T>, b: Cipher<T>) -> Cipher<T>
+where
+    T: FheType
+        + TypeName
+        + Copy
+        + GraphCipherAdd<Left = T, Right = T>
+        + GraphCipherMul<Left = T, Right = T>,
+{
+    a * a + b * b
+}
+
+#[test]
+fn const_generic_instantiations_have_distinct_names() {
+    assert_eq!(dot::<2>().name(), "dot<2>");
+    assert_eq!(dot::<3>().name(), "dot<3>");
+    assert_eq!(dot::<3>().signature().arguments.len(), 2);
+}
+
+#[test]
+fn can_compile_and_run_const_generic_instantiations() {
+    let app = Compiler::new()
+        .fhe_program(dot::<2>())
+        .fhe_program(dot::<3>())
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = runtime
+        .encrypt([Signed::from(1), Signed::from(2)], &public_key)
+        .unwrap();
+    let b = runtime
+        .encrypt([Signed::from(3), Signed::from(4)], &public_key)
+        .unwrap();
+
+    let result = runtime
+        .run(
+            app.get_fhe_program(dot::<2>()).unwrap(),
+            vec![a, b],
+            &public_key,
+        )
+        .unwrap();
+
+    let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+    assert_eq!(c, 11.into());
+
+    let a = runtime
+        .encrypt(
+            [Signed::from(1), Signed::from(2), Signed::from(3)],
+            &public_key,
+        )
+        .unwrap();
+    let b = runtime
+        .encrypt(
+            [Signed::from(4), Signed::from(5), Signed::from(6)],
+            &public_key,
+        )
+        .unwrap();
+
+    let result = runtime
+        .run(
+            app.get_fhe_program(dot::<3>()).unwrap(),
+            vec![a, b],
+            &public_key,
+        )
+        .unwrap();
+
+    let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+    assert_eq!(c, 32.into());
+}
+
+#[test]
+fn can_run_type_generic_instantiations() {
+    let signed = square_sum::<Signed>();
+
+    let program = signed.compile().unwrap();
+    let runtime = signed.runtime().unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = runtime.encrypt(Signed::from(3), &public_key).unwrap();
+    let b = runtime.encrypt(Signed::from(4), &public_key).unwrap();
+
+    let result = runtime.run(&program, vec![a, b], &public_key).unwrap();
+
+    let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+    assert_eq!(c, 25.into());
+
+    let fractional = square_sum::<Fractional<64>>();
+
+    assert_ne!(fractional.name(), signed.name());
+    assert_eq!(
+        fractional.name(),
+        format!(
+            "square_sum<{}>",
+            <Fractional<64> as TypeName>::type_name().name
+        )
+    );
+
+    let program = fractional.compile().unwrap();
+    let runtime = fractional.runtime().unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = runtime
+        .encrypt(Fractional::<64>::from(1.5), &public_key)
+        .unwrap();
+    let b = runtime
+        .encrypt(Fractional::<64>::from(2.0), &public_key)
+        .unwrap();
+
+    let result = runtime.run(&program, vec![a, b], &public_key).unwrap();
+
+    let c: Fractional<64> = runtime.decrypt(&result[0], &private_key).unwrap();
+    assert_eq!(c, 6.25.into());
+}
+
+#[zkp_program]
+fn sum_equals<F: FieldSpec, const N: usize>(x: [Field<F>; N], #[public] sum: Field<F>) {
+    let mut acc = x[0];
+
+    for x_i in x.iter().skip(1) {
+        acc = acc + *x_i;
+    }
+
+    acc.constrain_eq(sum);
+}
+
+#[test]
+fn can_prove_const_generic_zkp_program() {
+    type BPField = Field<<BulletproofsBackend as ZkpBackend>::Field>;
+
+    assert_eq!(sum_equals::<4>().as_ref(), "sum_equals<4>");
+
+    let program = sum_equals::<4>().compile::<BulletproofsBackend>().unwrap();
+    let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+    let x = [1u8, 2, 3, 4].map(BPField::from);
+    let sum = BPField::from(10u8);
+
+    let proof = runtime
+        .proof_builder(&program)
+        .private_input(x)
+        .public_input(sum)
+        .prove()
+        .unwrap();
+
+    runtime
+        .verification_builder(&program)
+        .proof(&proof)
+        .public_input(sum)
+        .verify()
+        .unwrap();
+}
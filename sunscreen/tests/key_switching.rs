@@ -0,0 +1,86 @@
+use sunscreen::{
+    fhe_program,
+    types::{bfv::Signed, Cipher},
+    Compiler, FheProgramInput, Runtime, RuntimeError,
+};
+
+#[fhe_program(scheme = "bfv")]
+fn add(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+    a + b
+}
+
+#[test]
+fn can_switch_keys() {
+    let app = Compiler::new().fhe_program(add).compile().unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (old_public, old_private) = runtime.generate_keys().unwrap();
+    let (new_public, new_private) = runtime.generate_keys().unwrap();
+
+    let a = runtime.encrypt(Signed::from(5), &old_public).unwrap();
+
+    let ksk = runtime
+        .generate_key_switching_key(&old_private, &new_private)
+        .unwrap();
+
+    let a = runtime.switch_key(&a, &ksk).unwrap();
+
+    let decrypted: Signed = runtime.decrypt(&a, &new_private).unwrap();
+    assert_eq!(decrypted, 5.into());
+
+    // Switched ciphertexts can be used alongside ones encrypted under the new key.
+    let b = runtime.encrypt(Signed::from(7), &new_public).unwrap();
+
+    let args: Vec<FheProgramInput> = vec![a.into(), b.into()];
+
+    let c = runtime
+        .run(app.get_fhe_program(add).unwrap(), args, &new_public)
+        .unwrap();
+
+    let c: Signed = runtime.decrypt(&c[0], &new_private).unwrap();
+    assert_eq!(c, 12.into());
+}
+
+#[test]
+fn key_switching_key_roundtrips() {
+    let app = Compiler::new().fhe_program(add).compile().unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (old_public, old_private) = runtime.generate_keys().unwrap();
+    let (_, new_private) = runtime.generate_keys().unwrap();
+
+    let ksk = runtime
+        .generate_key_switching_key(&old_private, &new_private)
+        .unwrap();
+    let ksk = bincode::deserialize(&bincode::serialize(&ksk).unwrap()).unwrap();
+
+    let a = runtime.encrypt(Signed::from(-3), &old_public).unwrap();
+    let a = runtime.switch_key(&a, &ksk).unwrap();
+
+    let decrypted: Signed = runtime.decrypt(&a, &new_private).unwrap();
+    assert_eq!(decrypted, (-3).into());
+}
+
+#[test]
+fn key_switching_rejects_mismatched_params() {
+    let app = Compiler::new().fhe_program(add).compile().unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let mut params = app.params().clone();
+    params.plain_modulus += 1;
+
+    let other_runtime = Runtime::new_fhe(&params).unwrap();
+
+    let (_, old_private) = runtime.generate_keys().unwrap();
+    let (_, new_private) = other_runtime.generate_keys().unwrap();
+
+    assert_eq!(
+        runtime
+            .generate_key_switching_key(&old_private, &new_private)
+            .err(),
+        Some(RuntimeError::ParameterMismatch)
+    );
+}
@@ -0,0 +1,90 @@
+use sunscreen::{
+    fhe_program,
+    types::{bfv::Signed, Cipher},
+    Compiler, Error, ParamsRejection, PlainModulusConstraint,
+};
+
+#[fhe_program(scheme = "bfv")]
+fn cube(a: Cipher<Signed>, b: Cipher<Signed>, c: Cipher<Signed>) -> Cipher<Signed> {
+    a * b * c + a
+}
+
+#[test]
+fn reports_rejected_candidates_and_winner() {
+    let app = Compiler::new()
+        .fhe_program(cube)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(64))
+        .compile()
+        .unwrap();
+
+    let report = app.params_report().unwrap();
+    let (chosen, rejected) = report.candidates.split_last().unwrap();
+
+    assert_eq!(chosen.rejection, None);
+    assert_eq!(chosen.lattice_dimension, app.params().lattice_dimension);
+    assert_eq!(chosen.plain_modulus, Some(app.params().plain_modulus));
+
+    for c in rejected {
+        assert!(c.rejection.is_some());
+        assert!(c.lattice_dimension < chosen.lattice_dimension);
+    }
+
+    assert_eq!(report.programs.len(), 1);
+
+    let program = &report.programs[0];
+
+    assert_eq!(program.name, "cube");
+    assert_eq!(program.output_noise_budgets.len(), 1);
+    assert!(program.output_noise_budgets[0] >= 20.);
+    assert_eq!(program.multiplicative_depth(), 2);
+
+    let line = include_str!("params_report.rs")
+        .lines()
+        .position(|x| x.contains("a * b * c + a"))
+        .unwrap() as u32
+        + 1;
+
+    for step in &program.critical_path {
+        assert_eq!(step.source.unwrap().line, line);
+    }
+
+    assert!(report.to_string().contains("chosen"));
+}
+
+#[test]
+fn no_report_with_explicit_params() {
+    let app = Compiler::new()
+        .fhe_program(cube)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(64))
+        .compile()
+        .unwrap();
+
+    let app = Compiler::new()
+        .fhe_program(cube)
+        .with_params(app.params())
+        .compile()
+        .unwrap();
+
+    assert!(app.params_report().is_none());
+}
+
+#[test]
+fn failed_search_reports_rejected_candidates() {
+    // SEAL's plaintext moduli have at most 60 bits.
+    let result = Compiler::new()
+        .fhe_program(cube)
+        .plain_modulus_constraint(PlainModulusConstraint::BatchingMinimum(61))
+        .compile();
+
+    let err = match result {
+        Err(err @ Error::AllParamsRejected(_)) => err,
+        _ => panic!("Expected every candidate to be rejected."),
+    };
+
+    let candidates = err.params_candidates().unwrap();
+
+    assert!(!candidates.is_empty());
+    assert!(candidates
+        .iter()
+        .all(|c| c.rejection == Some(ParamsRejection::PlainModulus)));
+}
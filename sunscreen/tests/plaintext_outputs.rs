@@ -0,0 +1,119 @@
+use sunscreen::{
+    fhe::FheOperation,
+    fhe_program, fhe_var,
+    types::{bfv::Signed, Cipher, TypeName},
+    Compiler, FheProgramFn, FheProgramInput, Params, RunOptions, Runtime, RuntimeError, SchemeType,
+    SecurityLevel,
+};
+use sunscreen_fhe_program::Operation;
+
+#[fhe_program(scheme = "bfv")]
+fn scale(a: Cipher<Signed>, factor: Signed) -> (Cipher<Signed>, Signed) {
+    (a * factor, factor)
+}
+
+#[fhe_program(scheme = "bfv")]
+fn format_version() -> Signed {
+    fhe_var!(7)
+}
+
+fn get_params() -> Params {
+    Params {
+        lattice_dimension: 1024,
+        plain_modulus: 1024,
+        coeff_modulus: vec![1],
+        security_level: SecurityLevel::TC128,
+        scheme_type: SchemeType::Bfv,
+    }
+}
+
+#[test]
+fn unencrypted_returns_are_plaintext_outputs() {
+    assert_eq!(
+        scale.signature().returns,
+        vec![Cipher::<Signed>::type_name(), Signed::type_name()]
+    );
+    assert_eq!(scale.signature().num_ciphertexts, vec![1, 1]);
+
+    let context = scale.build(&get_params()).unwrap();
+
+    let outputs = context
+        .node_indices()
+        .map(|i| context[i].operation.clone())
+        .filter(|o| matches!(o, FheOperation::Output | FheOperation::PlaintextOutput))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        outputs,
+        vec![FheOperation::Output, FheOperation::PlaintextOutput]
+    );
+}
+
+#[test]
+fn can_return_plaintexts_alongside_ciphertexts() {
+    let app = Compiler::new().fhe_program(scale).compile().unwrap();
+    let program = app.get_fhe_program(scale).unwrap();
+
+    assert!(program
+        .fhe_program_fn
+        .graph
+        .node_weights()
+        .any(|n| n.operation == Operation::OutputPlaintext));
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public, private) = runtime.generate_keys().unwrap();
+
+    let a = runtime.encrypt(Signed::from(6), &public).unwrap();
+    let args: Vec<FheProgramInput> = vec![a.into(), Signed::from(3).into()];
+
+    let output = runtime
+        .run_with_options(program, args, &public, &RunOptions::default())
+        .unwrap();
+
+    assert_eq!(output.outputs.len(), 2);
+
+    let product: Signed = runtime
+        .decrypt(output.outputs[0].ciphertext().unwrap(), &private)
+        .unwrap();
+    let factor: Signed = runtime
+        .decode(output.outputs[1].plaintext().unwrap())
+        .unwrap();
+
+    assert_eq!(product, 18.into());
+    assert_eq!(factor, 3.into());
+
+    let a = runtime.encrypt(Signed::from(6), &public).unwrap();
+    let args: Vec<FheProgramInput> = vec![a.into(), Signed::from(3).into()];
+
+    assert!(matches!(
+        runtime.run(program, args, &public),
+        Err(RuntimeError::PlaintextOutputs)
+    ));
+}
+
+#[test]
+fn can_return_literals_unencrypted() {
+    let app = Compiler::new()
+        .fhe_program(format_version)
+        .compile()
+        .unwrap();
+    let program = app.get_fhe_program(format_version).unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public, _) = runtime.generate_keys().unwrap();
+
+    let output = runtime
+        .run_with_options(
+            program,
+            Vec::<FheProgramInput>::new(),
+            &public,
+            &RunOptions::default(),
+        )
+        .unwrap();
+
+    let version: Signed = runtime
+        .decode(output.outputs[0].plaintext().unwrap())
+        .unwrap();
+
+    assert_eq!(version, 7.into());
+}
@@ -0,0 +1,173 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use sunscreen::{
+    types::{bfv::Signed, Cipher},
+    Compiler, Error, FheProgramInput, InnerCiphertext, RunOptions, Runtime, RuntimeError,
+};
+use sunscreen_compiler_macros::fhe_program;
+
+#[fhe_program(scheme = "bfv")]
+fn poly(a: Cipher<Signed>, b: Signed) -> Cipher<Signed> {
+    a * a + b
+}
+
+#[test]
+fn can_run_batch() -> Result<(), Error> {
+    let app = Compiler::new().fhe_program(poly).compile()?;
+
+    let runtime = Runtime::new_fhe(app.params())?;
+
+    let (public, private) = runtime.generate_keys()?;
+
+    let args = (0..8)
+        .map(|i| {
+            let a = runtime.encrypt(Signed::from(i), &public)?;
+
+            Ok(vec![
+                FheProgramInput::from(a),
+                FheProgramInput::from(Signed::from(i)),
+            ])
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let results = runtime.run_batch(
+        app.get_fhe_program(poly).unwrap(),
+        args,
+        &public,
+        &RunOptions::new(),
+    )?;
+
+    assert_eq!(results.len(), 8);
+
+    for (i, result) in results.into_iter().enumerate() {
+        let c: Signed = runtime.decrypt(&result?[0], &private)?;
+        let i = i as i64;
+
+        assert_eq!(c, (i * i + i).into());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn run_batch_reports_per_instance_errors() -> Result<(), Error> {
+    let app = Compiler::new().fhe_program(poly).compile()?;
+
+    let runtime = Runtime::new_fhe(app.params())?;
+
+    let (public, private) = runtime.generate_keys()?;
+
+    let a = runtime.encrypt(Signed::from(3), &public)?;
+
+    let args: Vec<Vec<FheProgramInput>> = vec![
+        vec![a.clone().into(), Signed::from(1).into()],
+        // Wrong number of arguments.
+        vec![a.into()],
+    ];
+
+    let results = runtime.run_batch(
+        app.get_fhe_program(poly).unwrap(),
+        args,
+        &public,
+        &RunOptions::new(),
+    )?;
+
+    let c: Signed = runtime.decrypt(&results[0].as_ref().unwrap()[0], &private)?;
+
+    assert_eq!(c, 10.into());
+    assert!(matches!(results[1], Err(RuntimeError::ArgumentMismatch(_))));
+
+    Ok(())
+}
+
+#[test]
+fn strict_run_batch_validates_each_instance() -> Result<(), Error> {
+    let app = Compiler::new().fhe_program(poly).compile()?;
+
+    let runtime = Runtime::new_fhe(app.params())?;
+
+    let (public, private) = runtime.generate_keys()?;
+
+    let a = runtime.encrypt(Signed::from(3), &public)?;
+    let mut b = runtime.encrypt(Signed::from(4), &public)?;
+
+    match &mut b.inner {
+        InnerCiphertext::Seal(inner) => inner.clear(),
+    }
+
+    let args: Vec<Vec<FheProgramInput>> = vec![
+        vec![a.into(), Signed::from(1).into()],
+        // Missing its inner ciphertexts.
+        vec![b.into(), Signed::from(1).into()],
+    ];
+
+    let results = runtime.run_batch(
+        app.get_fhe_program(poly).unwrap(),
+        args,
+        &public,
+        &RunOptions::new().strict(true),
+    )?;
+
+    let c: Signed = runtime.decrypt(&results[0].as_ref().unwrap()[0], &private)?;
+
+    assert_eq!(c, 10.into());
+    assert!(matches!(
+        results[1],
+        Err(RuntimeError::IncorrectCiphertextCount)
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn can_run_batch_on_custom_thread_pool() -> Result<(), Error> {
+    let app = Compiler::new().fhe_program(poly).compile()?;
+
+    let runtime = Runtime::new_fhe(app.params())?;
+
+    let (public, private) = runtime.generate_keys()?;
+
+    let args = (0..4)
+        .map(|i| {
+            let a = runtime.encrypt(Signed::from(i), &public)?;
+
+            Ok(vec![
+                FheProgramInput::from(a),
+                FheProgramInput::from(Signed::from(i)),
+            ])
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let pool = Arc::new(
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .thread_name(|_| "batch".to_owned())
+            .build()
+            .unwrap(),
+    );
+
+    let off_pool = Arc::new(AtomicUsize::new(0));
+    let off_pool_clone = off_pool.clone();
+
+    let options = RunOptions::new().thread_pool(pool).progress(move |_| {
+        if std::thread::current().name() != Some("batch") {
+            off_pool_clone.fetch_add(1, Ordering::Relaxed);
+        }
+    });
+
+    let results = runtime.run_batch(app.get_fhe_program(poly).unwrap(), args, &public, &options)?;
+
+    assert_eq!(off_pool.load(Ordering::Relaxed), 0);
+
+    for (i, result) in results.into_iter().enumerate() {
+        let c: Signed = runtime.decrypt(&result?[0], &private)?;
+        let i = i as i64;
+
+        assert_eq!(c, (i * i + i).into());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,191 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use sunscreen::{
+    run_debug_with_predictions,
+    types::{bfv::Signed, Cipher},
+    CancellationToken, Compiler, FheProgramInput, NoiseReport, RunOptions, Runtime, RuntimeError,
+};
+use sunscreen_compiler_macros::fhe_program;
+use sunscreen_runtime::FheProgramRunFailure;
+
+#[fhe_program(scheme = "bfv")]
+fn poly(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+    a * b + a + b
+}
+
+#[test]
+fn can_run_with_options() {
+    let app = Compiler::new().fhe_program(poly).compile().unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public, private) = runtime.generate_keys().unwrap();
+
+    let a = runtime.encrypt(Signed::from(3), &public).unwrap();
+    let b = runtime.encrypt(Signed::from(4), &public).unwrap();
+
+    let completed = Arc::new(AtomicUsize::new(0));
+    let completed_clone = completed.clone();
+
+    let pool = Arc::new(
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap(),
+    );
+
+    let options = RunOptions::new()
+        .thread_pool(pool)
+        .progress(move |_| {
+            completed_clone.fetch_add(1, Ordering::Relaxed);
+        })
+        .trace(true);
+
+    let program = app.get_fhe_program(poly).unwrap();
+    let args: Vec<FheProgramInput> = vec![a.into(), b.into()];
+
+    let output = runtime
+        .run_with_options(program, args, &public, &options)
+        .unwrap();
+
+    let c: Signed = runtime
+        .decrypt(output.outputs[0].ciphertext().unwrap(), &private)
+        .unwrap();
+
+    assert_eq!(c, 19.into());
+
+    let node_count = program.fhe_program_fn.graph.node_count();
+
+    assert_eq!(completed.load(Ordering::Relaxed), node_count);
+    assert_eq!(output.trace.unwrap().nodes.len(), node_count);
+}
+
+#[test]
+fn can_run_async() {
+    let app = Compiler::new().fhe_program(poly).compile().unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public, private) = runtime.generate_keys().unwrap();
+
+    let a = runtime.encrypt(Signed::from(3), &public).unwrap();
+    let b = runtime.encrypt(Signed::from(4), &public).unwrap();
+
+    let args: Vec<FheProgramInput> = vec![a.into(), b.into()];
+
+    let handle = runtime
+        .run_async(
+            app.get_fhe_program(poly).unwrap(),
+            args,
+            &public,
+            RunOptions::new(),
+        )
+        .unwrap();
+
+    let output = handle.wait().unwrap();
+
+    let c: Signed = runtime
+        .decrypt(output.outputs[0].ciphertext().unwrap(), &private)
+        .unwrap();
+
+    assert_eq!(c, 19.into());
+    assert!(output.trace.is_none());
+}
+
+#[test]
+fn cancelled_async_run_fails() {
+    let app = Compiler::new().fhe_program(poly).compile().unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public, _) = runtime.generate_keys().unwrap();
+
+    let a = runtime.encrypt(Signed::from(3), &public).unwrap();
+    let b = runtime.encrypt(Signed::from(4), &public).unwrap();
+
+    let token = CancellationToken::new();
+    token.cancel();
+
+    let args: Vec<FheProgramInput> = vec![a.into(), b.into()];
+
+    let handle = runtime
+        .run_async(
+            app.get_fhe_program(poly).unwrap(),
+            args,
+            &public,
+            RunOptions::new().cancellation_token(token),
+        )
+        .unwrap();
+
+    assert!(handle.cancellation_token().is_cancelled());
+
+    assert_eq!(
+        handle.wait().err(),
+        Some(RuntimeError::FheProgramRunError(
+            FheProgramRunFailure::Cancelled
+        ))
+    );
+}
+
+#[test]
+fn can_run_debug_with_noise_report() {
+    let app = Compiler::new().fhe_program(poly).compile().unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public, private) = runtime.generate_keys().unwrap();
+
+    let a = runtime.encrypt(Signed::from(3), &public).unwrap();
+    let b = runtime.encrypt(Signed::from(4), &public).unwrap();
+
+    let program = app.get_fhe_program(poly).unwrap();
+    let args: Vec<FheProgramInput> = vec![a.into(), b.into()];
+
+    let output = run_debug_with_predictions(
+        &runtime,
+        program,
+        args,
+        &public,
+        &private,
+        &RunOptions::new(),
+    )
+    .unwrap();
+
+    let c: Signed = runtime
+        .decrypt(output.outputs[0].ciphertext().unwrap(), &private)
+        .unwrap();
+
+    assert_eq!(c, 19.into());
+
+    let report = output.noise_report.unwrap();
+
+    // Every node in this program produces a ciphertext.
+    assert_eq!(
+        report.nodes.len(),
+        program.fhe_program_fn.graph.node_count()
+    );
+
+    let output_budget = runtime
+        .measure_noise_budget(output.outputs[0].ciphertext().unwrap(), &private)
+        .unwrap();
+
+    assert_eq!(
+        report.min_noise_budget().unwrap().noise_budget,
+        output_budget
+    );
+
+    // The canonical embedding norm model bounds the noise, so it should
+    // never predict more budget than we measured.
+    assert!(report
+        .nodes
+        .iter()
+        .all(|n| n.predicted_noise_budget.unwrap() <= n.noise_budget as f64));
+
+    let json = report.to_json().unwrap();
+    let deserialized: NoiseReport = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(deserialized, report);
+}
@@ -0,0 +1,222 @@
+use sunscreen::{
+    fhe_program,
+    types::{
+        bfv::{select, Batched, Fractional, Rational, Signed, SELECT_DEPTH},
+        Cipher,
+    },
+    Compiler, PlainModulusConstraint, Runtime,
+};
+use sunscreen_fhe_program::Operation;
+
+#[fhe_program(scheme = "bfv")]
+fn select_signed(cond: Cipher<Signed>, a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+    select(cond, a, b)
+}
+
+#[fhe_program(scheme = "bfv")]
+fn select_fractional(
+    cond: Cipher<Signed>,
+    a: Cipher<Fractional<64>>,
+    b: Cipher<Fractional<64>>,
+) -> Cipher<Fractional<64>> {
+    select(cond, a, b)
+}
+
+#[fhe_program(scheme = "bfv")]
+fn select_rational(
+    cond: Cipher<Signed>,
+    a: Cipher<Rational>,
+    b: Cipher<Rational>,
+) -> Cipher<Rational> {
+    select(cond, a, b)
+}
+
+#[fhe_program(scheme = "bfv")]
+fn select_batched(
+    cond: Cipher<Signed>,
+    a: Cipher<Batched<4>>,
+    b: Cipher<Batched<4>>,
+) -> Cipher<Batched<4>> {
+    select(cond, a, b)
+}
+
+#[test]
+fn can_select_signed() {
+    let app = Compiler::new()
+        .fhe_program(select_signed)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(64))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = runtime.encrypt(Signed::from(-12), &public_key).unwrap();
+    let b = runtime.encrypt(Signed::from(7), &public_key).unwrap();
+
+    for (cond, expected) in [(1, -12), (0, 7)] {
+        let cond = runtime.encrypt(Signed::from(cond), &public_key).unwrap();
+
+        let result = runtime
+            .run(
+                app.get_fhe_program(select_signed).unwrap(),
+                vec![cond, a.clone(), b.clone()],
+                &public_key,
+            )
+            .unwrap();
+
+        let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+
+        assert_eq!(c, expected.into());
+    }
+}
+
+#[test]
+fn can_select_fractional() {
+    let app = Compiler::new()
+        .fhe_program(select_fractional)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(64))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = runtime
+        .encrypt(Fractional::<64>::from(-3.25), &public_key)
+        .unwrap();
+    let b = runtime
+        .encrypt(Fractional::<64>::from(0.5), &public_key)
+        .unwrap();
+
+    for (cond, expected) in [(1, -3.25), (0, 0.5)] {
+        let cond = runtime.encrypt(Signed::from(cond), &public_key).unwrap();
+
+        let result = runtime
+            .run(
+                app.get_fhe_program(select_fractional).unwrap(),
+                vec![cond, a.clone(), b.clone()],
+                &public_key,
+            )
+            .unwrap();
+
+        let c: Fractional<64> = runtime.decrypt(&result[0], &private_key).unwrap();
+
+        assert_eq!(c, expected.into());
+    }
+}
+
+#[test]
+fn can_select_rational() {
+    let app = Compiler::new()
+        .fhe_program(select_rational)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(64))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = runtime
+        .encrypt(Rational::try_from(-3.25).unwrap(), &public_key)
+        .unwrap();
+    let b = runtime
+        .encrypt(Rational::try_from(1.5).unwrap(), &public_key)
+        .unwrap();
+
+    for (cond, expected) in [(1, -3.25), (0, 1.5)] {
+        let cond = runtime.encrypt(Signed::from(cond), &public_key).unwrap();
+
+        let result = runtime
+            .run(
+                app.get_fhe_program(select_rational).unwrap(),
+                vec![cond, a.clone(), b.clone()],
+                &public_key,
+            )
+            .unwrap();
+
+        let c: Rational = runtime.decrypt(&result[0], &private_key).unwrap();
+
+        assert_eq!(c, Rational::try_from(expected).unwrap());
+    }
+}
+
+#[test]
+fn can_select_batched() {
+    let app = Compiler::new()
+        .fhe_program(select_batched)
+        .plain_modulus_constraint(PlainModulusConstraint::BatchingMinimum(0))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a_data = [vec![1, 2, 3, 4], vec![5, 6, 7, 8]];
+    let b_data = [vec![-1, -2, -3, -4], vec![-5, -6, -7, -8]];
+
+    let a = runtime
+        .encrypt(Batched::<4>::try_from(a_data.clone()).unwrap(), &public_key)
+        .unwrap();
+    let b = runtime
+        .encrypt(Batched::<4>::try_from(b_data.clone()).unwrap(), &public_key)
+        .unwrap();
+
+    for (cond, expected) in [(1, a_data), (0, b_data)] {
+        let cond = runtime.encrypt(Signed::from(cond), &public_key).unwrap();
+
+        let result = runtime
+            .run(
+                app.get_fhe_program(select_batched).unwrap(),
+                vec![cond, a.clone(), b.clone()],
+                &public_key,
+            )
+            .unwrap();
+
+        let c: Batched<4> = runtime.decrypt(&result[0], &private_key).unwrap();
+
+        assert_eq!(c, expected.try_into().unwrap());
+    }
+}
+
+#[test]
+fn select_multiplies_once_per_ciphertext() {
+    let app = Compiler::new()
+        .fhe_program(select_signed)
+        .fhe_program(select_rational)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(64))
+        .compile()
+        .unwrap();
+
+    for (program, expected) in [
+        (app.get_fhe_program(select_signed).unwrap(), 1),
+        (app.get_fhe_program(select_rational).unwrap(), 2),
+    ] {
+        let multiplies = program
+            .fhe_program_fn
+            .graph
+            .node_weights()
+            .filter(|n| n.operation == Operation::Multiply)
+            .count();
+
+        assert_eq!(multiplies, expected);
+    }
+}
+
+#[test]
+fn select_depth_matches_params_report() {
+    let app = Compiler::new()
+        .fhe_program(select_signed)
+        .fhe_program(select_rational)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(64))
+        .compile()
+        .unwrap();
+
+    let report = app.params_report().unwrap();
+
+    assert_eq!(report.programs.len(), 2);
+
+    for program in &report.programs {
+        assert_eq!(program.multiplicative_depth(), SELECT_DEPTH);
+    }
+}
@@ -1,5 +1,9 @@
 use seal_fhe::{CoefficientModulus, SecurityLevel};
-use sunscreen::types::bfv::Signed;
+use sunscreen::{
+    fhe_program,
+    types::{bfv::Signed, Cipher},
+    Compiler, FheApplication, FheProgramInput,
+};
 use sunscreen_fhe_program::SchemeType;
 use sunscreen_runtime::{Ciphertext, Params, Runtime};
 
@@ -62,3 +66,32 @@ fn can_roundtrip_ciphertexts_json() {
     let actual: i64 = v.into();
     assert_eq!(actual, expected);
 }
+
+#[fhe_program(scheme = "bfv")]
+fn add(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+    a + b
+}
+
+#[test]
+fn can_roundtrip_application() {
+    let app = Compiler::new().fhe_program(add).compile().unwrap();
+
+    let app = FheApplication::from_bytes(&app.to_bytes().unwrap()).unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = runtime.encrypt(Signed::from(2), &public_key).unwrap();
+    let b = runtime.encrypt(Signed::from(3), &public_key).unwrap();
+
+    let args: Vec<FheProgramInput> = vec![a.into(), b.into()];
+
+    let c = runtime
+        .run(app.get_fhe_program(add).unwrap(), args, &public_key)
+        .unwrap();
+
+    let c: Signed = runtime.decrypt(&c[0], &private_key).unwrap();
+
+    assert_eq!(c, 5.into());
+}
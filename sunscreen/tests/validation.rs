@@ -0,0 +1,157 @@
+use seal_fhe::{BFVEvaluator, BfvEncryptionParametersBuilder, Context, Evaluator, Modulus};
+use sunscreen::{
+    fhe_program,
+    types::{bfv::Signed, Cipher},
+    Ciphertext, Compiler, FheProgramInput, InnerCiphertext, Params, RunOptions, Runtime,
+    RuntimeError,
+};
+
+#[fhe_program(scheme = "bfv")]
+fn add(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+    a + b
+}
+
+fn square_inner_ciphertexts(params: &Params, c: &Ciphertext) -> Ciphertext {
+    let encryption_params = BfvEncryptionParametersBuilder::new()
+        .set_poly_modulus_degree(params.lattice_dimension)
+        .set_coefficient_modulus(
+            params
+                .coeff_modulus
+                .iter()
+                .map(|x| Modulus::new(*x).unwrap())
+                .collect(),
+        )
+        .set_plain_modulus_u64(params.plain_modulus)
+        .build()
+        .unwrap();
+
+    let context = Context::new(&encryption_params, true, params.security_level).unwrap();
+    let evaluator = BFVEvaluator::new(&context).unwrap();
+
+    let mut c = c.clone();
+
+    match &mut c.inner {
+        InnerCiphertext::Seal(inner) => {
+            for x in inner.iter_mut() {
+                // Squaring without relinearizing results in 3 polynomials.
+                evaluator.square_inplace(&mut x.data).unwrap();
+            }
+        }
+    }
+
+    c
+}
+
+#[test]
+fn fresh_ciphertext_is_valid() {
+    let app = Compiler::new().fhe_program(add).compile().unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, _) = runtime.generate_keys().unwrap();
+
+    let a = runtime.encrypt(Signed::from(3), &public_key).unwrap();
+
+    a.validate(app.params()).unwrap();
+}
+
+#[test]
+fn rejects_ciphertext_with_wrong_params() {
+    let app = Compiler::new().fhe_program(add).compile().unwrap();
+
+    let mut params = app.params().clone();
+    params.plain_modulus += 1;
+
+    let runtime = Runtime::new_fhe(&params).unwrap();
+    let (public_key, _) = runtime.generate_keys().unwrap();
+
+    let a = runtime.encrypt(Signed::from(3), &public_key).unwrap();
+
+    assert_eq!(
+        a.validate(app.params()).err(),
+        Some(RuntimeError::ParameterMismatch)
+    );
+}
+
+#[test]
+fn rejects_ciphertext_with_wrong_size() {
+    let app = Compiler::new().fhe_program(add).compile().unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, _) = runtime.generate_keys().unwrap();
+
+    let a = runtime.encrypt(Signed::from(3), &public_key).unwrap();
+    let a = square_inner_ciphertexts(app.params(), &a);
+
+    assert!(matches!(
+        a.validate(app.params()),
+        Err(RuntimeError::InvalidCiphertext(_))
+    ));
+}
+
+#[test]
+fn strict_run_rejects_invalid_ciphertexts() {
+    let app = Compiler::new().fhe_program(add).compile().unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, _) = runtime.generate_keys().unwrap();
+
+    let a = runtime.encrypt(Signed::from(3), &public_key).unwrap();
+    let b = runtime.encrypt(Signed::from(4), &public_key).unwrap();
+    let b = square_inner_ciphertexts(app.params(), &b);
+
+    let args: Vec<FheProgramInput> = vec![a.into(), b.into()];
+
+    let result = runtime.run_with_options(
+        app.get_fhe_program(add).unwrap(),
+        args,
+        &public_key,
+        &RunOptions::new().strict(true),
+    );
+
+    assert!(matches!(result, Err(RuntimeError::InvalidCiphertext(_))));
+}
+
+#[test]
+fn strict_run_rejects_missing_inner_ciphertexts() {
+    let app = Compiler::new().fhe_program(add).compile().unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = runtime.encrypt(Signed::from(3), &public_key).unwrap();
+    let mut b = runtime.encrypt(Signed::from(4), &public_key).unwrap();
+
+    match &mut b.inner {
+        InnerCiphertext::Seal(inner) => inner.clear(),
+    }
+
+    let args: Vec<FheProgramInput> = vec![a.clone().into(), b.into()];
+
+    let result = runtime.run_with_options(
+        app.get_fhe_program(add).unwrap(),
+        args,
+        &public_key,
+        &RunOptions::new().strict(true),
+    );
+
+    assert_eq!(result.err(), Some(RuntimeError::IncorrectCiphertextCount));
+
+    // Valid arguments still run in strict mode.
+    let b = runtime.encrypt(Signed::from(4), &public_key).unwrap();
+    let args: Vec<FheProgramInput> = vec![a.into(), b.into()];
+
+    let output = runtime
+        .run_with_options(
+            app.get_fhe_program(add).unwrap(),
+            args,
+            &public_key,
+            &RunOptions::new().strict(true),
+        )
+        .unwrap();
+
+    let c: Signed = runtime
+        .decrypt(output.outputs[0].ciphertext().unwrap(), &private_key)
+        .unwrap();
+
+    assert_eq!(c, 7.into());
+}
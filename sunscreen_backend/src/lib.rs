@@ -16,6 +16,7 @@ mod transforms;
 pub use error::*;
 
 use sunscreen_fhe_program::FheProgram;
+use sunscreen_runtime::Params;
 
 use transforms::transform_intermediate_representation;
 
@@ -25,7 +26,7 @@ use transforms::transform_intermediate_representation;
 pub fn compile(ir: &FheProgram) -> FheProgram {
     let mut clone = ir.clone();
 
-    transform_intermediate_representation(&mut clone);
+    transform_intermediate_representation(&mut clone, None);
 
     clone
 }
@@ -34,7 +35,37 @@ pub fn compile(ir: &FheProgram) -> FheProgram {
  * Consumes the given [`FheProgram`] and compiles it.
  */
 pub fn compile_inplace(mut ir: FheProgram) -> FheProgram {
-    transform_intermediate_representation(&mut ir);
+    transform_intermediate_representation(&mut ir, None);
+
+    ir
+}
+
+/**
+ * Clones the given [`FheProgram`] and compiles it for the given
+ * [`Params`].
+ *
+ * # Remarks
+ * Unlike [`compile`], this lets transformations consult the noise
+ * model under these parameters.
+ */
+pub fn compile_with_params(ir: &FheProgram, params: &Params) -> FheProgram {
+    let mut clone = ir.clone();
+
+    transform_intermediate_representation(&mut clone, Some(params));
+
+    clone
+}
+
+/**
+ * Consumes the given [`FheProgram`] and compiles it for the given
+ * [`Params`].
+ *
+ * # Remarks
+ * Unlike [`compile_inplace`], this lets transformations consult the noise
+ * model under these parameters.
+ */
+pub fn compile_inplace_with_params(mut ir: FheProgram, params: &Params) -> FheProgram {
+    transform_intermediate_representation(&mut ir, Some(params));
 
     ir
 }
@@ -36,6 +36,42 @@ pub const NOISE_MAX: f64 = NOISE_STD_DEV * NOISE_NUM_STD_DEVIATIONS;
  * validate before using this function to ascertain this.
  */
 pub fn predict_noise(model: &(dyn NoiseModel + Sync), fhe_program: &FheProgram) -> Vec<f64> {
+    predict_node_noise(model, fhe_program)
+        .into_iter()
+        .zip(fhe_program.graph.node_indices())
+        .filter_map(|(x, node_id)| match fhe_program.graph[node_id].operation {
+            OutputCiphertext => Some(x),
+            _ => None,
+        })
+        .collect()
+}
+
+/**
+ * Returns the predicted noise budget (in bits) after every node in the
+ * given [`FheProgram`], indexed by node index. Pass the result to
+ * [`NoiseReport::set_predicted_noise_budgets`](sunscreen_runtime::NoiseReport::set_predicted_noise_budgets)
+ * to compare the predictions against the budgets measured by
+ * [`run_debug`](sunscreen_runtime::GenericRuntime::run_debug).
+ *
+ * # Panic
+ * Panics if the FHE program is not well formed. You should call
+ * validate before using this function to ascertain this.
+ */
+pub fn predict_noise_budgets(
+    model: &(dyn NoiseModel + Sync),
+    fhe_program: &FheProgram,
+) -> Vec<f64> {
+    predict_node_noise(model, fhe_program)
+        .into_iter()
+        .map(noise_to_noise_budget)
+        .collect()
+}
+
+/**
+ * Returns the predicted noise level after every node in the given
+ * [`FheProgram`], indexed by node index.
+ */
+fn predict_node_noise(model: &(dyn NoiseModel + Sync), fhe_program: &FheProgram) -> Vec<f64> {
     let mut noise_levels: Vec<AtomicCell<f64>> = Vec::with_capacity(fhe_program.graph.node_count());
 
     for _ in 0..fhe_program.graph.node_count() {
@@ -117,7 +153,7 @@ pub fn predict_noise(model: &(dyn NoiseModel + Sync), fhe_program: &FheProgram)
 
                     model.output(output_id, noise_levels[x.index()].load())
                 }
-                Literal(_) => 0.0,
+                Literal(_) | OutputPlaintext => 0.0,
                 ShiftLeft => {
                     let (left, right) = query.get_binary_operands(node_id).unwrap();
 
@@ -159,14 +195,7 @@ pub fn predict_noise(model: &(dyn NoiseModel + Sync), fhe_program: &FheProgram)
     )
     .unwrap(); // No errors returned, so unwrap is safe.
 
-    noise_levels
-        .iter()
-        .zip(fhe_program.graph.node_indices())
-        .filter_map(|(x, node_id)| match fhe_program.graph[node_id].operation {
-            OutputCiphertext => Some(x.load()),
-            _ => None,
-        })
-        .collect()
+    noise_levels.iter().map(|x| x.load()).collect()
 }
 
 /**
@@ -0,0 +1,418 @@
+use std::convert::Infallible;
+
+use seal_fhe::Plaintext;
+use sunscreen_compiler_common::{
+    forward_traverse_mut,
+    transforms::{GraphTransforms, Transform},
+    EdgeInfo, GraphQuery, NodeInfo,
+};
+use sunscreen_fhe_program::{FheProgram, Literal, Operation};
+use sunscreen_runtime::{InnerPlaintext, WithContext};
+
+use petgraph::{stable_graph::NodeIndex, Direction};
+
+type FheGraphQuery<'a> = GraphQuery<'a, NodeInfo<Operation>, EdgeInfo>;
+
+/**
+ * Decodes the given node's plaintext literal, if it is one.
+ */
+fn get_plaintext_literal(query: &FheGraphQuery, id: NodeIndex) -> Option<WithContext<Plaintext>> {
+    match &query.get_node(id)?.operation {
+        Operation::Literal(Literal::Plaintext(bytes)) => {
+            let InnerPlaintext::Seal(mut p) = InnerPlaintext::from_bytes(bytes).ok()?;
+
+            if p.len() != 1 || p[0].data.is_ntt_form() {
+                return None;
+            }
+
+            p.pop()
+        }
+        _ => None,
+    }
+}
+
+fn coefficients(p: &Plaintext) -> Vec<u64> {
+    (0..p.len()).map(|i| p.get_coefficient(i)).collect()
+}
+
+/**
+ * Computes `a + b` if `negate_a` is false and `-a + b` otherwise, modulo
+ * `plain_modulus`.
+ */
+fn add_coefficients(a: &[u64], b: &[u64], negate_a: bool, plain_modulus: u64) -> Vec<u64> {
+    (0..a.len().max(b.len()))
+        .map(|i| {
+            let a = a.get(i).copied().unwrap_or(0);
+            let b = b.get(i).copied().unwrap_or(0);
+            let a = if negate_a && a != 0 {
+                plain_modulus - a
+            } else {
+                a
+            };
+
+            ((a as u128 + b as u128) % plain_modulus as u128) as u64
+        })
+        .collect()
+}
+
+/**
+ * Returns the index and value of each nonzero coefficient in `a`.
+ */
+fn nonzero_coefficients(a: &[u64]) -> Vec<(usize, u64)> {
+    a.iter()
+        .copied()
+        .enumerate()
+        .filter(|(_, x)| *x != 0)
+        .collect()
+}
+
+/**
+ * Computes `a * b` in the plaintext ring, i.e. modulo `x^n + 1` and
+ * `plain_modulus`, where `a` and `b` are sparse polynomials given by
+ * their nonzero coefficients.
+ *
+ * # Remarks
+ * This takes `a.len() * b.len()` multiplications, so callers should
+ * only use it on sparse polynomials.
+ */
+fn multiply_coefficients(
+    a: &[(usize, u64)],
+    b: &[(usize, u64)],
+    n: usize,
+    plain_modulus: u64,
+) -> Vec<u64> {
+    let t = plain_modulus as u128;
+    let mut c = vec![0u128; n];
+
+    for (i, a) in a {
+        for (j, b) in b {
+            let prod = (*a as u128 * *b as u128) % t;
+            let k = i + j;
+
+            // x^n = -1
+            if k < n {
+                c[k] = (c[k] + prod) % t;
+            } else {
+                c[k - n] = (c[k - n] + t - prod) % t;
+            }
+        }
+    }
+
+    c.into_iter().map(|x| x as u64).collect()
+}
+
+/**
+ * Combines the right operands of the `inner` and `outer` plaintext
+ * operations into a single literal such that `(y inner a) outer b` equals
+ * `y outer c`. Returns [`None`] if the operations don't combine or the
+ * literals can't be decoded.
+ */
+fn fold_literals(
+    inner: &Operation,
+    outer: &Operation,
+    a: WithContext<Plaintext>,
+    b: WithContext<Plaintext>,
+) -> Option<Literal> {
+    if a.params != b.params {
+        return None;
+    }
+
+    let params = a.params;
+    let plain_modulus = params.plain_modulus;
+    let (a, b) = (coefficients(&a.data), coefficients(&b.data));
+
+    let c = match (inner, outer) {
+        (Operation::MultiplyPlaintext, Operation::MultiplyPlaintext) => {
+            let n = params.lattice_dimension as usize;
+            let (a, b) = (nonzero_coefficients(&a), nonzero_coefficients(&b));
+
+            // Schoolbook multiplication of dense literals (e.g. batched
+            // vectors) takes O(n^2) time, so only fold sparse literals.
+            if a.len() * b.len() > n {
+                return None;
+            }
+
+            multiply_coefficients(&a, &b, n, plain_modulus)
+        }
+        // y + a - b = y - (-a + b) and y - a + b = y + (-a + b)
+        (
+            Operation::AddPlaintext | Operation::SubPlaintext,
+            Operation::AddPlaintext | Operation::SubPlaintext,
+        ) => add_coefficients(&a, &b, inner != outer, plain_modulus),
+        _ => return None,
+    };
+
+    // SEAL disallows multiplying by a zero plaintext, so leave such chains
+    // alone.
+    let len = c.iter().rposition(|x| *x != 0)? + 1;
+
+    let mut p = Plaintext::new().ok()?;
+    p.resize(len);
+
+    for (i, x) in c.iter().take(len).enumerate() {
+        p.set_coefficient(i, *x);
+    }
+
+    let p = InnerPlaintext::Seal(vec![WithContext { params, data: p }]);
+
+    Some(Literal::Plaintext(p.to_bytes().ok()?))
+}
+
+/**
+ * Folds chains of plaintext operations with literal operands into a
+ * single operation. For example, `(x + a) - b` becomes `x + (a - b)` and
+ * `(x * a) * b` becomes `x * (a * b)`, where `a * b` and `a - b` are
+ * computed at compile time.
+ *
+ * # Remarks
+ * Plaintext operations always take a ciphertext left operand, so the IR
+ * can only combine plaintexts through such chains. Each fold saves a
+ * ciphertext operation and, for multiplications, noise.
+ *
+ * Chains are only folded where the intermediate result isn't used
+ * elsewhere. Plaintext inputs aren't known until run time and aren't
+ * folded. Multiplication chains are only folded when the literals are
+ * sparse, as multiplying dense literals at compile time is expensive.
+ * Replaced nodes are left in the graph and should be removed by pruning it
+ * to its outputs.
+ */
+pub fn apply_constant_folding(ir: &mut FheProgram) {
+    forward_traverse_mut(&mut ir.graph.0, |query, id| {
+        let mut transforms = GraphTransforms::new();
+
+        let outer = &query.get_node(id).unwrap().operation;
+
+        if !matches!(
+            outer,
+            Operation::AddPlaintext | Operation::SubPlaintext | Operation::MultiplyPlaintext
+        ) {
+            return Ok::<_, Infallible>(transforms);
+        }
+
+        let (left, right) = query.get_binary_operands(id).unwrap();
+        let inner = &query.get_node(left).unwrap().operation;
+
+        if !matches!(
+            inner,
+            Operation::AddPlaintext | Operation::SubPlaintext | Operation::MultiplyPlaintext
+        ) || query.edges_directed(left, Direction::Outgoing).count() != 1
+        {
+            return Ok(transforms);
+        }
+
+        let (operand, inner_right) = query.get_binary_operands(left).unwrap();
+
+        let literal = match (
+            get_plaintext_literal(&query, inner_right),
+            get_plaintext_literal(&query, right),
+        ) {
+            (Some(a), Some(b)) => fold_literals(inner, outer, a, b),
+            _ => None,
+        };
+
+        let literal = match literal {
+            Some(x) => x,
+            None => return Ok(transforms),
+        };
+
+        let literal = transforms.push(Transform::AddNode(NodeInfo::new(Operation::Literal(
+            literal,
+        ))));
+
+        transforms.push(Transform::RemoveEdge(left.into(), id.into()));
+        transforms.push(Transform::RemoveEdge(right.into(), id.into()));
+        transforms.push(Transform::AddEdge(
+            operand.into(),
+            id.into(),
+            EdgeInfo::Left,
+        ));
+        transforms.push(Transform::AddEdge(
+            literal.into(),
+            id.into(),
+            EdgeInfo::Right,
+        ));
+
+        Ok(transforms)
+    })
+    .unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use seal_fhe::{CoefficientModulus, SecurityLevel};
+    use sunscreen_fhe_program::{FheProgramTrait, SchemeType};
+    use sunscreen_runtime::Params;
+
+    const LATTICE_DIMENSION: u64 = 1024;
+
+    fn params() -> Params {
+        Params {
+            lattice_dimension: LATTICE_DIMENSION,
+            plain_modulus: 17,
+            coeff_modulus: CoefficientModulus::bfv_default(LATTICE_DIMENSION, SecurityLevel::TC128)
+                .unwrap()
+                .iter()
+                .map(|x| x.value())
+                .collect(),
+            scheme_type: SchemeType::Bfv,
+            security_level: SecurityLevel::TC128,
+        }
+    }
+
+    fn literal(ir: &mut FheProgram, coeffs: &[u64]) -> NodeIndex {
+        let mut p = Plaintext::new().unwrap();
+        p.resize(coeffs.len());
+
+        for (i, c) in coeffs.iter().enumerate() {
+            p.set_coefficient(i, *c);
+        }
+
+        let p = InnerPlaintext::Seal(vec![WithContext {
+            params: params(),
+            data: p,
+        }]);
+
+        ir.add_input_literal(Literal::Plaintext(p.to_bytes().unwrap()))
+    }
+
+    fn literal_coefficients(ir: &FheProgram, id: NodeIndex) -> Vec<u64> {
+        let query = GraphQuery::new(&ir.graph.0);
+
+        coefficients(&get_plaintext_literal(&query, id).unwrap().data)
+    }
+
+    fn fold(ir: &mut FheProgram) {
+        apply_constant_folding(ir);
+
+        ir.validate().unwrap();
+    }
+
+    fn live_node_count(ir: &FheProgram) -> usize {
+        ir.prune(&ir.get_outputs().collect::<Vec<NodeIndex>>())
+            .graph
+            .node_count()
+    }
+
+    #[test]
+    fn folds_add_sub_chain() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let x = ir.add_input_ciphertext(0);
+        let a = literal(&mut ir, &[1, 2]);
+        let b = literal(&mut ir, &[5]);
+        let c = literal(&mut ir, &[0, 0, 3]);
+
+        // ((x + a) - b) + c
+        let add = ir.add_binary_operation(Operation::AddPlaintext, x, a);
+        let sub = ir.add_binary_operation(Operation::SubPlaintext, add, b);
+        let add_2 = ir.add_binary_operation(Operation::AddPlaintext, sub, c);
+        ir.add_output_ciphertext(add_2);
+
+        fold(&mut ir);
+
+        // x + (a - b + c)
+        assert_eq!(live_node_count(&ir), 4);
+
+        let query = GraphQuery::new(&ir.graph.0);
+        let (left, right) = query.get_binary_operands(add_2).unwrap();
+
+        assert_eq!(left, x);
+        assert_eq!(literal_coefficients(&ir, right), vec![13, 2, 3]);
+    }
+
+    #[test]
+    fn folds_multiply_chain() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let x = ir.add_input_ciphertext(0);
+        let n = LATTICE_DIMENSION as usize;
+
+        let mut a = vec![0; n];
+        a[0] = 2;
+        a[n - 1] = 1;
+
+        let a = literal(&mut ir, &a);
+        let b = literal(&mut ir, &[3, 1]);
+
+        let mul = ir.add_multiply_plaintext(x, a);
+        let mul_2 = ir.add_multiply_plaintext(mul, b);
+        ir.add_output_ciphertext(mul_2);
+
+        fold(&mut ir);
+
+        assert_eq!(live_node_count(&ir), 4);
+
+        let query = GraphQuery::new(&ir.graph.0);
+        let (left, right) = query.get_binary_operands(mul_2).unwrap();
+
+        // (2 + x^(n-1))(3 + x) = 6 + 2x + 3x^(n-1) + x^n = 5 + 2x + 3x^(n-1)
+        let mut expected = vec![0; n];
+        expected[0] = 5;
+        expected[1] = 2;
+        expected[n - 1] = 3;
+
+        assert_eq!(left, x);
+        assert_eq!(literal_coefficients(&ir, right), expected);
+    }
+
+    #[test]
+    fn keeps_dense_multiply_chain() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let x = ir.add_input_ciphertext(0);
+        let n = LATTICE_DIMENSION as usize;
+
+        let a = literal(&mut ir, &vec![1; n]);
+        let b = literal(&mut ir, &[3, 1]);
+
+        let mul = ir.add_multiply_plaintext(x, a);
+        let mul_2 = ir.add_multiply_plaintext(mul, b);
+        ir.add_output_ciphertext(mul_2);
+
+        fold(&mut ir);
+
+        let query = GraphQuery::new(&ir.graph.0);
+
+        assert_eq!(query.get_binary_operands(mul_2).unwrap(), (mul, b));
+    }
+
+    #[test]
+    fn keeps_shared_intermediate_results() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let x = ir.add_input_ciphertext(0);
+        let a = literal(&mut ir, &[1]);
+        let b = literal(&mut ir, &[2]);
+
+        let add = ir.add_binary_operation(Operation::AddPlaintext, x, a);
+        let add_2 = ir.add_binary_operation(Operation::AddPlaintext, add, b);
+        ir.add_output_ciphertext(add);
+        ir.add_output_ciphertext(add_2);
+
+        fold(&mut ir);
+
+        let query = GraphQuery::new(&ir.graph.0);
+
+        assert_eq!(query.get_binary_operands(add_2).unwrap(), (add, b));
+    }
+
+    #[test]
+    fn keeps_plaintext_inputs_and_mixed_chains() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let x = ir.add_input_ciphertext(0);
+        let p = ir.add_input_plaintext(1);
+        let a = literal(&mut ir, &[1]);
+        let b = literal(&mut ir, &[2]);
+
+        let add = ir.add_binary_operation(Operation::AddPlaintext, x, p);
+        let add_2 = ir.add_binary_operation(Operation::AddPlaintext, add, a);
+        let mul = ir.add_multiply_plaintext(add_2, b);
+        ir.add_output_ciphertext(mul);
+
+        fold(&mut ir);
+
+        assert_eq!(live_node_count(&ir), 8);
+    }
+}
@@ -1,7 +1,8 @@
+use std::collections::HashSet;
 use std::convert::Infallible;
 
 use sunscreen_compiler_common::{
-    forward_traverse_mut,
+    forward_traverse, forward_traverse_mut,
     transforms::{GraphTransforms, Transform},
     EdgeInfo, GraphQuery, NodeInfo,
 };
@@ -12,47 +13,210 @@ use sunscreen_fhe_program::{
 
 use petgraph::{stable_graph::NodeIndex, visit::EdgeRef, Direction};
 
+use crate::noise_model::{predict_noise, NoiseModel};
+
 type FheGraphQuery<'a> = GraphQuery<'a, NodeInfo<Operation>, EdgeInfo>;
 
-pub fn apply_insert_relinearizations(ir: &mut FheProgram) {
-    let insert_relin = |id: NodeIndex, query: FheGraphQuery| {
-        let mut transforms = GraphTransforms::new();
+fn insert_relin(
+    id: NodeIndex,
+    query: FheGraphQuery,
+) -> GraphTransforms<NodeInfo<Operation>, EdgeInfo> {
+    let mut transforms = GraphTransforms::new();
 
-        let relin_node = transforms.push(Transform::AddNode(NodeInfo {
-            operation: Operation::Relinearize,
-        }));
+    let source = query.get_node(id).unwrap().source;
+
+    let relin_node = transforms.push(Transform::AddNode(NodeInfo::with_source(
+        Operation::Relinearize,
+        source,
+    )));
+
+    transforms.push(Transform::AddEdge(
+        id.into(),
+        relin_node.into(),
+        EdgeInfo::Unary,
+    ));
+
+    for e in query.edges_directed(id, Direction::Outgoing) {
+        let operand_type = e.weight();
 
+        transforms.push(Transform::RemoveEdge(id.into(), e.target().into()));
         transforms.push(Transform::AddEdge(
-            id.into(),
             relin_node.into(),
-            EdgeInfo::Unary,
+            e.target().into(),
+            *operand_type,
         ));
+    }
+
+    transforms
+}
+
+/**
+ * Whether SEAL can perform this operation on an unrelinearized (i.e. 3
+ * polynomial) ciphertext. The result is then unrelinearized as well.
+ */
+fn accepts_unrelinearized(operation: &Operation) -> bool {
+    matches!(
+        operation,
+        Add | Sub | Negate | AddPlaintext | SubPlaintext | MultiplyPlaintext
+    )
+}
+
+/**
+ * Inserts the relinearizations needed after ciphertext multiplications.
+ * Rather than relinearizing every product, this delays relinearization
+ * while a product feeds only additions, subtractions, negations and
+ * plaintext operations. E.g. a sum of products then needs a single
+ * relinearization rather than one per term.
+ *
+ * # Remarks
+ * Plaintext multiplications don't increase the number of polynomials (see
+ * the multiply_plaintext_does_not_increase_polynomials test in
+ * assumptions.rs), so only ciphertext multiplications need
+ * relinearizing.
+ *
+ * A product's relinearization is only delayed when the product has exactly
+ * one consumer and that consumer accepts unrelinearized operands. Otherwise,
+ * delaying would duplicate the relinearization across consumers, so this
+ * pass never inserts more relinearizations than there are ciphertext
+ * multiplications.
+ *
+ * Operations on unrelinearized ciphertexts cost more than on relinearized
+ * ones, so a delayed relinearization must replace at least 2 eager ones.
+ * A lone product gets relinearized right away.
+ *
+ * When given a noise model, this predicts the noise in the program's
+ * outputs with and without each delay and relinearizes eagerly whenever
+ * delaying would increase it.
+ */
+pub fn apply_insert_relinearizations(
+    ir: &mut FheProgram,
+    noise_model: Option<&(dyn NoiseModel + Sync)>,
+) {
+    // Nodes producing an unrelinearized ciphertext that flows directly into
+    // their consumer.
+    let mut delayed = HashSet::new();
+    // Unrelinearized nodes whose consumers need a relinearized ciphertext.
+    let mut relin_points = HashSet::new();
+
+    forward_traverse(&ir.graph.0, |query, id| {
+        let operation = &query.get_node(id).unwrap().operation;
+
+        let is_unrelinearized = match operation {
+            Multiply => true,
+            x if accepts_unrelinearized(x) => query
+                .neighbors_directed(id, Direction::Incoming)
+                .any(|x| delayed.contains(&x)),
+            _ => false,
+        };
+
+        if is_unrelinearized {
+            let consumers = query
+                .neighbors_directed(id, Direction::Outgoing)
+                .collect::<HashSet<_>>();
+
+            let can_delay = consumers.len() == 1
+                && consumers
+                    .iter()
+                    .all(|c| accepts_unrelinearized(&query.get_node(*c).unwrap().operation));
+
+            if can_delay {
+                delayed.insert(id);
+            } else {
+                relin_points.insert(id);
+            }
+        }
+
+        Ok::<_, Infallible>(())
+    })
+    .unwrap();
+
+    // The products each delayed relinearization stands in for.
+    let groups = relin_points
+        .iter()
+        .map(|id| (*id, delayed_products(ir, *id, &delayed)))
+        .collect::<Vec<_>>();
+
+    let mut relinearize = HashSet::new();
+
+    for (id, products) in &groups {
+        if products.len() > 1 {
+            relinearize.insert(*id);
+        } else {
+            relinearize.extend(products);
+        }
+    }
+
+    if let Some(model) = noise_model {
+        let delayed_noise = predict_noise(model, &with_relinearizations(ir, &relinearize));
+
+        let mut eager_groups = vec![];
+
+        for (id, products) in groups.iter().filter(|(_, p)| p.len() > 1) {
+            let mut eager = relinearize.clone();
+            eager.remove(id);
+            eager.extend(products);
+
+            let eager_noise = predict_noise(model, &with_relinearizations(ir, &eager));
+
+            if delayed_noise
+                .iter()
+                .zip(eager_noise.iter())
+                .any(|(d, e)| d > e)
+            {
+                eager_groups.push((id, products));
+            }
+        }
+
+        for (id, products) in eager_groups {
+            relinearize.remove(id);
+            relinearize.extend(products);
+        }
+    }
+
+    insert_relinearizations(ir, &relinearize);
+}
 
-        for e in query.edges_directed(id, Direction::Outgoing) {
-            let operand_type = e.weight();
+/**
+ * Returns the ciphertext multiplications whose unrelinearized results flow
+ * into `id` through delayed nodes.
+ */
+fn delayed_products(
+    ir: &FheProgram,
+    id: NodeIndex,
+    delayed: &HashSet<NodeIndex>,
+) -> Vec<NodeIndex> {
+    let mut products = vec![];
+    let mut pending = vec![id];
 
-            transforms.push(Transform::RemoveEdge(id.into(), e.target().into()));
-            transforms.push(Transform::AddEdge(
-                relin_node.into(),
-                e.target().into(),
-                *operand_type,
-            ));
+    while let Some(node) = pending.pop() {
+        if ir.graph[node].operation == Multiply {
+            products.push(node);
+        } else {
+            pending.extend(
+                ir.graph
+                    .neighbors_directed(node, Direction::Incoming)
+                    .filter(|x| delayed.contains(x)),
+            );
         }
+    }
+
+    products
+}
+
+fn with_relinearizations(ir: &FheProgram, relinearize: &HashSet<NodeIndex>) -> FheProgram {
+    let mut ir = ir.clone();
+
+    insert_relinearizations(&mut ir, relinearize);
 
-        transforms
-    };
+    ir
+}
 
+fn insert_relinearizations(ir: &mut FheProgram, relinearize: &HashSet<NodeIndex>) {
     forward_traverse_mut(&mut ir.graph.0, |query, id| {
-        // Id is given to us, so the node should exist. Just
-        // unwrap.
-        let transforms = match query.get_node(id).unwrap().operation {
-            // We only need to insert relinearizations for ciphertext
-            // multiplications. Plaintext multiplications don't increase
-            // the number of polynomials (see
-            // multiply_plaintext_does_not_increase_polynomials) test in
-            // assumptions.rs
-            Multiply => insert_relin(id, query),
-            _ => GraphTransforms::default(),
+        let transforms = if relinearize.contains(&id) {
+            insert_relin(id, query)
+        } else {
+            GraphTransforms::default()
         };
 
         Ok::<_, Infallible>(transforms)
@@ -63,7 +227,10 @@ pub fn apply_insert_relinearizations(ir: &mut FheProgram) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::noise_model::CanonicalEmbeddingNormModel;
+    use seal_fhe::{CoefficientModulus, SecurityLevel};
     use sunscreen_fhe_program::{FheProgramTrait, Literal as FheProgramLiteral, SchemeType};
+    use sunscreen_runtime::Params;
 
     fn create_test_dag() -> FheProgram {
         let mut ir = FheProgram::new(SchemeType::Bfv);
@@ -85,7 +252,7 @@ mod tests {
 
         assert_eq!(ir.graph.node_count(), 7);
 
-        apply_insert_relinearizations(&mut ir);
+        apply_insert_relinearizations(&mut ir, None);
 
         assert_eq!(ir.graph.node_count(), 9);
 
@@ -110,15 +277,22 @@ mod tests {
             .iter()
             .all(|id| { query.neighbors_directed(*id, Direction::Incoming).count() == 1 }),);
 
-        // Every relin's predacessor should be a multiply
-        assert!(relin_nodes.iter().all(|id| {
-            query
-                .neighbors_directed(*id, Direction::Incoming)
-                .map(|id| query.get_node(id).unwrap())
-                .all(|node| matches!(node.operation, Operation::Multiply))
-        }));
+        // Delaying the first relin past the addition would save nothing, so
+        // it relinearizes the first multiply. The second relinearizes the
+        // final multiply.
+        let predecessor = |id: NodeIndex| {
+            let p = query
+                .neighbors_directed(id, Direction::Incoming)
+                .next()
+                .unwrap();
+
+            query.get_node(p).unwrap().operation.clone()
+        };
 
-        // The first relin node should point to add_2
+        assert_eq!(predecessor(relin_nodes[0]), Operation::Multiply);
+        assert_eq!(predecessor(relin_nodes[1]), Operation::Multiply);
+
+        // The first relin node should point to 1 node
         assert_eq!(
             query
                 .neighbors_directed(relin_nodes[0], Direction::Outgoing)
@@ -134,9 +308,226 @@ mod tests {
             0
         );
 
-        // The first relin node should point to add_2
+        // The first relin node should point to the addition
         assert!(query
             .neighbors_directed(relin_nodes[0], Direction::Outgoing)
             .all(|i| { matches!(query.get_node(i).unwrap().operation, Operation::Add) }),);
     }
+
+    fn relin_nodes(ir: &FheProgram) -> Vec<NodeIndex> {
+        ir.graph
+            .node_indices()
+            .filter(|i| matches!(ir.graph[*i].operation, Operation::Relinearize))
+            .collect()
+    }
+
+    fn sum_of_products(terms: usize, relinearize: bool) -> FheProgram {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let mut sum = None;
+
+        for i in 0..terms {
+            let a = ir.add_input_ciphertext(2 * i);
+            let b = ir.add_input_ciphertext(2 * i + 1);
+            let mut product = ir.add_multiply(a, b);
+
+            if relinearize {
+                product = ir.add_relinearize(product);
+            }
+
+            sum = Some(match sum {
+                Some(s) => ir.add_add(s, product),
+                None => product,
+            });
+        }
+
+        ir.add_output_ciphertext(sum.unwrap());
+
+        ir
+    }
+
+    #[test]
+    fn delays_relinearization_across_sum_of_products() {
+        let mut ir = sum_of_products(4, false);
+
+        apply_insert_relinearizations(&mut ir, None);
+
+        ir.validate().unwrap();
+
+        let relins = relin_nodes(&ir);
+
+        assert_eq!(relins.len(), 1);
+
+        let query = GraphQuery::new(&ir.graph.0);
+
+        let input = query.get_unary_operand(relins[0]).unwrap();
+        assert_eq!(ir.graph[input].operation, Operation::Add);
+
+        assert!(query
+            .neighbors_directed(relins[0], Direction::Outgoing)
+            .all(|i| matches!(ir.graph[i].operation, Operation::OutputCiphertext)));
+    }
+
+    #[test]
+    fn relinearizes_products_with_multiple_consumers() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let a = ir.add_input_ciphertext(0);
+        let b = ir.add_input_ciphertext(1);
+        let product = ir.add_multiply(a, b);
+        let x = ir.add_add(product, a);
+        let y = ir.add_sub(product, b);
+        let rotated = ir.add_unary_operation(Operation::SwapRows, x);
+        ir.add_output_ciphertext(rotated);
+        ir.add_output_ciphertext(y);
+
+        apply_insert_relinearizations(&mut ir, None);
+
+        let relins = relin_nodes(&ir);
+
+        // Delaying would need a relinearization for each consumer, so
+        // relinearize the product itself.
+        assert_eq!(relins.len(), 1);
+
+        let query = GraphQuery::new(&ir.graph.0);
+
+        assert_eq!(query.get_unary_operand(relins[0]).unwrap(), product);
+    }
+
+    #[test]
+    fn relinearizes_lone_products_eagerly() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let a = ir.add_input_ciphertext(0);
+        let b = ir.add_input_ciphertext(1);
+        let c = ir.add_input_ciphertext(2);
+        let product = ir.add_multiply(a, b);
+        let sum = ir.add_add(product, c);
+        ir.add_output_ciphertext(sum);
+
+        apply_insert_relinearizations(&mut ir, None);
+
+        let relins = relin_nodes(&ir);
+
+        // Delaying past the addition wouldn't save a relinearization, so
+        // relinearize the product itself.
+        assert_eq!(relins.len(), 1);
+
+        let query = GraphQuery::new(&ir.graph.0);
+
+        assert_eq!(query.get_unary_operand(relins[0]).unwrap(), product);
+    }
+
+    /**
+     * A noise model under which relinearizing a larger noise costs more,
+     * so delaying relinearization across a sum increases noise.
+     */
+    struct SquaringRelinModel;
+
+    impl NoiseModel for SquaringRelinModel {
+        fn encrypt(&self) -> f64 {
+            2.
+        }
+
+        fn add_ct_ct(&self, a_invariant_noise: f64, b_invariant_noise: f64) -> f64 {
+            a_invariant_noise + b_invariant_noise
+        }
+
+        fn add_ct_pt(&self, ct_invariant_noise: f64) -> f64 {
+            ct_invariant_noise
+        }
+
+        fn mul_ct_ct(&self, a_invariant_noise: f64, b_invariant_noise: f64) -> f64 {
+            a_invariant_noise * b_invariant_noise
+        }
+
+        fn mul_ct_pt(&self, a_invariant_noise: f64) -> f64 {
+            a_invariant_noise
+        }
+
+        fn relinearize(&self, a_invariant_noise: f64) -> f64 {
+            a_invariant_noise * a_invariant_noise
+        }
+
+        fn output(&self, _output_id: usize, invariant_noise: f64) -> f64 {
+            invariant_noise
+        }
+
+        fn neg(&self, invariant_noise: f64) -> f64 {
+            invariant_noise
+        }
+
+        fn sub_ct_ct(&self, a_invariant_noise: f64, b_invariant_noise: f64) -> f64 {
+            a_invariant_noise + b_invariant_noise
+        }
+
+        fn sub_ct_pt(&self, a_invariant_noise: f64) -> f64 {
+            a_invariant_noise
+        }
+
+        fn swap_rows(&self, a_invariant_noise: f64) -> f64 {
+            a_invariant_noise
+        }
+
+        fn shift_left(&self, a_invariant_noise: f64, _places: i32) -> f64 {
+            a_invariant_noise
+        }
+
+        fn shift_right(&self, a_invariant_noise: f64, _places: i32) -> f64 {
+            a_invariant_noise
+        }
+    }
+
+    #[test]
+    fn keeps_eager_relinearization_when_delay_increases_noise() {
+        let mut ir = sum_of_products(4, false);
+
+        apply_insert_relinearizations(&mut ir, Some(&SquaringRelinModel));
+
+        ir.validate().unwrap();
+
+        let relins = relin_nodes(&ir);
+
+        assert_eq!(relins.len(), 4);
+
+        let query = GraphQuery::new(&ir.graph.0);
+
+        assert!(relins.iter().all(|x| {
+            let input = query.get_unary_operand(*x).unwrap();
+
+            ir.graph[input].operation == Operation::Multiply
+        }));
+    }
+
+    #[test]
+    fn delayed_relinearization_does_not_increase_noise() {
+        let lattice_dimension = 8192;
+
+        let params = Params {
+            lattice_dimension,
+            plain_modulus: 1024,
+            coeff_modulus: CoefficientModulus::bfv_default(lattice_dimension, SecurityLevel::TC128)
+                .unwrap()
+                .iter()
+                .map(|x| x.value())
+                .collect(),
+            scheme_type: SchemeType::Bfv,
+            security_level: SecurityLevel::TC128,
+        };
+
+        let model = CanonicalEmbeddingNormModel::new(&params).unwrap();
+
+        let mut delayed = sum_of_products(8, false);
+        apply_insert_relinearizations(&mut delayed, Some(&model));
+
+        let eager = sum_of_products(8, true);
+
+        let delayed_noise = predict_noise(&model, &delayed);
+        let eager_noise = predict_noise(&model, &eager);
+
+        assert!(delayed_noise
+            .iter()
+            .zip(eager_noise.iter())
+            .all(|(d, e)| d <= e));
+    }
 }
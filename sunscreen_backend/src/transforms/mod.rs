@@ -1,12 +1,33 @@
+mod constant_folding;
 mod insert_relinearizations;
+mod rebalance;
 
 use petgraph::stable_graph::NodeIndex;
 use sunscreen_fhe_program::{FheProgram, FheProgramTrait};
+use sunscreen_runtime::Params;
+
+use crate::noise_model::{CanonicalEmbeddingNormModel, NoiseModel};
+
+use constant_folding::apply_constant_folding;
 
 use insert_relinearizations::apply_insert_relinearizations;
+use rebalance::apply_rebalance;
+
+pub fn transform_intermediate_representation(ir: &mut FheProgram, params: Option<&Params>) {
+    apply_constant_folding(ir);
+
+    // Remove the nodes constant folding replaced.
+    *ir = ir.prune(&ir.get_outputs().collect::<Vec<NodeIndex>>());
+
+    apply_rebalance(ir);
+
+    // Invalid parameters fail later on; just don't consult the noise model.
+    let noise_model = params.and_then(|p| CanonicalEmbeddingNormModel::new(p).ok());
 
-pub fn transform_intermediate_representation(ir: &mut FheProgram) {
-    apply_insert_relinearizations(ir);
+    apply_insert_relinearizations(
+        ir,
+        noise_model.as_ref().map(|x| x as &(dyn NoiseModel + Sync)),
+    );
 
     // Dead code elimination.
     *ir = ir.prune(&ir.get_outputs().collect::<Vec<NodeIndex>>());
@@ -0,0 +1,418 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::convert::Infallible;
+
+use sunscreen_compiler_common::{
+    forward_traverse_mut,
+    transforms::{GraphTransforms, Transform, TransformNodeIndex},
+    EdgeInfo, GraphQuery, NodeInfo, Operation as _,
+};
+use sunscreen_fhe_program::{
+    FheProgram,
+    Operation::{self, *},
+};
+
+use petgraph::{stable_graph::NodeIndex, visit::EdgeRef, Direction};
+
+type FheGraphQuery<'a> = GraphQuery<'a, NodeInfo<Operation>, EdgeInfo>;
+
+/**
+ * Whether chains of this operation can be reassociated.
+ *
+ * # Remarks
+ * [`Operation::is_commutative`] also holds for the plaintext variants, but
+ * those take one ciphertext and one plaintext operand, so chains of them
+ * can't be regrouped.
+ */
+fn is_rebalanceable(operation: &Operation) -> bool {
+    operation.is_commutative() && matches!(operation, Add | Multiply)
+}
+
+/**
+ * Whether the given operand of a chain node is an interior node of the same
+ * chain, i.e. performs the same operation and feeds only that node.
+ */
+fn is_interior(query: &FheGraphQuery, operand: NodeIndex, operation: &Operation) -> bool {
+    query.get_node(operand).unwrap().operation == *operation
+        && query.edges_directed(operand, Direction::Outgoing).count() == 1
+}
+
+/**
+ * The chain rooted at a node: the interior nodes it absorbs, the operands
+ * it reduces (with multiplicity) and its current height.
+ */
+struct Chain {
+    interior: Vec<NodeIndex>,
+    leaves: Vec<NodeIndex>,
+    height: usize,
+}
+
+fn collect_chain(
+    query: &FheGraphQuery,
+    id: NodeIndex,
+    operation: &Operation,
+    leaf_height: &dyn Fn(NodeIndex) -> usize,
+    chain: &mut Chain,
+) -> usize {
+    let (left, right) = query.get_binary_operands(id).unwrap();
+
+    let height = [left, right]
+        .iter()
+        .map(|x| {
+            if is_interior(query, *x, operation) {
+                chain.interior.push(*x);
+                collect_chain(query, *x, operation, leaf_height, chain)
+            } else {
+                chain.leaves.push(*x);
+                leaf_height(*x)
+            }
+        })
+        .max()
+        .unwrap();
+
+    height + 1
+}
+
+/**
+ * Rebalances chains of ciphertext additions and multiplications into
+ * balanced trees. Programs that fold a sum or product in a loop produce
+ * linear chains like `((a * b) * c) * d`, whose multiplicative depth grows
+ * linearly in the number of terms. Rebalanced, `(a * b) * (c * d)` has
+ * logarithmic depth, which allows smaller scheme parameters.
+ *
+ * # Remarks
+ * A chain consists of nodes performing the same operation where every node
+ * but the last feeds only the next one. Intermediate results used elsewhere
+ * are left in place and treated as operands of the chain, so this pass
+ * never duplicates work.
+ *
+ * Operands are combined in order of their multiplicative depth, shallowest
+ * first, so chains whose operands already have differing depths are still
+ * rebuilt with minimal depth. A chain is only rebuilt if doing so reduces
+ * its height.
+ *
+ * This pass must run before relinearizations are inserted, as those
+ * break up chains of multiplications.
+ */
+pub fn apply_rebalance(ir: &mut FheProgram) {
+    // The multiplicative depth of each visited node.
+    let mut depths: HashMap<NodeIndex, usize> = HashMap::new();
+
+    forward_traverse_mut(&mut ir.graph.0, |query, id| {
+        let operation = query.get_node(id).unwrap().operation.clone();
+        let source = query.get_node(id).unwrap().source;
+
+        let operand_depth = query
+            .neighbors_directed(id, Direction::Incoming)
+            .map(|x| depths[&x])
+            .max()
+            .unwrap_or(0);
+        let is_multiply = matches!(operation, Multiply);
+
+        depths.insert(id, operand_depth + usize::from(is_multiply));
+
+        let mut transforms = GraphTransforms::new();
+
+        // Only rebuild chains from their last node. Interior nodes are
+        // rebuilt along with it.
+        let is_root = is_rebalanceable(&operation)
+            && !query
+                .neighbors_directed(id, Direction::Outgoing)
+                .any(|x| is_interior(&query, id, &query.get_node(x).unwrap().operation));
+
+        if !is_root {
+            return Ok::<_, Infallible>(transforms);
+        }
+
+        // Balance multiplications by the operands' multiplicative depth and
+        // additions by the number of additions.
+        let leaf_height = |x: NodeIndex| if is_multiply { depths[&x] } else { 0 };
+
+        let mut chain = Chain {
+            interior: vec![],
+            leaves: vec![],
+            height: 0,
+        };
+        chain.height = collect_chain(&query, id, &operation, &leaf_height, &mut chain);
+
+        if chain.interior.is_empty() {
+            return Ok(transforms);
+        }
+
+        // Repeatedly combine the two shallowest operands. Ties are broken by
+        // order to keep the output deterministic.
+        let mut nodes = chain
+            .leaves
+            .iter()
+            .map(|x| TransformNodeIndex::from(*x))
+            .collect::<Vec<_>>();
+        let mut operands = chain
+            .leaves
+            .iter()
+            .enumerate()
+            .map(|(i, x)| Reverse((leaf_height(*x), i)))
+            .collect::<BinaryHeap<_>>();
+        let mut new_nodes = vec![];
+
+        while operands.len() > 2 {
+            let Reverse((left_height, left)) = operands.pop().unwrap();
+            let Reverse((right_height, right)) = operands.pop().unwrap();
+
+            // The chain's nodes are reordered, so attribute the new ones to
+            // the expression that completes the chain.
+            let node = transforms.push(Transform::AddNode(NodeInfo::with_source(
+                operation.clone(),
+                source,
+            )));
+
+            new_nodes.push((nodes[left], nodes[right], node.into()));
+            operands.push(Reverse((left_height.max(right_height) + 1, nodes.len())));
+            nodes.push(node.into());
+        }
+
+        let Reverse((left_height, left)) = operands.pop().unwrap();
+        let Reverse((right_height, right)) = operands.pop().unwrap();
+        let (left, right) = (nodes[left], nodes[right]);
+
+        let height = left_height.max(right_height) + 1;
+
+        if height >= chain.height {
+            return Ok(GraphTransforms::new());
+        }
+
+        for e in query.edges_directed(id, Direction::Incoming) {
+            transforms.push(Transform::RemoveEdge(e.source().into(), id.into()));
+        }
+
+        for x in chain.interior {
+            transforms.push(Transform::RemoveNode(x.into()));
+        }
+
+        for (left, right, node) in
+            new_nodes
+                .into_iter()
+                .chain(std::iter::once((left, right, id.into())))
+        {
+            transforms.push(Transform::AddEdge(left, node, EdgeInfo::Left));
+            transforms.push(Transform::AddEdge(right, node, EdgeInfo::Right));
+        }
+
+        if is_multiply {
+            depths.insert(id, height);
+        }
+
+        Ok(transforms)
+    })
+    .unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_model::{predict_noise, CanonicalEmbeddingNormModel};
+    use seal_fhe::{CoefficientModulus, SecurityLevel};
+    use sunscreen_compiler_common::forward_traverse;
+    use sunscreen_fhe_program::{FheProgramTrait, SchemeType};
+    use sunscreen_runtime::Params;
+
+    fn product_chain(terms: usize) -> FheProgram {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let mut product = ir.add_input_ciphertext(0);
+
+        for i in 1..terms {
+            let x = ir.add_input_ciphertext(i);
+            product = ir.add_multiply(product, x);
+        }
+
+        ir.add_output_ciphertext(product);
+
+        ir
+    }
+
+    fn multiplicative_depth(ir: &FheProgram) -> usize {
+        let mut depths = HashMap::new();
+
+        forward_traverse(&ir.graph.0, |query, id| {
+            let depth = query
+                .neighbors_directed(id, Direction::Incoming)
+                .map(|x| depths[&x])
+                .max()
+                .unwrap_or(0);
+            let is_multiply = matches!(query.get_node(id).unwrap().operation, Multiply);
+
+            depths.insert(id, depth + usize::from(is_multiply));
+
+            Ok::<_, Infallible>(())
+        })
+        .unwrap();
+
+        depths.values().copied().max().unwrap_or(0)
+    }
+
+    fn count(ir: &FheProgram, operation: Operation) -> usize {
+        ir.graph
+            .node_indices()
+            .filter(|i| ir.graph[*i].operation == operation)
+            .count()
+    }
+
+    #[test]
+    fn rebalances_product_chain() {
+        for (terms, depth) in [(2, 1), (4, 2), (5, 3), (8, 3), (9, 4)] {
+            let mut ir = product_chain(terms);
+
+            assert_eq!(multiplicative_depth(&ir), terms - 1);
+
+            apply_rebalance(&mut ir);
+
+            ir.validate().unwrap();
+
+            assert_eq!(multiplicative_depth(&ir), depth);
+            assert_eq!(count(&ir, Multiply), terms - 1);
+        }
+    }
+
+    #[test]
+    fn rebalances_sum_chain() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let mut sum = ir.add_input_ciphertext(0);
+
+        for i in 1..8 {
+            let x = ir.add_input_ciphertext(i);
+            sum = ir.add_add(sum, x);
+        }
+
+        ir.add_output_ciphertext(sum);
+
+        apply_rebalance(&mut ir);
+
+        ir.validate().unwrap();
+
+        assert_eq!(count(&ir, Add), 7);
+
+        let query = GraphQuery::new(&ir.graph.0);
+        let (left, right) = query.get_binary_operands(sum).unwrap();
+
+        // Each half of the sum should have 4 inputs.
+        for x in [left, right] {
+            let (a, b) = query.get_binary_operands(x).unwrap();
+
+            assert_eq!(ir.graph[x].operation, Add);
+            assert_eq!(ir.graph[a].operation, Add);
+            assert_eq!(ir.graph[b].operation, Add);
+        }
+    }
+
+    #[test]
+    fn rebalanced_sum_chain_survives_pruning() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let mut sum = ir.add_input_ciphertext(0);
+
+        for i in 1..4 {
+            let x = ir.add_input_ciphertext(i);
+            sum = ir.add_add(sum, x);
+        }
+
+        ir.add_output_ciphertext(sum);
+
+        // Rebalancing removes the chain's interior nodes, leaving holes in
+        // the graph's indices for pruning to handle.
+        crate::transforms::transform_intermediate_representation(&mut ir, None);
+
+        ir.validate().unwrap();
+
+        assert_eq!(ir.get_outputs().count(), 1);
+        assert_eq!(count(&ir, Add), 3);
+        assert_eq!(ir.graph.node_count(), 8);
+    }
+
+    #[test]
+    fn keeps_shared_intermediate_results() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let a = ir.add_input_ciphertext(0);
+        let b = ir.add_input_ciphertext(1);
+        let c = ir.add_input_ciphertext(2);
+        let d = ir.add_input_ciphertext(3);
+
+        let ab = ir.add_multiply(a, b);
+        let abc = ir.add_multiply(ab, c);
+        let abcd = ir.add_multiply(abc, d);
+
+        // abc is used twice, so only the chain a * b * c can be rebuilt, and
+        // it is already as shallow as it can be.
+        ir.add_output_ciphertext(abc);
+        ir.add_output_ciphertext(abcd);
+
+        apply_rebalance(&mut ir);
+
+        ir.validate().unwrap();
+
+        assert_eq!(count(&ir, Multiply), 3);
+        assert_eq!(multiplicative_depth(&ir), 3);
+
+        let query = GraphQuery::new(&ir.graph.0);
+        assert_eq!(query.get_binary_operands(abcd).unwrap(), (abc, d));
+        assert_eq!(query.get_binary_operands(abc).unwrap(), (ab, c));
+    }
+
+    #[test]
+    fn combines_shallowest_operands_first() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        // A depth 2 operand followed by 3 fresh ones. Combining the fresh
+        // ones first gives depth 3 rather than 4.
+        let a = ir.add_input_ciphertext(0);
+        let a2 = ir.add_multiply(a, a);
+        let a4 = ir.add_multiply(a2, a2);
+
+        let mut product = a4;
+
+        for i in 1..4 {
+            let x = ir.add_input_ciphertext(i);
+            product = ir.add_multiply(product, x);
+        }
+
+        ir.add_output_ciphertext(product);
+
+        assert_eq!(multiplicative_depth(&ir), 5);
+
+        apply_rebalance(&mut ir);
+
+        ir.validate().unwrap();
+
+        assert_eq!(multiplicative_depth(&ir), 3);
+    }
+
+    #[test]
+    fn rebalancing_reduces_noise() {
+        let lattice_dimension = 8192;
+
+        let params = Params {
+            lattice_dimension,
+            plain_modulus: 1024,
+            coeff_modulus: CoefficientModulus::bfv_default(lattice_dimension, SecurityLevel::TC128)
+                .unwrap()
+                .iter()
+                .map(|x| x.value())
+                .collect(),
+            scheme_type: SchemeType::Bfv,
+            security_level: SecurityLevel::TC128,
+        };
+
+        let model = CanonicalEmbeddingNormModel::new(&params).unwrap();
+
+        let mut balanced = product_chain(8);
+        apply_rebalance(&mut balanced);
+
+        let chain = product_chain(8);
+
+        let balanced_noise = predict_noise(&model, &balanced);
+        let chain_noise = predict_noise(&model, &chain);
+
+        assert!(balanced_noise[0] < chain_noise[0]);
+    }
+}
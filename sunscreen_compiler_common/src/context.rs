@@ -7,9 +7,9 @@ use petgraph::visit::{EdgeRef, IntoEdgeReferences, IntoNodeReferences};
 use petgraph::Graph;
 use serde::{Deserialize, Serialize};
 
-use crate::{Operation, Render};
+use crate::{current_source_location, Operation, Render, SourceLocation};
 
-#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[derive(Clone, Deserialize, Serialize)]
 /**
  * Information about a node in the compilation graph.
  */
@@ -21,6 +21,16 @@ where
      * The operation this node performs.
      */
     pub operation: O,
+
+    /**
+     * Where in the program's source this node was created, if known.
+     *
+     * # Remarks
+     * This is a diagnostic aid; it isn't serialized and doesn't
+     * participate in comparisons.
+     */
+    #[serde(skip)]
+    pub source: Option<SourceLocation>,
 }
 
 impl<O> NodeInfo<O>
@@ -31,7 +41,39 @@ where
      * Creates a new [`NodeInfo`].
      */
     pub fn new(operation: O) -> Self {
-        Self { operation }
+        Self {
+            operation,
+            source: None,
+        }
+    }
+
+    /**
+     * Creates a new [`NodeInfo`] attributed to the given source location.
+     */
+    pub fn with_source(operation: O, source: Option<SourceLocation>) -> Self {
+        Self { operation, source }
+    }
+}
+
+impl<O> PartialEq for NodeInfo<O>
+where
+    O: Operation,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.operation == other.operation
+    }
+}
+
+impl<O> Eq for NodeInfo<O> where O: Operation {}
+
+impl<O> Debug for NodeInfo<O>
+where
+    O: Operation,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NodeInfo")
+            .field("operation", &self.operation)
+            .finish()
     }
 }
 
@@ -236,10 +278,13 @@ where
     }
 
     /**
-     * Add a node to the parse graph.
+     * Add a node to the parse graph, attributed to the source location
+     * [`with_source_location`](crate::with_source_location) is recording,
+     * if any.
      */
     pub fn add_node(&mut self, operation: O) -> NodeIndex {
-        self.graph.add_node(NodeInfo { operation })
+        self.graph
+            .add_node(NodeInfo::with_source(operation, current_source_location()))
     }
 
     /**
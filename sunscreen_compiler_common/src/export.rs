@@ -0,0 +1,337 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt::Write;
+
+use petgraph::{
+    stable_graph::NodeIndex,
+    visit::{EdgeRef, IntoEdgeReferences},
+    Direction,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{forward_traverse, CompilationResult, Context, Operation, Render};
+
+/**
+ * Describes how an [`Operation`] appears when exporting its graph with
+ * [`CompilationResult::export`].
+ */
+pub trait ExportOperation: Operation {
+    /**
+     * The label for nodes performing this operation. Defaults to the
+     * operation's [`Debug`](std::fmt::Debug) representation.
+     */
+    fn label(&self) -> String {
+        format!("{self:?}")
+    }
+
+    /**
+     * The type of value this operation produces, if meaningful.
+     */
+    fn data_type(&self) -> Option<String> {
+        None
+    }
+
+    /**
+     * Whether this operation increases the multiplicative depth of its
+     * result.
+     */
+    fn is_multiplication(&self) -> bool;
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/**
+ * A node in an [`ExportedGraph`].
+ */
+pub struct ExportedNode {
+    /**
+     * The index of the node in the exported graph.
+     */
+    pub id: usize,
+
+    /**
+     * The operation the node performs.
+     */
+    pub operation: String,
+
+    /**
+     * The type of value the node produces, if known.
+     */
+    pub data_type: Option<String>,
+
+    /**
+     * The number of multiplications on the longest path from an input to
+     * this node.
+     */
+    pub multiplicative_depth: usize,
+
+    /**
+     * The noise budget (in bits) predicted for this node's output, if
+     * known.
+     */
+    pub predicted_noise_budget: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/**
+ * An edge in an [`ExportedGraph`].
+ */
+pub struct ExportedEdge {
+    /**
+     * The index of the node producing the operand.
+     */
+    pub source: usize,
+
+    /**
+     * The index of the node consuming the operand.
+     */
+    pub target: usize,
+
+    /**
+     * Which operand of the target the source is.
+     */
+    pub operand: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+/**
+ * An annotated, format-agnostic copy of a compiler graph for viewing in
+ * external tooling. Create one with [`CompilationResult::export`] and
+ * render it with [`to_dot`](Self::to_dot) or [`to_json`](Self::to_json).
+ */
+pub struct ExportedGraph {
+    /**
+     * The graph's nodes, ordered by index.
+     */
+    pub nodes: Vec<ExportedNode>,
+
+    /**
+     * The graph's edges.
+     */
+    pub edges: Vec<ExportedEdge>,
+}
+
+impl ExportedGraph {
+    /**
+     * Gets the node with the given index, if present.
+     */
+    pub fn node_mut(&mut self, index: NodeIndex) -> Option<&mut ExportedNode> {
+        self.nodes.iter_mut().find(|n| n.id == index.index())
+    }
+
+    /**
+     * Renders this graph in the Graphviz DOT language.
+     */
+    pub fn to_dot(&self) -> String {
+        let mut dot = "digraph {\n".to_owned();
+
+        for n in &self.nodes {
+            let mut label = format!("{}: {}", n.id, n.operation);
+
+            if let Some(t) = &n.data_type {
+                write!(label, "\ntype: {t}").unwrap();
+            }
+
+            write!(label, "\ndepth: {}", n.multiplicative_depth).unwrap();
+
+            if let Some(b) = n.predicted_noise_budget {
+                write!(label, "\nnoise budget: {b:.2}").unwrap();
+            }
+
+            writeln!(dot, "    {} [ label = \"{}\" ]", n.id, escape(&label)).unwrap();
+        }
+
+        for e in &self.edges {
+            writeln!(
+                dot,
+                "    {} -> {} [ label = \"{}\" ]",
+                e.source,
+                e.target,
+                escape(&e.operand)
+            )
+            .unwrap();
+        }
+
+        dot.push_str("}\n");
+
+        dot
+    }
+
+    /**
+     * Serializes this graph as pretty-printed JSON.
+     */
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+impl<O> CompilationResult<O>
+where
+    O: ExportOperation,
+{
+    /**
+     * Exports this graph, annotating each node with its operation, type
+     * and multiplicative depth.
+     */
+    pub fn export(&self) -> ExportedGraph {
+        let mut depths = HashMap::new();
+
+        forward_traverse(&self.0, |query, id| {
+            let depth = query
+                .neighbors_directed(id, Direction::Incoming)
+                .map(|x| depths[&x])
+                .max()
+                .unwrap_or(0);
+
+            let is_mul = query.get_node(id).unwrap().operation.is_multiplication();
+
+            depths.insert(id, depth + usize::from(is_mul));
+
+            Ok::<_, Infallible>(())
+        })
+        .unwrap();
+
+        let nodes = self
+            .node_indices()
+            .map(|id| {
+                let operation = &self[id].operation;
+
+                ExportedNode {
+                    id: id.index(),
+                    operation: operation.label(),
+                    data_type: operation.data_type(),
+                    multiplicative_depth: depths[&id],
+                    predicted_noise_budget: None,
+                }
+            })
+            .collect();
+
+        let edges = self
+            .edge_references()
+            .map(|e| ExportedEdge {
+                source: e.source().index(),
+                target: e.target().index(),
+                operand: e.weight().render(),
+            })
+            .collect();
+
+        ExportedGraph { nodes, edges }
+    }
+}
+
+impl<O, D> Context<O, D>
+where
+    O: ExportOperation,
+{
+    /**
+     * Exports this context's graph. See [`CompilationResult::export`].
+     */
+    pub fn export(&self) -> ExportedGraph {
+        self.graph.export()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, Hash, PartialEq, Eq)]
+    enum Op {
+        In,
+        Add,
+        Mul,
+    }
+
+    impl Operation for Op {
+        fn is_binary(&self) -> bool {
+            matches!(self, Self::Add | Self::Mul)
+        }
+
+        fn is_commutative(&self) -> bool {
+            matches!(self, Self::Add | Self::Mul)
+        }
+
+        fn is_unary(&self) -> bool {
+            false
+        }
+
+        fn is_unordered(&self) -> bool {
+            false
+        }
+
+        fn is_ordered(&self) -> bool {
+            false
+        }
+    }
+
+    impl ExportOperation for Op {
+        fn data_type(&self) -> Option<String> {
+            Some("Field".to_owned())
+        }
+
+        fn is_multiplication(&self) -> bool {
+            matches!(self, Self::Mul)
+        }
+    }
+
+    fn graph() -> Context<Op, ()> {
+        let mut ctx = Context::new(());
+
+        let a = ctx.add_node(Op::In);
+        let b = ctx.add_node(Op::In);
+        let c = ctx.add_binary_operation(Op::Mul, a, b);
+        let d = ctx.add_binary_operation(Op::Mul, c, a);
+        ctx.add_binary_operation(Op::Add, d, b);
+
+        ctx
+    }
+
+    #[test]
+    fn can_export_multiplicative_depth() {
+        let exported = graph().export();
+
+        let depths = exported
+            .nodes
+            .iter()
+            .map(|n| n.multiplicative_depth)
+            .collect::<Vec<_>>();
+
+        assert_eq!(depths, vec![0, 0, 1, 2, 2]);
+        assert_eq!(exported.edges.len(), 6);
+    }
+
+    #[test]
+    fn can_export_dot() {
+        let mut exported = graph().export();
+        exported
+            .node_mut(NodeIndex::from(4))
+            .unwrap()
+            .predicted_noise_budget = Some(12.5);
+
+        let dot = exported.to_dot();
+
+        assert!(dot.starts_with("digraph {\n"));
+        assert!(dot.contains("    2 [ label = \"2: Mul\\ntype: Field\\ndepth: 1\" ]\n"));
+        assert!(dot.contains(
+            "    4 [ label = \"4: Add\\ntype: Field\\ndepth: 2\\nnoise budget: 12.50\" ]\n"
+        ));
+        assert!(dot.contains("    0 -> 2 [ label = \"Left\" ]\n"));
+    }
+
+    #[test]
+    fn can_roundtrip_json() {
+        let exported = graph().export();
+
+        let json = exported.to_json().unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<ExportedGraph>(&json).unwrap(),
+            exported
+        );
+    }
+}
@@ -751,9 +751,7 @@ mod tests {
             if n.index() == 2 {
                 let mut transforms: GraphTransforms<NodeInfo<Operation>, EdgeInfo> =
                     GraphTransforms::new();
-                let mul = transforms.push(Transform::AddNode(NodeInfo {
-                    operation: Operation::Mul,
-                }));
+                let mul = transforms.push(Transform::AddNode(NodeInfo::new(Operation::Mul)));
                 transforms.push(Transform::AddEdge(n.into(), mul.into(), EdgeInfo::Left));
                 transforms.push(Transform::AddEdge(
                     NodeIndex::from(1).into(),
@@ -5,11 +5,13 @@
 //! compilers.
 
 mod context;
+mod export;
 mod graph;
 /**
  * Helper methods for macros.
  */
 pub mod macros;
+mod source;
 
 /**
  * A set of generic compiler transforms.
@@ -17,7 +19,9 @@ pub mod macros;
 pub mod transforms;
 
 pub use context::*;
+pub use export::*;
 pub use graph::*;
+pub use source::*;
 
 use semver::Version;
 use serde::{
@@ -0,0 +1,146 @@
+use std::cell::Cell;
+use std::fmt::Display;
+use std::panic::Location;
+
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+/**
+ * A location in a program's source code, used to attribute compiler
+ * graph nodes and diagnostics to the expression that created them.
+ */
+pub struct SourceLocation {
+    /**
+     * The file containing the expression.
+     */
+    pub file: &'static str,
+
+    /**
+     * The 1-based line of the expression.
+     */
+    pub line: u32,
+
+    /**
+     * The 1-based column of the expression.
+     */
+    pub column: u32,
+}
+
+impl SourceLocation {
+    /**
+     * Returns the location of the caller of the function invoking this
+     * method, following `#[track_caller]` attributes.
+     */
+    #[track_caller]
+    pub fn caller() -> Self {
+        Location::caller().into()
+    }
+}
+
+impl From<&'static Location<'static>> for SourceLocation {
+    fn from(location: &'static Location<'static>) -> Self {
+        Self {
+            file: location.file(),
+            line: location.line(),
+            column: location.column(),
+        }
+    }
+}
+
+impl Display for SourceLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+thread_local! {
+    static CURRENT_SOURCE_LOCATION: Cell<Option<SourceLocation>> = Cell::new(None);
+}
+
+/**
+ * Runs `f`, attributing every node [`Context::add_node`](crate::Context::add_node)
+ * creates during the call to the caller of this function.
+ *
+ * # Remarks
+ * Operator implementations on program nodes (e.g. `Add` for
+ * `FheProgramNode`) should be `#[track_caller]` and wrap their body in
+ * this function so nodes point at the user's expression rather than the
+ * implementation.
+ *
+ * Calls nest: when a location is already being recorded, the outermost
+ * one wins. Thus, an operation implemented in terms of other operations
+ * attributes all of its nodes to where the user invoked it.
+ */
+#[track_caller]
+pub fn with_source_location<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let location = SourceLocation::caller();
+
+    CURRENT_SOURCE_LOCATION.with(|current| {
+        if current.get().is_some() {
+            return f();
+        }
+
+        current.set(Some(location));
+
+        // Reset the location even if `f` panics, as compilers may catch the
+        // panic and move on to the next program.
+        struct Reset<'a>(&'a Cell<Option<SourceLocation>>);
+
+        impl Drop for Reset<'_> {
+            fn drop(&mut self) {
+                self.0.set(None);
+            }
+        }
+
+        let _reset = Reset(current);
+
+        f()
+    })
+}
+
+/**
+ * Returns the location [`with_source_location`] is currently recording on
+ * this thread, if any.
+ */
+pub fn current_source_location() -> Option<SourceLocation> {
+    CURRENT_SOURCE_LOCATION.with(|x| x.get())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_outermost_location() {
+        assert_eq!(current_source_location(), None);
+
+        let line = line!() + 1;
+        let (outer, inner) = with_source_location(|| {
+            let outer = current_source_location();
+            let inner = with_source_location(current_source_location);
+
+            (outer, inner)
+        });
+
+        let outer = outer.unwrap();
+
+        assert_eq!(outer.file, file!());
+        assert_eq!(outer.line, line);
+        assert_eq!(Some(outer), inner);
+        assert_eq!(current_source_location(), None);
+    }
+
+    #[test]
+    fn formats_as_file_line_column() {
+        let location = SourceLocation {
+            file: "src/main.rs",
+            line: 12,
+            column: 5,
+        };
+
+        assert_eq!(location.to_string(), "src/main.rs:12:5");
+    }
+}
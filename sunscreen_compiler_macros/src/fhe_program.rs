@@ -5,7 +5,9 @@ use crate::{
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, quote_spanned};
 use sunscreen_compiler_common::macros::{extract_fn_arguments, ExtractFnArgumentsError, FnArgInfo};
-use syn::{parse_macro_input, spanned::Spanned, Error, Ident, ItemFn, Result, Type};
+use syn::{
+    parse_macro_input, spanned::Spanned, Error, GenericParam, Generics, Ident, ItemFn, Result, Type,
+};
 
 pub fn fhe_program_impl(
     metadata: proc_macro::TokenStream,
@@ -39,10 +41,10 @@ impl<'a> FheProgram<'a> {
         let inputs = &item_fn.sig.inputs;
         let ret = &item_fn.sig.output;
 
-        if !generics.params.is_empty() {
+        if let Some(lifetime) = generics.lifetimes().next() {
             return Err(Error::new(
-                generics.params.span(),
-                "FHE programs do not support generics.",
+                lifetime.span(),
+                "FHE programs do not support lifetimes.",
             ));
         }
 
@@ -154,6 +156,53 @@ impl<'a> FheProgram<'a> {
             .collect()
     }
 
+    // The generics of the FHE program struct. These are the function's
+    // generics without bounds, which instead go on the impls.
+    fn struct_generics(&self) -> Generics {
+        let mut generics = self.item_fn.sig.generics.clone();
+
+        for param in generics.params.iter_mut() {
+            if let GenericParam::Type(t) = param {
+                t.colon_token = None;
+                t.bounds.clear();
+            }
+        }
+
+        generics.where_clause = None;
+
+        generics
+    }
+
+    // The name of an instantiation of the FHE program, e.g. `dot<4>`. Each
+    // instantiation of a generic FHE program gets a distinct name so several
+    // can be compiled into the same application. Type arguments are named by
+    // their `TypeName` rather than `std::any::type_name`, which isn't stable
+    // across compiler versions, as saved artifacts look programs up by name.
+    fn instance_name(&self) -> TokenStream {
+        let fhe_program_name_literal = self.item_fn.sig.ident.to_string();
+        let generics = &self.item_fn.sig.generics;
+
+        if generics.params.is_empty() {
+            return quote! { #fhe_program_name_literal };
+        }
+
+        let args = generics.params.iter().filter_map(|p| match p {
+            GenericParam::Type(t) => {
+                let ident = &t.ident;
+                Some(quote! { <#ident as sunscreen::types::TypeName>::type_name().name })
+            }
+            GenericParam::Const(c) => {
+                let ident = &c.ident;
+                Some(quote! { #ident.to_string() })
+            }
+            GenericParam::Lifetime(_) => None,
+        });
+
+        quote! {
+            format!("{}<{}>", #fhe_program_name_literal, [#(#args),*].join(", "))
+        }
+    }
+
     fn output(self) -> TokenStream {
         let input_fn = self.item_fn;
         let attr_params = &self.attr_params;
@@ -194,16 +243,59 @@ impl<'a> FheProgram<'a> {
         let fhe_program_struct_name =
             Ident::new(&format!("{}_struct", fhe_program_name), Span::call_site());
 
-        let fhe_program_name_literal = format!("{}", fhe_program_name);
+        let generics = &input_fn.sig.generics;
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let turbofish = ty_generics.as_turbofish();
+        let struct_generics = self.struct_generics();
+        let instance_name = self.instance_name();
+
+        // Generic FHE programs can't be a const, so instead emit a function
+        // returning the requested instantiation.
+        let (struct_fields, name_impl, constructor) = if generics.params.is_empty() {
+            (
+                quote! {
+                    chain_count: usize
+                },
+                instance_name,
+                quote! {
+                    #[allow(non_upper_case_globals)]
+                    #vis const #fhe_program_name: #fhe_program_struct_name = #fhe_program_struct_name {
+                        chain_count: #chain_count
+                    };
+                },
+            )
+        } else {
+            let type_params = generics.type_params().map(|t| &t.ident);
+
+            (
+                quote! {
+                    chain_count: usize,
+                    name: String,
+                    _phantom: std::marker::PhantomData<fn() -> (#(#type_params,)*)>
+                },
+                quote! {
+                    &self.name
+                },
+                quote! {
+                    #vis fn #fhe_program_name #impl_generics () -> #fhe_program_struct_name #ty_generics #where_clause {
+                        #fhe_program_struct_name {
+                            chain_count: #chain_count,
+                            name: #instance_name,
+                            _phantom: std::marker::PhantomData,
+                        }
+                    }
+                },
+            )
+        };
 
         quote! {
             #[allow(non_camel_case_types)]
             #[derive(Clone)]
-            #vis struct #fhe_program_struct_name {
-                chain_count: usize
+            #vis struct #fhe_program_struct_name #struct_generics {
+                #struct_fields
             }
 
-            impl sunscreen::FheProgramFn for #fhe_program_struct_name {
+            impl #impl_generics sunscreen::FheProgramFn for #fhe_program_struct_name #ty_generics #where_clause {
                 #[allow(unused_imports)]
                 fn build(&self, params: &sunscreen::Params) -> sunscreen::Result<sunscreen::fhe::FheFrontendCompilation> {
                     use std::cell::RefCell;
@@ -222,9 +314,9 @@ impl<'a> FheProgram<'a> {
                         #[allow(clippy::type_complexity)]
                         #[forbid(unused_variables)]
                         let internal = | #(#fhe_program_args)* | -> #fhe_program_return {
-                            fn internal_inner(#(#fhe_program_args)*) -> #inner_return #body
+                            fn internal_inner #impl_generics (#(#fhe_program_args)*) -> #inner_return #where_clause #body
 
-                            let #inner_return_values = internal_inner( #(#inner_arg_values),* );
+                            let #inner_return_values = internal_inner #turbofish ( #(#inner_arg_values),* );
                             #inner_return_into_values
                         };
 
@@ -235,9 +327,9 @@ impl<'a> FheProgram<'a> {
 
                         #(#fhe_arg_var_decl)*
 
-                        let panic_res = std::panic::catch_unwind(|| {
+                        let panic_res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
                             internal(#(#fhe_arg_vars),*)
-                        });
+                        }));
 
                         // when panicing or not, we need to collect our indicies arena and
                         // unset the context reference.
@@ -273,7 +365,7 @@ impl<'a> FheProgram<'a> {
                 }
 
                 fn name(&self) -> &str {
-                    #fhe_program_name_literal
+                    #name_impl
                 }
 
                 fn chain_count(&self) -> usize {
@@ -281,7 +373,7 @@ impl<'a> FheProgram<'a> {
                 }
             }
 
-            impl AsRef<str> for #fhe_program_struct_name {
+            impl #impl_generics AsRef<str> for #fhe_program_struct_name #ty_generics #where_clause {
                 fn as_ref(&self) -> &str {
                     use sunscreen::FheProgramFn;
 
@@ -289,10 +381,7 @@ impl<'a> FheProgram<'a> {
                 }
             }
 
-            #[allow(non_upper_case_globals)]
-            #vis const #fhe_program_name: #fhe_program_struct_name = #fhe_program_struct_name {
-                chain_count: #chain_count
-            };
+            #constructor
         }
     }
 }
@@ -334,13 +423,28 @@ mod test {
     }
 
     #[test]
-    fn disallows_generics() {
+    fn allows_generics() {
+        let attrs = FheProgramAttrs {
+            scheme: Scheme::Bfv,
+            chain_count: 1,
+        };
+        let attempt_fn = parse_quote! {
+            fn dot<T, const N: usize>(a: [Cipher<T>; N], b: [Cipher<T>; N]) -> Cipher<T> {
+                a[0] * b[0]
+            }
+        };
+
+        assert!(FheProgram::new(&attempt_fn, attrs).is_ok())
+    }
+
+    #[test]
+    fn disallows_lifetimes() {
         let attrs = FheProgramAttrs {
             scheme: Scheme::Bfv,
             chain_count: 1,
         };
         let attempt_fn = parse_quote! {
-            fn simple_multiply<T>(a: Cipher<T>, b: Cipher<Signed>) -> Cipher<Signed> {
+            fn simple_multiply<'a>(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
                 b
             }
         };
@@ -48,7 +48,7 @@ pub fn create_fhe_program_node(var_name: &str, arg_type: &Type) -> TokenStream2
     let var_name = format_ident!("{}", var_name);
 
     quote_spanned! {arg_type.span() =>
-        { struct _AssertInput where #mapped_type: Input; }
+        { fn _assert_input<T: Input>() {} _assert_input::<#mapped_type>(); }
         let #var_name: #mapped_type = <#mapped_type as Input>::input();
     }
 }
@@ -130,7 +130,8 @@ pub fn suffix_coerce(is: &[Ident]) -> Vec<TokenStream2> {
 pub fn emit_output_capture(var: &Ident, return_types: &[Type]) -> TokenStream2 {
     match return_types {
         [ty] => quote_spanned! { ty.span() => {
-            struct _AssertOutput where FheProgramNode<#ty>: Output;
+            fn _assert_output<T: NumCiphertexts>() where FheProgramNode<T>: Output {}
+            _assert_output::<#ty>();
             #var.output();
         }},
         _ => return_types
@@ -140,7 +141,8 @@ pub fn emit_output_capture(var: &Ident, return_types: &[Type]) -> TokenStream2 {
                 let index = Index::from(i);
 
                 quote_spanned! {ty.span() => {
-                    struct _AssertOutput where FheProgramNode<#ty>: Output;
+                    fn _assert_output<T: NumCiphertexts>() where FheProgramNode<T>: Output {}
+                    _assert_output::<#ty>();
                     #var.#index.output();
                 }}
             })
@@ -148,57 +150,35 @@ pub fn emit_output_capture(var: &Ident, return_types: &[Type]) -> TokenStream2 {
     }
 }
 
+/**
+ * Emits the `CallSignature` for the given argument and return types.
+ *
+ * # Remarks
+ * This refers to the types directly rather than through local type aliases,
+ * as the latter can't name the generic parameters of a generic FHE program.
+ */
 pub fn emit_signature(args: &[Type], return_types: &[Type]) -> TokenStream2 {
-    let arg_type_names = args
-        .iter()
-        .enumerate()
-        .map(|(i, t)| {
-            let alias = format_ident!("T{}", i);
-
-            quote! {
-                type #alias = #t;
-            }
-        })
-        .collect::<Vec<TokenStream2>>();
-
-    let arg_get_types = arg_type_names.iter().enumerate().map(|(i, _)| {
-        let alias = format_ident!("T{}", i);
-
+    let arg_get_types = args.iter().map(|t| {
         quote! {
-            #alias::type_name(),
+            <#t>::type_name(),
         }
     });
 
-    let return_type_aliases = return_types.iter().enumerate().map(|(i, t)| {
-        let alias = format_ident!("R{}", i);
-
+    let return_type_names = return_types.iter().map(|t| {
         quote! {
-            type #alias = #t;
+            <#t>::type_name(),
         }
     });
 
-    let return_type_names = return_types.iter().enumerate().map(|(i, _)| {
-        let alias = format_ident!("R{}", i);
-
+    let return_type_sizes = return_types.iter().map(|t| {
         quote! {
-            #alias ::type_name(),
-        }
-    });
-
-    let return_type_sizes = return_types.iter().enumerate().map(|(i, _)| {
-        let alias = format_ident!("R{}", i);
-
-        quote! {
-            #alias ::NUM_CIPHERTEXTS,
+            <#t>::NUM_CIPHERTEXTS,
         }
     });
 
     quote! {
         use sunscreen::types::TypeName;
 
-        #(#arg_type_names)*
-        #(#return_type_aliases)*
-
         sunscreen::CallSignature {
             arguments: vec![#(#arg_get_types)*],
             returns: vec![#(#return_type_names)*],
@@ -313,7 +293,7 @@ mod test {
         let actual = create_fhe_program_node("horse", &type_name);
 
         let expected = quote! {
-            { struct _AssertInput where FheProgramNode<Cipher<Rational> >: Input; }
+            { fn _assert_input<T: Input>() {} _assert_input::<FheProgramNode<Cipher<Rational> > >(); }
             let horse: FheProgramNode<Cipher<Rational> > = <FheProgramNode<Cipher<Rational> > as Input>::input();
         };
 
@@ -331,7 +311,7 @@ mod test {
         let actual = create_fhe_program_node("horse", &type_name);
 
         let expected = quote! {
-            { struct _AssertInput where [FheProgramNode<Cipher<Rational> >; 7]: Input; }
+            { fn _assert_input<T: Input>() {} _assert_input::<[FheProgramNode<Cipher<Rational> >; 7]>(); }
             let horse: [FheProgramNode<Cipher<Rational> >; 7] = <[FheProgramNode<Cipher<Rational> >; 7] as Input>::input();
         };
 
@@ -349,7 +329,7 @@ mod test {
         let actual = create_fhe_program_node("horse", &type_name);
 
         let expected = quote! {
-            { struct _AssertInput where [[FheProgramNode<Cipher<Rational> >; 7]; 6]: Input; }
+            { fn _assert_input<T: Input>() {} _assert_input::<[[FheProgramNode<Cipher<Rational> >; 7]; 6]>(); }
             let horse: [[FheProgramNode<Cipher<Rational> >; 7]; 6] = <[[FheProgramNode<Cipher<Rational> >; 7]; 6] as Input>::input();
         };
 
@@ -442,7 +422,8 @@ mod test {
 
         let expected = quote! {
             {
-                struct _AssertOutput where FheProgramNode< Cipher < Signed > > : Output;
+                fn _assert_output<T: NumCiphertexts>() where FheProgramNode<T>: Output {}
+                _assert_output::< Cipher < Signed > >();
                 __v.output();
             }
         };
@@ -465,11 +446,13 @@ mod test {
 
         let expected = quote! {
             {
-                struct _AssertOutput where FheProgramNode< Cipher < Signed > > : Output;
+                fn _assert_output<T: NumCiphertexts>() where FheProgramNode<T>: Output {}
+                _assert_output::< Cipher < Signed > >();
                 __v.0.output();
             }
             {
-                struct _AssertOutput where FheProgramNode<[[Cipher<Signed>; 6]; 7]>: Output;
+                fn _assert_output<T: NumCiphertexts>() where FheProgramNode<T>: Output {}
+                _assert_output::<[[Cipher<Signed>; 6]; 7]>();
                 __v.1.output();
             }
         };
@@ -0,0 +1,227 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, spanned::Spanned, Data, DeriveInput, Error, Fields, Ident,
+    Result, Type,
+};
+
+use crate::type_name::derive_typename_inner;
+
+pub fn derive_fhe_type(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match derive_fhe_type_inner(input) {
+        Ok(t) => t.into(),
+        Err(e) => e.into_compile_error().into(),
+    }
+}
+
+fn derive_fhe_type_inner(input: DeriveInput) -> Result<TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new(
+            input.generics.span(),
+            "#[derive(FheType)] does not support generics.",
+        ));
+    }
+
+    let fields = match &input.data {
+        Data::Struct(s) => match &s.fields {
+            Fields::Named(f) => &f.named,
+            _ => {
+                return Err(Error::new(
+                    s.fields.span(),
+                    "#[derive(FheType)] requires a struct with named fields.",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "#[derive(FheType)] requires a struct with named fields.",
+            ))
+        }
+    };
+
+    if fields.is_empty() {
+        return Err(Error::new(
+            input.ident.span(),
+            "#[derive(FheType)] requires at least one field.",
+        ));
+    }
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let fields_name = Ident::new(&format!("{name}Fields"), Span::call_site());
+
+    let field_names = fields
+        .iter()
+        .map(|f| f.ident.as_ref().unwrap())
+        .collect::<Vec<_>>();
+    let field_types = fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+
+    // The offset of each field's ciphertexts, i.e. the sum of the sizes of
+    // the preceding fields.
+    let offsets = (0..fields.len())
+        .map(|i| {
+            let preceding = &field_types[..i];
+
+            quote! {
+                0 #(+ <#preceding as sunscreen::types::NumCiphertexts>::NUM_CIPHERTEXTS)*
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let node_types = field_types
+        .iter()
+        .map(|t| node_type(t))
+        .collect::<Result<Vec<_>>>()?;
+
+    let type_name = derive_typename_inner(input.clone());
+
+    let struct_doc = format!("The fields of an encrypted [`{name}`] in an FHE program.");
+    let join_doc = format!("Combines these fields into an encrypted [`{name}`].");
+    let field_docs = field_names
+        .iter()
+        .map(|f| format!("The `{f}` field."))
+        .collect::<Vec<_>>();
+
+    Ok(quote! {
+        #type_name
+
+        impl sunscreen::types::NumCiphertexts for #name {
+            const NUM_CIPHERTEXTS: usize =
+                0 #(+ <#field_types as sunscreen::types::NumCiphertexts>::NUM_CIPHERTEXTS)*;
+        }
+
+        impl sunscreen::types::TryIntoPlaintext for #name {
+            fn try_into_plaintext(
+                &self,
+                params: &sunscreen::Params,
+            ) -> std::result::Result<sunscreen::Plaintext, sunscreen::RuntimeError> {
+                use sunscreen::types::TypeName;
+
+                let mut inner = vec![];
+
+                #(
+                    match sunscreen::types::TryIntoPlaintext::try_into_plaintext(&self.#field_names, params)?.inner {
+                        sunscreen::InnerPlaintext::Seal(p) => inner.extend(p),
+                    };
+                )*
+
+                Ok(sunscreen::Plaintext {
+                    data_type: Self::type_name(),
+                    inner: sunscreen::InnerPlaintext::Seal(inner),
+                })
+            }
+        }
+
+        impl sunscreen::types::TryFromPlaintext for #name {
+            fn try_from_plaintext(
+                plaintext: &sunscreen::Plaintext,
+                params: &sunscreen::Params,
+            ) -> std::result::Result<Self, sunscreen::RuntimeError> {
+                use sunscreen::types::{NumCiphertexts, TypeName};
+
+                let p = match &plaintext.inner {
+                    sunscreen::InnerPlaintext::Seal(p) => p,
+                };
+
+                if p.len() != Self::NUM_CIPHERTEXTS {
+                    return Err(sunscreen::RuntimeError::MalformedPlaintext);
+                }
+
+                Ok(Self {
+                    #(
+                        #field_names: {
+                            let offset = #offsets;
+                            let field = sunscreen::Plaintext {
+                                data_type: <#field_types>::type_name(),
+                                inner: sunscreen::InnerPlaintext::Seal(
+                                    p[offset..offset + <#field_types>::NUM_CIPHERTEXTS].to_owned()
+                                ),
+                            };
+
+                            sunscreen::types::TryFromPlaintext::try_from_plaintext(&field, params)?
+                        },
+                    )*
+                })
+            }
+        }
+
+        impl sunscreen::FheProgramInputTrait for #name {}
+
+        impl sunscreen::types::FheType for #name {}
+
+        impl sunscreen::types::BfvType for #name {}
+
+        #[doc = #struct_doc]
+        #[derive(Clone, Copy)]
+        #vis struct #fields_name {
+            #(
+                #[doc = #field_docs]
+                pub #field_names: #node_types,
+            )*
+        }
+
+        impl #fields_name {
+            #[doc = #join_doc]
+            pub fn join(self) -> sunscreen::types::intern::FheProgramNode<sunscreen::types::Cipher<#name>> {
+                <#name as sunscreen::types::intern::FheStruct>::join(self)
+            }
+        }
+
+        impl sunscreen::types::intern::FheStruct for #name {
+            type Fields = #fields_name;
+
+            fn split(
+                node: sunscreen::types::intern::FheProgramNode<sunscreen::types::Cipher<Self>>,
+            ) -> Self::Fields {
+                use sunscreen::types::{intern::FheStructField, NumCiphertexts};
+
+                #fields_name {
+                    #(
+                        #field_names: FheStructField::from_ids(
+                            &node.ids[#offsets..#offsets + <#field_types>::NUM_CIPHERTEXTS]
+                        ),
+                    )*
+                }
+            }
+
+            fn join(
+                fields: Self::Fields,
+            ) -> sunscreen::types::intern::FheProgramNode<sunscreen::types::Cipher<Self>> {
+                use sunscreen::types::intern::FheStructField;
+
+                let mut ids = vec![];
+
+                #(
+                    fields.#field_names.append_ids(&mut ids);
+                )*
+
+                sunscreen::types::intern::FheProgramNode::new(&ids)
+            }
+        }
+    })
+}
+
+/**
+ * The type of a field in an FHE program. I.e. `FheProgramNode<Cipher<T>>`
+ * for an FHE type `T`, or an array of such.
+ */
+fn node_type(ty: &Type) -> Result<Type> {
+    match ty {
+        Type::Path(_) => Ok(parse_quote! {
+            sunscreen::types::intern::FheProgramNode<sunscreen::types::Cipher<#ty>>
+        }),
+        Type::Array(a) => {
+            let elem = node_type(&a.elem)?;
+            let len = &a.len;
+
+            Ok(parse_quote! { [#elem; #len] })
+        }
+        _ => Err(Error::new(
+            ty.span(),
+            "#[derive(FheType)] fields must be FHE types or arrays of them.",
+        )),
+    }
+}
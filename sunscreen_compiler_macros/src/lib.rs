@@ -9,6 +9,7 @@ extern crate proc_macro;
 mod error;
 mod fhe_program;
 mod fhe_program_transforms;
+mod fhe_type;
 mod internals;
 mod type_name;
 mod zkp_program;
@@ -21,6 +22,41 @@ pub fn derive_typename(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
     type_name::derive_typename(input)
 }
 
+#[proc_macro_derive(FheType)]
+/**
+ * Allows you to `#[derive(FheType)]` on a struct whose fields are all FHE
+ * types (e.g. `Signed`, `Fractional` or `Batched`), so `Cipher<MyStruct>`
+ * can be an [`fhe_program`](macro@fhe_program) argument or return value.
+ * The struct encrypts to one ciphertext per field. This also derives
+ * `TypeName`.
+ *
+ * Inside an [`fhe_program`](macro@fhe_program), call `fields()` on a
+ * `Cipher<MyStruct>` to get a generated `MyStructFields` struct of encrypted
+ * fields, and `join()` on a `MyStructFields` to build a `Cipher<MyStruct>`.
+ *
+ * # Examples
+ * ```rust,ignore
+ * # use sunscreen::{fhe_program, types::{bfv::Signed, Cipher}, FheType};
+ *
+ * #[derive(Clone, Copy, FheType)]
+ * struct Account {
+ *   balance: Signed,
+ *   limit: Signed,
+ * }
+ *
+ * #[fhe_program(scheme = "bfv")]
+ * fn deposit(account: Cipher<Account>, amount: Cipher<Signed>) -> Cipher<Account> {
+ *   let mut fields = account.fields();
+ *   fields.balance = fields.balance + amount;
+ *
+ *   fields.join()
+ * }
+ * ```
+ */
+pub fn derive_fhe_type(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    fhe_type::derive_fhe_type(input)
+}
+
 #[proc_macro_attribute]
 /**
  * Specifies a function to be an [`fhe_program`](macro@fhe_program). An [`fhe_program`](macro@fhe_program) has any number of inputs that impl the
@@ -59,6 +95,36 @@ pub fn derive_typename(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
  *   (a + b, b + c)
  * }
  * ```
+ *
+ * # Generics
+ * [`fhe_program`](macro@fhe_program)s may take const and type parameters, but not lifetimes.
+ * Rather than a value, a generic [`fhe_program`](macro@fhe_program) emits a function returning
+ * the requested instantiation. Each instantiation has a distinct name (e.g. `dot<4>`), so
+ * several can be compiled into the same application.
+ *
+ * Type parameters need a `TypeName` bound, which also names the instantiation, and bounds
+ * for each operation the program performs; the traits in `sunscreen::types::ops` provide
+ * these.
+ *
+ * ```rust,ignore
+ * # use sunscreen::{fhe_program, types::{bfv::Signed, Cipher}, Compiler};
+ *
+ * #[fhe_program(scheme = "bfv")]
+ * fn dot<const N: usize>(a: [Cipher<Signed>; N], b: [Cipher<Signed>; N]) -> Cipher<Signed> {
+ *   let mut sum = a[0] * b[0];
+ *
+ *   for i in 1..N {
+ *     sum = sum + a[i] * b[i];
+ *   }
+ *
+ *   sum
+ * }
+ *
+ * let app = Compiler::new()
+ *   .fhe_program(dot::<4>())
+ *   .fhe_program(dot::<8>())
+ *   .compile()?;
+ * ```
  */
 pub fn fhe_program(
     metadata: proc_macro::TokenStream,
@@ -70,6 +136,10 @@ pub fn fhe_program(
 #[proc_macro_attribute]
 /**
  * Specifies a function to be a ZKP program. TODO: docs.
+ *
+ * ZKP programs take a type parameter for the backend field and may additionally take const
+ * parameters. Like generic [`fhe_program`](macro@fhe_program)s, const generic ZKP programs emit
+ * a function returning the requested instantiation, e.g. `range::<8>()`.
  */
 pub fn zkp_program(
     metadata: proc_macro::TokenStream,
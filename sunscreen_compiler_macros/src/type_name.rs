@@ -11,7 +11,7 @@ pub fn derive_typename(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
     derive_typename_inner(input).into()
 }
 
-fn derive_typename_inner(parse_stream: DeriveInput) -> TokenStream {
+pub fn derive_typename_inner(parse_stream: DeriveInput) -> TokenStream {
     let name = &parse_stream.ident;
     let generics = &parse_stream.generics;
     let generic_idents = generics
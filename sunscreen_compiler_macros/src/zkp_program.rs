@@ -38,7 +38,7 @@ fn get_generic_arg(generics: &Generics) -> Result<(Ident, Path)> {
     if generics.type_params().count() != 1 {
         return Err(Error::compile_error(
             generics.span(),
-            "ZKP programs must take 1 generic argument with bound sunscreen::BackendField",
+            "ZKP programs must take 1 generic type argument with bound sunscreen::BackendField",
         ));
     }
 
@@ -49,13 +49,6 @@ fn get_generic_arg(generics: &Generics) -> Result<(Ident, Path)> {
         ));
     }
 
-    if generics.const_params().count() > 0 {
-        return Err(Error::compile_error(
-            generics.span(),
-            "ZKP programs don't support const params.",
-        ));
-    }
-
     let generic = generics.type_params().next().unwrap();
     if generic.bounds.len() != 1 {
         return Err(Error::compile_error(generic.span(), "ZKP programs must take 1 generic argument with bound sunscreen::BackendField. This must be the only bound and cannot be specified in a `where` clause."));
@@ -206,6 +199,54 @@ fn parse_inner(_attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenS
 
     let zkp_program_name_literal = format!("{}", zkp_program_name);
 
+    // The const params of the program, which parameterize the program's
+    // struct. The field type param is instead a parameter of the impl.
+    let const_params = input_fn.sig.generics.const_params().collect::<Vec<_>>();
+    let const_idents = const_params.iter().map(|c| &c.ident).collect::<Vec<_>>();
+    let struct_generics = if const_params.is_empty() {
+        quote! {}
+    } else {
+        quote! { <#(#const_params),*> }
+    };
+    let struct_args = if const_params.is_empty() {
+        quote! {}
+    } else {
+        quote! { <#(#const_idents),*> }
+    };
+
+    // Const generic ZKP programs can't be a const, so instead emit a function
+    // returning the requested instantiation, named e.g. `range<8>`.
+    let (struct_fields, name_impl, constructor) = if const_params.is_empty() {
+        (
+            quote! { ; },
+            quote! { #zkp_program_name_literal },
+            quote! {
+                #[allow(non_upper_case_globals)]
+                #vis const #zkp_program_name: #zkp_program_struct_name = #zkp_program_struct_name;
+            },
+        )
+    } else {
+        (
+            quote! {
+                {
+                    name: String
+                }
+            },
+            quote! { &self.name },
+            quote! {
+                #vis fn #zkp_program_name #struct_generics () -> #zkp_program_struct_name #struct_args {
+                    #zkp_program_struct_name {
+                        name: format!(
+                            "{}<{}>",
+                            #zkp_program_name_literal,
+                            [#(#const_idents.to_string()),*].join(", ")
+                        ),
+                    }
+                }
+            },
+        )
+    };
+
     let (associated_link_type, into_linked_variant, ext_impl) = if is_linked {
         (quote! { sunscreen::zkp::Linked }, quote! {Ok}, None)
     } else {
@@ -213,7 +254,7 @@ fn parse_inner(_attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenS
             quote! { sunscreen::zkp::NotLinked },
             quote! {Err},
             Some(quote! {
-                impl sunscreen::ZkpProgramFnExt for #zkp_program_struct_name {}
+                impl #struct_generics sunscreen::ZkpProgramFnExt for #zkp_program_struct_name #struct_args {}
             }),
         )
     };
@@ -221,15 +262,15 @@ fn parse_inner(_attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenS
     Ok(quote! {
         #[allow(non_camel_case_types)]
         #[derive(Clone)]
-        #vis struct #zkp_program_struct_name;
+        #vis struct #zkp_program_struct_name #struct_generics #struct_fields
 
-        impl AsRef<str> for #zkp_program_struct_name {
+        impl #struct_generics AsRef<str> for #zkp_program_struct_name #struct_args {
             fn as_ref(&self) -> &str {
-                #zkp_program_name_literal
+                #name_impl
             }
         }
 
-        impl <#generic_ident: #generic_bound> sunscreen::ZkpProgramFn<#generic_ident> for #zkp_program_struct_name {
+        impl <#generic_ident: #generic_bound, #(#const_params),*> sunscreen::ZkpProgramFn<#generic_ident> for #zkp_program_struct_name #struct_args {
             type Link = #associated_link_type;
 
             fn build(&self, linked_input: <Self::Link as sunscreen::zkp::Link>::Input) -> sunscreen::Result<sunscreen::zkp::ZkpFrontendCompilation> {
@@ -247,10 +288,10 @@ fn parse_inner(_attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenS
 
                     #[allow(clippy::type_complexity)]
                     #[forbid(unused_variables)]
-                    let panic_res = std::panic::catch_unwind(|| {
+                    let panic_res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
                         #(#var_decl)*
                         #body
-                    });
+                    }));
 
                     // when panicing or not, we need to clear our indicies arena and
                     // unset the context reference.
@@ -275,7 +316,7 @@ fn parse_inner(_attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenS
             }
 
             fn name(&self) -> &str {
-                #zkp_program_name_literal
+                #name_impl
             }
 
             fn signature(&self) -> sunscreen::CallSignature {
@@ -290,7 +331,6 @@ fn parse_inner(_attr_params: ZkpProgramAttrs, input_fn: ItemFn) -> Result<TokenS
 
         #ext_impl
 
-        #[allow(non_upper_case_globals)]
-        #vis const #zkp_program_name: #zkp_program_struct_name = #zkp_program_struct_name;
+        #constructor
     })
 }
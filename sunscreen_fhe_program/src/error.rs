@@ -1,5 +1,6 @@
 use petgraph::stable_graph::NodeIndex;
 use static_assertions::const_assert;
+use sunscreen_compiler_common::SourceLocation;
 
 use crate::{EdgeInfo, OutputType};
 
@@ -49,9 +50,11 @@ pub enum IRError {
     IRHasCycles,
 
     /**
-     * A node in the IR has an error.
+     * A node in the IR has an error. Contains the node, its operation,
+     * the error and, if known, where in the program's source the node was
+     * created.
      */
-    NodeError(Box<(NodeIndex, OpName, NodeError)>),
+    NodeError(Box<(NodeIndex, OpName, NodeError, Option<SourceLocation>)>),
 }
 
 impl std::fmt::Display for IRError {
@@ -67,7 +70,13 @@ impl std::fmt::Display for IRError {
                     x.1,
                     x.0.index(),
                     x.2
-                )
+                )?;
+
+                if let Some(source) = x.3 {
+                    write!(f, " (at {source})")?;
+                }
+
+                Ok(())
             }
         }
     }
@@ -78,7 +87,20 @@ impl IRError {
      * Creates an [`IRError::NodeError`].
      */
     pub fn node_error(node_id: NodeIndex, op: OpName, inner: NodeError) -> Self {
-        Self::NodeError(Box::new((node_id, op, inner)))
+        Self::node_error_at(node_id, op, inner, None)
+    }
+
+    /**
+     * Creates an [`IRError::NodeError`] for a node created at the given
+     * source location.
+     */
+    pub fn node_error_at(
+        node_id: NodeIndex,
+        op: OpName,
+        inner: NodeError,
+        source: Option<SourceLocation>,
+    ) -> Self {
+        Self::NodeError(Box::new((node_id, op, inner, source)))
     }
 }
 
@@ -111,6 +133,12 @@ pub enum NodeError {
      * but got some other number (second argument).
      */
     WrongOperandCount(Box<(usize, usize)>),
+
+    /**
+     * The parent node at the given [`EdgeInfo`] is a
+     * [`Literal::U64`](crate::Literal::U64), which this node can't consume.
+     */
+    UnexpectedU64Literal(EdgeInfo),
 }
 
 impl std::fmt::Display for NodeError {
@@ -136,6 +164,9 @@ impl std::fmt::Display for NodeError {
                     x.0, x.1
                 )
             }
+            Self::UnexpectedU64Literal(e) => {
+                write!(f, "The {e:#?} operand can't be a u64 literal")
+            }
         }
     }
 }
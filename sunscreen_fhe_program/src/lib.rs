@@ -26,7 +26,7 @@ pub use seal_fhe::SecurityLevel;
 
 use sunscreen_compiler_common::{CompilationResult, Context, EdgeInfo, NodeInfo};
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Clone, Copy, Serialize, Hash, Deserialize, PartialEq, Eq)]
 /**
@@ -147,6 +147,7 @@ impl OutputTypeTrait for NodeInfo<Operation> {
         match self.operation {
             Operation::InputPlaintext(_) => OutputType::Plaintext,
             Operation::Literal(_) => OutputType::Plaintext,
+            Operation::OutputPlaintext => OutputType::Plaintext,
             _ => OutputType::Ciphertext,
         }
     }
@@ -209,6 +210,12 @@ pub trait FheProgramTrait {
      */
     fn add_output_ciphertext(&mut self, x: NodeIndex) -> NodeIndex;
 
+    /**
+     * Adds a node designating plaintext `x` as an unencrypted output of
+     * the FHE program.
+     */
+    fn add_output_plaintext(&mut self, x: NodeIndex) -> NodeIndex;
+
     /**
      * Appends an operation that relinearizes `x`.
      */
@@ -233,7 +240,8 @@ pub trait FheProgramTrait {
     fn append_rotate_right(&mut self, x: NodeIndex, y: NodeIndex) -> NodeIndex;
 
     /**
-     * Returns the node indices of output ciphertexts
+     * Returns the node indices of the FHE program's outputs, both
+     * ciphertext and plaintext.
      */
     fn get_outputs(&self) -> Box<dyn Iterator<Item = NodeIndex> + '_>;
 
@@ -306,6 +314,10 @@ impl FheProgramTrait for FheProgram {
         self.add_unary_operation(Operation::OutputCiphertext, x)
     }
 
+    fn add_output_plaintext(&mut self, x: NodeIndex) -> NodeIndex {
+        self.add_unary_operation(Operation::OutputPlaintext, x)
+    }
+
     fn add_relinearize(&mut self, x: NodeIndex) -> NodeIndex {
         self.add_unary_operation(Operation::Relinearize, x)
     }
@@ -319,11 +331,12 @@ impl FheProgramTrait for FheProgram {
     }
 
     fn get_outputs(&self) -> Box<dyn Iterator<Item = NodeIndex> + '_> {
-        Box::new(
-            self.graph
-                .node_indices()
-                .filter(|g| matches!(self.graph[*g].operation, Operation::OutputCiphertext)),
-        )
+        Box::new(self.graph.node_indices().filter(|g| {
+            matches!(
+                self.graph[*g].operation,
+                Operation::OutputCiphertext | Operation::OutputPlaintext
+            )
+        }))
     }
 
     fn num_inputs(&self) -> usize {
@@ -334,6 +347,15 @@ impl FheProgramTrait for FheProgram {
     }
 
     fn prune(&self, nodes: &[NodeIndex]) -> FheProgram {
+        // Converting to a Graph removes any holes left by removed nodes, keeping
+        // the remaining nodes in index order. Map the given indices accordingly.
+        let compact_ids = self
+            .graph
+            .node_indices()
+            .enumerate()
+            .map(|(i, id)| (id, NodeIndex::new(i)))
+            .collect::<HashMap<NodeIndex, NodeIndex>>();
+
         let mut compact_graph = Graph::from(self.graph.0.clone());
         compact_graph.reverse();
 
@@ -346,7 +368,7 @@ impl FheProgramTrait for FheProgram {
         let mut visit: Vec<NodeIndex> = vec![];
 
         for n in nodes {
-            let mapped_id = revmap[n.index()];
+            let mapped_id = revmap[compact_ids[n].index()];
             visit.push(mapped_id);
             closure_set.insert(mapped_id);
         }
@@ -474,6 +496,28 @@ mod tests {
         assert!(eq(&pruned, &expected_ir));
     }
 
+    #[test]
+    fn can_prune_graph_with_holes() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let ct = ir.add_input_ciphertext(0);
+        let dead = ir.add_negate(ct);
+        let l1 = ir.add_input_literal(Literal::from(7u64));
+        let add = ir.add_add(ct, l1);
+        let o1 = ir.add_output_ciphertext(add);
+        ir.graph.0.remove_node(dead);
+
+        let pruned = ir.prune(&[o1]);
+
+        let mut expected_ir = FheProgram::new(SchemeType::Bfv);
+        let ct = expected_ir.add_input_ciphertext(0);
+        let l1 = expected_ir.add_input_literal(Literal::from(7u64));
+        let add = expected_ir.add_add(ct, l1);
+        expected_ir.add_output_ciphertext(add);
+
+        assert!(eq(&pruned, &expected_ir));
+    }
+
     #[test]
     fn can_prune_with_multiple_nodes() {
         let mut ir = FheProgram::new(SchemeType::Bfv);
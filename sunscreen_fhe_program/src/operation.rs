@@ -1,7 +1,7 @@
 use std::fmt::Display;
 
 use serde::{Deserialize, Serialize};
-use sunscreen_compiler_common::Operation as OperationTrait;
+use sunscreen_compiler_common::{ExportOperation, Operation as OperationTrait};
 
 use crate::Literal;
 
@@ -91,6 +91,17 @@ pub enum Operation {
      * Represents a ciphertext output for the FHE program.
      */
     OutputCiphertext,
+
+    /**
+     * Represents a plaintext output for the FHE program, i.e. a result
+     * returned unencrypted.
+     *
+     * # Remarks
+     * The operand must be a plaintext input or a plaintext literal. There
+     * are no plaintext-plaintext operations, so FHE programs can only pass
+     * such values through rather than compute new plaintext results.
+     */
+    OutputPlaintext,
 }
 
 impl Display for Operation {
@@ -124,7 +135,11 @@ impl OperationTrait for Operation {
     fn is_unary(&self) -> bool {
         matches!(
             self,
-            Self::Negate | Self::Relinearize | Self::SwapRows | Self::OutputCiphertext
+            Self::Negate
+                | Self::Relinearize
+                | Self::SwapRows
+                | Self::OutputCiphertext
+                | Self::OutputPlaintext
         )
     }
 
@@ -136,3 +151,29 @@ impl OperationTrait for Operation {
         false
     }
 }
+
+impl ExportOperation for Operation {
+    fn label(&self) -> String {
+        match self {
+            // Don't dump serialized plaintexts into the label.
+            Self::Literal(Literal::Plaintext(_)) => "Literal(Plaintext)".to_owned(),
+            _ => format!("{self:?}"),
+        }
+    }
+
+    fn data_type(&self) -> Option<String> {
+        let t = match self {
+            Self::InputPlaintext(_)
+            | Self::Literal(Literal::Plaintext(_))
+            | Self::OutputPlaintext => "Plaintext",
+            Self::Literal(Literal::U64(_)) => "u64",
+            _ => "Ciphertext",
+        };
+
+        Some(t.to_owned())
+    }
+
+    fn is_multiplication(&self) -> bool {
+        matches!(self, Self::Multiply)
+    }
+}
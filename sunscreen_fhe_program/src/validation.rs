@@ -69,7 +69,16 @@ pub(crate) fn validate_nodes(ir: &FheProgram) -> Vec<IRError> {
             Negate => Some(validate_unary_op_has_correct_operands(ir, i)),
             InputCiphertext(_) => None,
             InputPlaintext(_) => None,
-            OutputCiphertext => Some(validate_unary_op_has_correct_operands(ir, i)),
+            OutputCiphertext => Some(validate_output_has_correct_operand(
+                ir,
+                i,
+                OutputType::Ciphertext,
+            )),
+            OutputPlaintext => Some(validate_output_has_correct_operand(
+                ir,
+                i,
+                OutputType::Plaintext,
+            )),
             Relinearize => Some(validate_unary_op_has_correct_operands(ir, i)),
             Literal(_) => None,
             SwapRows => None,
@@ -79,7 +88,14 @@ pub(crate) fn validate_nodes(ir: &FheProgram) -> Vec<IRError> {
             errors.append(
                 &mut node_errors
                     .into_iter()
-                    .map(|e| IRError::node_error(i, node_info.operation.to_string(), e))
+                    .map(|e| {
+                        IRError::node_error_at(
+                            i,
+                            node_info.operation.to_string(),
+                            e,
+                            node_info.source,
+                        )
+                    })
                     .collect(),
             )
         }
@@ -159,6 +175,34 @@ fn validate_unary_op_has_correct_operands(ir: &FheProgram, index: NodeIndex) ->
     errors
 }
 
+fn validate_output_has_correct_operand(
+    ir: &FheProgram,
+    index: NodeIndex,
+    expected_output: OutputType,
+) -> Vec<NodeError> {
+    let errors = validate_unary_op_has_correct_operands(ir, index);
+
+    if !errors.is_empty() {
+        return errors;
+    }
+
+    match get_unary_operand(ir, index) {
+        Some(x) if !ir.graph.contains_node(x) => vec![NodeError::MissingParent(x)],
+        Some(x) if ir.graph[x].output_type() != expected_output => {
+            vec![NodeError::parent_has_incorrect_output_type(
+                EdgeInfo::Unary,
+                ir.graph[x].output_type(),
+                expected_output,
+            )]
+        }
+        // u64 literals are only shift amounts; the runtime can't return them.
+        Some(x) if matches!(ir.graph[x].operation, Literal(crate::Literal::U64(_))) => {
+            vec![NodeError::UnexpectedU64Literal(EdgeInfo::Unary)]
+        }
+        _ => vec![],
+    }
+}
+
 fn get_left_right_operands(
     ir: &FheProgram,
     index: NodeIndex,
@@ -192,6 +236,7 @@ pub fn get_unary_operand(ir: &FheProgram, index: NodeIndex) -> Option<NodeIndex>
 mod tests {
     use super::*;
     use crate::{FheProgramTrait, SchemeType};
+    use sunscreen_compiler_common::{current_source_location, with_source_location};
 
     // FheProgram objects created with the API are guaranteed
     // to never produce errors. We may only deserialize an erroneous IR.
@@ -355,6 +400,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn node_errors_report_source_location() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+        let a = ir.add_input_ciphertext(0);
+
+        let (add, source) = with_source_location(|| (ir.add_node(Add), current_source_location()));
+        ir.graph.add_edge(a, add, EdgeInfo::Left);
+
+        let errors = validate_ir(&ir);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0],
+            IRError::node_error_at(
+                add,
+                "Add".to_owned(),
+                NodeError::wrong_operand_count(2, 1),
+                source
+            )
+        );
+        assert!(errors[0]
+            .to_string()
+            .ends_with(&format!(" (at {})", source.unwrap())));
+    }
+
     #[test]
     fn add_too_many_operands() {
         let ir_str = serde_json::json!({
@@ -411,4 +481,58 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn outputs_must_match_operand_type() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+        let a = ir.add_input_ciphertext(0);
+        let b = ir.add_input_plaintext(1);
+        ir.add_output_ciphertext(a);
+        ir.add_output_plaintext(b);
+
+        assert_eq!(validate_ir(&ir).len(), 0);
+
+        let bad_ciphertext = ir.add_output_ciphertext(b);
+        let bad_plaintext = ir.add_output_plaintext(a);
+
+        assert_eq!(
+            validate_ir(&ir),
+            vec![
+                IRError::node_error(
+                    bad_ciphertext,
+                    "OutputCiphertext".to_owned(),
+                    NodeError::parent_has_incorrect_output_type(
+                        EdgeInfo::Unary,
+                        OutputType::Plaintext,
+                        OutputType::Ciphertext
+                    )
+                ),
+                IRError::node_error(
+                    bad_plaintext,
+                    "OutputPlaintext".to_owned(),
+                    NodeError::parent_has_incorrect_output_type(
+                        EdgeInfo::Unary,
+                        OutputType::Ciphertext,
+                        OutputType::Plaintext
+                    )
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn plaintext_outputs_reject_u64_literals() {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+        let shift = ir.add_input_literal(crate::Literal::U64(3));
+        let output = ir.add_output_plaintext(shift);
+
+        assert_eq!(
+            validate_ir(&ir),
+            vec![IRError::node_error(
+                output,
+                "OutputPlaintext".to_owned(),
+                NodeError::UnexpectedU64Literal(EdgeInfo::Unary)
+            )]
+        );
+    }
 }
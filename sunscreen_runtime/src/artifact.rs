@@ -0,0 +1,320 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+
+use crate::{CompiledFheProgram, Error, Params, Result};
+
+/**
+ * The bytes every serialized [`ApplicationArtifact`] starts with.
+ */
+const ARTIFACT_MAGIC: &[u8; 8] = b"SUNSCRN\0";
+
+/**
+ * The current version of the [`ApplicationArtifact`] format. Artifacts with
+ * a different version are rejected when loading.
+ */
+pub const ARTIFACT_FORMAT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct ArtifactProgram {
+    name: String,
+    graph_hash: [u8; 32],
+    program: CompiledFheProgram,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ArtifactBody {
+    params: Params,
+    programs: Vec<ArtifactProgram>,
+}
+
+#[derive(Clone)]
+/**
+ * A set of compiled FHE programs sharing the same [`Params`], in a form that
+ * can be saved to and loaded from disk. This lets you compile programs once
+ * (e.g. in CI) and run them elsewhere without the compiler.
+ *
+ * # Remarks
+ * The serialized form consists of a magic number, the format version
+ * ([`ARTIFACT_FORMAT_VERSION`]) and a bincode-encoded body containing the
+ * shared parameters and each program along with a SHA3-256 hash of its
+ * graph.
+ *
+ * Loading an artifact fails if its format version differs from this crate's,
+ * if any program's graph doesn't match its hash, or if any program's
+ * parameters differ from the shared ones.
+ */
+pub struct ApplicationArtifact {
+    params: Params,
+    fhe_programs: HashMap<String, CompiledFheProgram>,
+}
+
+impl ApplicationArtifact {
+    /**
+     * Creates an artifact from the given named FHE programs.
+     *
+     * # Remarks
+     * Returns [`Error::ArtifactParamsMismatch`] if the programs don't all
+     * use `params`.
+     */
+    pub fn new(params: &Params, fhe_programs: HashMap<String, CompiledFheProgram>) -> Result<Self> {
+        Self::check_params(params, fhe_programs.iter())?;
+
+        Ok(Self {
+            params: params.clone(),
+            fhe_programs,
+        })
+    }
+
+    /**
+     * The [`Params`] shared by every program in this artifact.
+     */
+    pub fn params(&self) -> &Params {
+        &self.params
+    }
+
+    /**
+     * Gets the [`CompiledFheProgram`] with the given name or [`None`] if not present.
+     */
+    pub fn get_fhe_program<N>(&self, name: N) -> Option<&CompiledFheProgram>
+    where
+        N: AsRef<str>,
+    {
+        self.fhe_programs.get(name.as_ref())
+    }
+
+    /**
+     * Returns an iterator over all the programs in this artifact.
+     */
+    pub fn get_fhe_programs(&self) -> impl Iterator<Item = (&String, &CompiledFheProgram)> {
+        self.fhe_programs.iter()
+    }
+
+    /**
+     * Consumes this artifact and returns its programs.
+     */
+    pub fn into_fhe_programs(self) -> HashMap<String, CompiledFheProgram> {
+        self.fhe_programs
+    }
+
+    /**
+     * Serialize this artifact into bytes.
+     */
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        // Sort the programs so the same artifact always serializes to the
+        // same bytes.
+        let mut names = self.fhe_programs.keys().collect::<Vec<_>>();
+        names.sort();
+
+        let programs = names
+            .into_iter()
+            .map(|name| {
+                let program = self.fhe_programs[name].clone();
+
+                Ok(ArtifactProgram {
+                    name: name.clone(),
+                    graph_hash: Self::graph_hash(&program)?,
+                    program,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let body = ArtifactBody {
+            params: self.params.clone(),
+            programs,
+        };
+
+        let mut bytes = ARTIFACT_MAGIC.to_vec();
+        bytes.extend_from_slice(&ARTIFACT_FORMAT_VERSION.to_le_bytes());
+        bytes.extend(bincode::serialize(&body)?);
+
+        Ok(bytes)
+    }
+
+    /**
+     * Deserialize an artifact from bytes produced by
+     * [`to_bytes`](Self::to_bytes), checking its version, program hashes and
+     * parameters.
+     */
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let rest = bytes
+            .strip_prefix(&ARTIFACT_MAGIC[..])
+            .ok_or(Error::MalformedArtifact)?;
+
+        if rest.len() < 4 {
+            return Err(Error::MalformedArtifact);
+        }
+
+        let (version, rest) = rest.split_at(4);
+        let version = u32::from_le_bytes(version.try_into().unwrap());
+
+        if version != ARTIFACT_FORMAT_VERSION {
+            return Err(Error::ArtifactVersionMismatch(version));
+        }
+
+        let body: ArtifactBody = bincode::deserialize(rest)?;
+
+        let mut fhe_programs = HashMap::with_capacity(body.programs.len());
+
+        for p in body.programs {
+            if Self::graph_hash(&p.program)? != p.graph_hash {
+                return Err(Error::ArtifactHashMismatch(Box::new(p.name)));
+            }
+
+            fhe_programs.insert(p.name, p.program);
+        }
+
+        Self::new(&body.params, fhe_programs)
+    }
+
+    /**
+     * Serialize this artifact and write it to the given file.
+     */
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        std::fs::write(path, self.to_bytes()?).map_err(|e| Error::io_error(&e))
+    }
+
+    /**
+     * Read and deserialize an artifact from the given file. See
+     * [`from_bytes`](Self::from_bytes).
+     */
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let bytes = std::fs::read(path).map_err(|e| Error::io_error(&e))?;
+
+        Self::from_bytes(&bytes)
+    }
+
+    fn graph_hash(program: &CompiledFheProgram) -> Result<[u8; 32]> {
+        let graph = bincode::serialize(&program.fhe_program_fn)?;
+
+        Ok(Sha3_256::digest(graph).into())
+    }
+
+    fn check_params<'a>(
+        params: &Params,
+        mut programs: impl Iterator<Item = (&'a String, &'a CompiledFheProgram)>,
+    ) -> Result<()> {
+        match programs.find(|(_, p)| p.metadata.params != *params) {
+            Some((name, _)) => Err(Error::ArtifactParamsMismatch(Box::new(name.clone()))),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CallSignature, FheProgramMetadata};
+    use seal_fhe::SecurityLevel;
+    use sunscreen_fhe_program::{FheProgram, FheProgramTrait, SchemeType};
+
+    fn params(lattice_dimension: u64) -> Params {
+        Params {
+            lattice_dimension,
+            coeff_modulus: vec![1, 2, 3],
+            plain_modulus: 64,
+            scheme_type: SchemeType::Bfv,
+            security_level: SecurityLevel::TC128,
+        }
+    }
+
+    fn program(params: &Params) -> CompiledFheProgram {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let a = ir.add_input_ciphertext(0);
+        let b = ir.add_input_ciphertext(1);
+        let c = ir.add_add(a, b);
+        ir.add_output_ciphertext(c);
+
+        CompiledFheProgram {
+            fhe_program_fn: ir,
+            metadata: FheProgramMetadata {
+                params: params.clone(),
+                signature: CallSignature {
+                    arguments: vec![],
+                    returns: vec![],
+                    num_ciphertexts: vec![],
+                },
+                required_keys: vec![],
+            },
+        }
+    }
+
+    fn artifact() -> ApplicationArtifact {
+        let params = params(4096);
+
+        let programs = HashMap::from([
+            ("a".to_owned(), program(&params)),
+            ("b".to_owned(), program(&params)),
+        ]);
+
+        ApplicationArtifact::new(&params, programs).unwrap()
+    }
+
+    #[test]
+    fn can_roundtrip_artifact() {
+        let artifact = artifact();
+
+        let bytes = artifact.to_bytes().unwrap();
+        let loaded = ApplicationArtifact::from_bytes(&bytes).unwrap();
+
+        assert_eq!(loaded.params(), artifact.params());
+        assert_eq!(loaded.get_fhe_programs().count(), 2);
+        assert_eq!(loaded.to_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn rejects_mismatched_version() {
+        let mut bytes = artifact().to_bytes().unwrap();
+        bytes[ARTIFACT_MAGIC.len()] ^= 0xFF;
+
+        assert!(matches!(
+            ApplicationArtifact::from_bytes(&bytes),
+            Err(Error::ArtifactVersionMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_bad_magic() {
+        let mut bytes = artifact().to_bytes().unwrap();
+        bytes[0] = b'X';
+
+        assert_eq!(
+            ApplicationArtifact::from_bytes(&bytes).err(),
+            Some(Error::MalformedArtifact)
+        );
+    }
+
+    #[test]
+    fn rejects_mismatched_hash() {
+        let artifact = artifact();
+        let bytes = artifact.to_bytes().unwrap();
+
+        // Corrupt the stored hash of the first program without changing the
+        // program itself.
+        let hash = ApplicationArtifact::graph_hash(artifact.get_fhe_program("a").unwrap()).unwrap();
+        let pos = bytes.windows(32).position(|w| w == hash).unwrap();
+
+        let mut bytes = bytes;
+        bytes[pos] ^= 0x1;
+
+        assert_eq!(
+            ApplicationArtifact::from_bytes(&bytes).err(),
+            Some(Error::ArtifactHashMismatch(Box::new("a".to_owned())))
+        );
+    }
+
+    #[test]
+    fn rejects_mismatched_params() {
+        let programs = HashMap::from([("a".to_owned(), program(&params(8192)))]);
+
+        let params = params(4096);
+
+        assert_eq!(
+            ApplicationArtifact::new(&params, programs).err(),
+            Some(Error::ArtifactParamsMismatch(Box::new("a".to_owned())))
+        );
+    }
+}
@@ -0,0 +1,747 @@
+use std::time::Duration;
+
+use petgraph::{algo::toposort, stable_graph::NodeIndex, visit::NodeIndexable, Direction};
+use serde::{Deserialize, Serialize};
+use sunscreen_fhe_program::{FheProgram, Literal, Operation};
+
+use crate::{CompiledFheProgram, Params, Result};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+/**
+ * The number of each kind of operation an FHE program performs.
+ */
+pub struct OperationCounts {
+    /**
+     * Ciphertext-ciphertext multiplications.
+     */
+    pub multiplies: usize,
+
+    /**
+     * Relinearizations.
+     */
+    pub relinearizations: usize,
+
+    /**
+     * Key switches performed by row shifts and row swaps.
+     *
+     * # Remarks
+     * The runtime only generates Galois keys for shifts by powers of two,
+     * so a shift by any other amount costs one key switch per term in the
+     * amount's non-adjacent form (e.g. shifting by 7 = 8 - 1 costs 2).
+     */
+    pub rotations: usize,
+
+    /**
+     * Ciphertext-plaintext multiplications.
+     */
+    pub plaintext_multiplies: usize,
+
+    /**
+     * Ciphertext-plaintext additions and subtractions.
+     */
+    pub plaintext_additions: usize,
+
+    /**
+     * Ciphertext-ciphertext additions, subtractions and negations.
+     */
+    pub additions: usize,
+}
+
+impl OperationCounts {
+    /**
+     * Counts the operations in the given FHE program.
+     */
+    pub fn new(ir: &FheProgram) -> Self {
+        let mut counts = Self::default();
+
+        for id in ir.graph.node_indices() {
+            let operation = &ir.graph[id].operation;
+
+            match operation {
+                Operation::Multiply => counts.multiplies += 1,
+                Operation::Relinearize => counts.relinearizations += 1,
+                Operation::ShiftLeft | Operation::ShiftRight | Operation::SwapRows => {
+                    counts.rotations += rotation_key_switches(ir, id)
+                }
+                Operation::MultiplyPlaintext => counts.plaintext_multiplies += 1,
+                Operation::AddPlaintext | Operation::SubPlaintext => {
+                    counts.plaintext_additions += 1
+                }
+                Operation::Add | Operation::Sub | Operation::Negate => counts.additions += 1,
+                Operation::InputCiphertext(_)
+                | Operation::InputPlaintext(_)
+                | Operation::Literal(_)
+                | Operation::OutputCiphertext
+                | Operation::OutputPlaintext => {}
+            };
+        }
+
+        counts
+    }
+}
+
+/**
+ * The number of key switches the rotation at the given node performs.
+ */
+fn rotation_key_switches(ir: &FheProgram, id: NodeIndex) -> usize {
+    let graph = &ir.graph.0;
+
+    if matches!(graph[id].operation, Operation::SwapRows) {
+        return 1;
+    }
+
+    let steps = graph
+        .neighbors_directed(id, Direction::Incoming)
+        .find_map(|x| match graph[x].operation {
+            Operation::Literal(Literal::U64(steps)) => Some(steps),
+            _ => None,
+        })
+        .unwrap_or(1);
+
+    naf_weight(steps).max(1)
+}
+
+/**
+ * The number of non-zero terms in `x`'s non-adjacent form.
+ */
+fn naf_weight(mut x: u64) -> usize {
+    let mut weight = 0;
+
+    while x != 0 {
+        if x & 1 == 1 {
+            weight += 1;
+
+            // Round to the nearest multiple of 4 so runs of ones collapse
+            // into a single subtraction.
+            x = if x & 2 == 2 { x.wrapping_add(1) } else { x - 1 };
+        }
+
+        x >>= 1;
+    }
+
+    weight
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/**
+ * How long each FHE operation takes under a reference parameter set.
+ */
+pub struct OperationTiming {
+    /**
+     * The lattice dimension the timings were measured at.
+     */
+    pub lattice_dimension: u64,
+
+    /**
+     * The number of coefficient moduli (including the special modulus) the
+     * timings were measured with.
+     */
+    pub coeff_modulus_count: usize,
+
+    /**
+     * A ciphertext-ciphertext multiplication.
+     */
+    pub multiply: Duration,
+
+    /**
+     * A relinearization. Zero if the reference parameters don't support
+     * key switching, i.e. have a single coefficient modulus.
+     */
+    pub relinearize: Duration,
+
+    /**
+     * A single key switching row rotation. Zero if the reference parameters
+     * don't support key switching.
+     */
+    pub rotate: Duration,
+
+    /**
+     * A ciphertext-plaintext multiplication.
+     */
+    pub multiply_plaintext: Duration,
+
+    /**
+     * A ciphertext-plaintext addition.
+     */
+    pub add_plaintext: Duration,
+
+    /**
+     * A ciphertext-ciphertext addition.
+     */
+    pub add: Duration,
+}
+
+impl OperationTiming {
+    /**
+     * Estimates these timings under the given parameters.
+     *
+     * # Remarks
+     * Operations scale with the NTT's `n log n` in the lattice dimension.
+     * Multiplications and key switches are quadratic in the number of
+     * coefficient moduli, while the remaining operations are linear.
+     */
+    fn scale(&self, params: &Params) -> Self {
+        let n_log_n = |n: u64| n as f64 * (n.max(2) as f64).log2();
+
+        let dimension = n_log_n(params.lattice_dimension) / n_log_n(self.lattice_dimension);
+        let moduli = params.coeff_modulus.len().max(1) as f64 / self.coeff_modulus_count as f64;
+
+        let linear = |x: Duration| x.mul_f64(dimension * moduli);
+        let quadratic = |x: Duration| x.mul_f64(dimension * moduli * moduli);
+
+        Self {
+            lattice_dimension: params.lattice_dimension,
+            coeff_modulus_count: params.coeff_modulus.len(),
+            multiply: quadratic(self.multiply),
+            relinearize: quadratic(self.relinearize),
+            rotate: quadratic(self.rotate),
+            multiply_plaintext: linear(self.multiply_plaintext),
+            add_plaintext: linear(self.add_plaintext),
+            add: linear(self.add),
+        }
+    }
+
+    /**
+     * The time a single node performing the given operation takes.
+     */
+    fn of(&self, ir: &FheProgram, id: NodeIndex) -> Duration {
+        match ir.graph[id].operation {
+            Operation::Multiply => self.multiply,
+            Operation::Relinearize => self.relinearize,
+            Operation::ShiftLeft | Operation::ShiftRight | Operation::SwapRows => {
+                self.rotate * rotation_key_switches(ir, id) as u32
+            }
+            Operation::MultiplyPlaintext => self.multiply_plaintext,
+            Operation::AddPlaintext | Operation::SubPlaintext => self.add_plaintext,
+            Operation::Add | Operation::Sub | Operation::Negate => self.add,
+            Operation::InputCiphertext(_)
+            | Operation::InputPlaintext(_)
+            | Operation::Literal(_)
+            | Operation::OutputCiphertext
+            | Operation::OutputPlaintext => Duration::ZERO,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/**
+ * Where the measurements in an [`OperationTimings`] table came from.
+ */
+pub struct TimingsProvenance {
+    /**
+     * The CPU the `fhe_op_timings` benchmark ran on.
+     */
+    pub machine: String,
+
+    /**
+     * The commit of this repository the benchmark was built from.
+     */
+    pub commit: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "TimingTable", into = "TimingTable")]
+/**
+ * A table of single-threaded per-operation timings at several reference
+ * parameter sets.
+ *
+ * # Remarks
+ * The default table in `op_timings.json` holds rough, order-of-magnitude
+ * estimates rather than measurements from any particular machine, so it
+ * has no [`provenance`](Self::provenance). For meaningful latencies, run
+ * the `fhe_op_timings` benchmark under `benchmarks/` on your hardware and
+ * load its output with [`OperationTimings::from_json`].
+ */
+pub struct OperationTimings {
+    provenance: Option<TimingsProvenance>,
+    entries: Vec<OperationTiming>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+/**
+ * The serialized form of [`OperationTimings`].
+ */
+struct TimingTable {
+    #[serde(default)]
+    provenance: Option<TimingsProvenance>,
+    entries: Vec<OperationTiming>,
+}
+
+impl OperationTimings {
+    /**
+     * Reads a timing table from JSON, as written by the `fhe_op_timings`
+     * benchmark.
+     */
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /**
+     * Serializes this table as pretty-printed JSON.
+     */
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /**
+     * The reference timings in this table.
+     */
+    pub fn entries(&self) -> &[OperationTiming] {
+        &self.entries
+    }
+
+    /**
+     * Where these timings were measured, or `None` if they're estimates.
+     */
+    pub fn provenance(&self) -> Option<&TimingsProvenance> {
+        self.provenance.as_ref()
+    }
+
+    /**
+     * Records where these timings were measured.
+     */
+    pub fn with_provenance(mut self, provenance: TimingsProvenance) -> Self {
+        self.provenance = Some(provenance);
+        self
+    }
+
+    /**
+     * Estimates the timings under the given parameters from the entry
+     * with the nearest lattice dimension. Parameters supporting key
+     * switching prefer entries that measured it.
+     */
+    fn estimate(&self, params: &Params) -> OperationTiming {
+        let key_switching = params.coeff_modulus.len() > 1;
+
+        self.entries
+            .iter()
+            .min_by_key(|x| {
+                (
+                    key_switching && x.coeff_modulus_count == 1,
+                    distance(x.lattice_dimension, params.lattice_dimension),
+                    distance(
+                        x.coeff_modulus_count as u64,
+                        params.coeff_modulus.len() as u64,
+                    ),
+                )
+            })
+            .expect("Timing tables are non-empty")
+            .scale(params)
+    }
+}
+
+fn distance(a: u64, b: u64) -> u64 {
+    a.max(b) - a.min(b)
+}
+
+impl TryFrom<Vec<OperationTiming>> for OperationTimings {
+    type Error = &'static str;
+
+    fn try_from(entries: Vec<OperationTiming>) -> std::result::Result<Self, Self::Error> {
+        if entries.is_empty() {
+            return Err("a timing table needs at least one entry");
+        }
+
+        if entries.iter().any(|x| x.coeff_modulus_count == 0) {
+            return Err("timings need at least one coefficient modulus");
+        }
+
+        Ok(Self {
+            provenance: None,
+            entries,
+        })
+    }
+}
+
+impl From<OperationTimings> for Vec<OperationTiming> {
+    fn from(timings: OperationTimings) -> Self {
+        timings.entries
+    }
+}
+
+impl TryFrom<TimingTable> for OperationTimings {
+    type Error = &'static str;
+
+    fn try_from(table: TimingTable) -> std::result::Result<Self, Self::Error> {
+        let timings = Self::try_from(table.entries)?;
+
+        Ok(Self {
+            provenance: table.provenance,
+            ..timings
+        })
+    }
+}
+
+impl From<OperationTimings> for TimingTable {
+    fn from(timings: OperationTimings) -> Self {
+        Self {
+            provenance: timings.provenance,
+            entries: timings.entries,
+        }
+    }
+}
+
+impl Default for OperationTimings {
+    fn default() -> Self {
+        Self::from_json(include_str!("op_timings.json")).expect("Default timings are valid")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/**
+ * The predicted cost of running an FHE program under a set of parameters.
+ * See [`CompiledFheProgram::estimate_cost`].
+ */
+pub struct CostEstimate {
+    /**
+     * The operations the program performs.
+     */
+    pub operation_counts: OperationCounts,
+
+    /**
+     * The predicted time to run the program on a single thread.
+     */
+    pub latency: Duration,
+
+    /**
+     * The predicted time of the program's slowest dependency chain, i.e.
+     * the latency with unlimited threads.
+     */
+    pub critical_path_latency: Duration,
+
+    /**
+     * The size (in bytes) of a freshly encrypted ciphertext.
+     */
+    pub ciphertext_size: u64,
+
+    /**
+     * The predicted memory (in bytes) a run uses, including the
+     * evaluation keys it requires. The runtime holds every intermediate
+     * value until the run completes.
+     */
+    pub memory: u64,
+
+    /**
+     * The size (in bytes) of the public key.
+     */
+    pub public_key_size: u64,
+
+    /**
+     * The size (in bytes) of the relinearization keys, or [`None`] if the
+     * program doesn't need them.
+     */
+    pub relinearization_keys_size: Option<u64>,
+
+    /**
+     * The size (in bytes) of the Galois keys, or [`None`] if the program
+     * doesn't need them.
+     */
+    pub galois_keys_size: Option<u64>,
+}
+
+/**
+ * Sizes (in bytes) of SEAL's in-memory objects under a set of parameters.
+ */
+struct Sizes {
+    n: u64,
+    key_moduli: u64,
+    data_moduli: u64,
+}
+
+impl Sizes {
+    fn new(params: &Params) -> Self {
+        let key_moduli = params.coeff_modulus.len().max(1) as u64;
+
+        Self {
+            n: params.lattice_dimension,
+            key_moduli,
+            // The last modulus is reserved for key switching when there's
+            // more than one.
+            data_moduli: (key_moduli - 1).max(1),
+        }
+    }
+
+    fn ciphertext(&self, polys: u64) -> u64 {
+        polys * self.n * self.data_moduli * 8
+    }
+
+    fn plaintext(&self) -> u64 {
+        self.n * 8
+    }
+
+    fn public_key(&self) -> u64 {
+        2 * self.n * self.key_moduli * 8
+    }
+
+    /**
+     * A key switching key, made of one public key per data modulus.
+     */
+    fn key_switching_key(&self) -> u64 {
+        self.data_moduli * self.public_key()
+    }
+
+    /**
+     * SEAL's default Galois keys: one for swapping rows and one for each
+     * power of two shift in each direction.
+     */
+    fn galois_keys(&self) -> u64 {
+        // Lattice dimensions are powers of two.
+        let log_n = self.n.max(2).trailing_zeros() as u64;
+
+        (1 + 2 * (log_n - 1)) * self.key_switching_key()
+    }
+}
+
+impl CompiledFheProgram {
+    /**
+     * Predicts the latency, memory and key sizes of running this program
+     * under the given parameters, using the default
+     * [`OperationTimings`].
+     *
+     * # Remarks
+     * The given parameters needn't be the ones the program was compiled
+     * with, so you can compare the cost of different lattice dimensions.
+     * The default timings are rough estimates; when planning capacity,
+     * measure a table on your hardware and pass it to
+     * [`estimate_cost_with_timings`](Self::estimate_cost_with_timings).
+     *
+     * The compiler chooses parameters by noise and security alone and
+     * doesn't consult these estimates.
+     */
+    pub fn estimate_cost(&self, params: &Params) -> CostEstimate {
+        self.estimate_cost_with_timings(params, &OperationTimings::default())
+    }
+
+    /**
+     * The same as [`estimate_cost`](Self::estimate_cost), but using the
+     * given timing table.
+     */
+    pub fn estimate_cost_with_timings(
+        &self,
+        params: &Params,
+        timings: &OperationTimings,
+    ) -> CostEstimate {
+        let ir = &self.fhe_program_fn;
+        let graph = &ir.graph.0;
+        let timing = timings.estimate(params);
+        let sizes = Sizes::new(params);
+
+        // When each node can finish if every node it depends on runs as soon
+        // as possible.
+        let mut finish = vec![Duration::ZERO; graph.node_bound()];
+        let mut latency = Duration::ZERO;
+        let mut memory = 0;
+
+        for id in toposort(graph, None).expect("FHE programs are acyclic") {
+            let time = timing.of(ir, id);
+
+            let start = graph
+                .neighbors_directed(id, Direction::Incoming)
+                .map(|x| finish[x.index()])
+                .max()
+                .unwrap_or_default();
+
+            finish[id.index()] = start + time;
+            latency += time;
+
+            memory += match &graph[id].operation {
+                // Products have 3 polynomials until relinearized.
+                Operation::Multiply => sizes.ciphertext(3),
+                Operation::InputPlaintext(_) | Operation::Literal(Literal::Plaintext(_)) => {
+                    sizes.plaintext()
+                }
+                // Outputs share their operand's data.
+                Operation::Literal(Literal::U64(_))
+                | Operation::OutputCiphertext
+                | Operation::OutputPlaintext => 0,
+                _ => sizes.ciphertext(2),
+            };
+        }
+
+        let operation_counts = OperationCounts::new(ir);
+
+        let relinearization_keys_size =
+            (operation_counts.relinearizations > 0).then(|| sizes.key_switching_key());
+        let galois_keys_size = (operation_counts.rotations > 0).then(|| sizes.galois_keys());
+
+        CostEstimate {
+            operation_counts,
+            latency,
+            critical_path_latency: finish.into_iter().max().unwrap_or_default(),
+            ciphertext_size: sizes.ciphertext(2),
+            memory: memory + relinearization_keys_size.unwrap_or(0) + galois_keys_size.unwrap_or(0),
+            public_key_size: sizes.public_key(),
+            relinearization_keys_size,
+            galois_keys_size,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CallSignature, FheProgramMetadata};
+    use seal_fhe::SecurityLevel;
+    use sunscreen_fhe_program::{FheProgramTrait, SchemeType};
+
+    fn params(lattice_dimension: u64, coeff_modulus_count: usize) -> Params {
+        Params {
+            lattice_dimension,
+            coeff_modulus: vec![0; coeff_modulus_count],
+            plain_modulus: 1_032_193,
+            scheme_type: SchemeType::Bfv,
+            security_level: SecurityLevel::TC128,
+        }
+    }
+
+    fn compiled(ir: FheProgram) -> CompiledFheProgram {
+        CompiledFheProgram {
+            fhe_program_fn: ir,
+            metadata: FheProgramMetadata {
+                params: params(4096, 3),
+                signature: CallSignature {
+                    arguments: vec![],
+                    returns: vec![],
+                    num_ciphertexts: vec![],
+                },
+                required_keys: vec![],
+            },
+        }
+    }
+
+    fn timings() -> OperationTimings {
+        let us = Duration::from_micros;
+
+        OperationTimings::try_from(vec![OperationTiming {
+            lattice_dimension: 4096,
+            coeff_modulus_count: 3,
+            multiply: us(1000),
+            relinearize: us(300),
+            rotate: us(200),
+            multiply_plaintext: us(100),
+            add_plaintext: us(20),
+            add: us(10),
+        }])
+        .unwrap()
+    }
+
+    /**
+     * `(a * b) relinearized + (a << 7)`.
+     */
+    fn program() -> FheProgram {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let a = ir.add_input_ciphertext(0);
+        let b = ir.add_input_ciphertext(1);
+        let ab = ir.add_multiply(a, b);
+        let ab = ir.add_relinearize(ab);
+        let seven = ir.add_input_literal(Literal::U64(7));
+        let shifted = ir.add_rotate_left(a, seven);
+        let sum = ir.add_add(ab, shifted);
+        ir.add_output_ciphertext(sum);
+
+        ir
+    }
+
+    #[test]
+    fn counts_operations() {
+        assert_eq!(
+            OperationCounts::new(&program()),
+            OperationCounts {
+                multiplies: 1,
+                relinearizations: 1,
+                // 7 = 8 - 1
+                rotations: 2,
+                additions: 1,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn naf_weights() {
+        assert_eq!(naf_weight(0), 0);
+        assert_eq!(naf_weight(1), 1);
+        assert_eq!(naf_weight(8), 1);
+        assert_eq!(naf_weight(7), 2);
+        assert_eq!(naf_weight(5), 2);
+        assert_eq!(naf_weight(0b1011), 3);
+    }
+
+    #[test]
+    fn estimates_latency_and_sizes() {
+        let cost = compiled(program()).estimate_cost_with_timings(&params(4096, 3), &timings());
+
+        let us = Duration::from_micros;
+
+        assert_eq!(cost.latency, us(1000 + 300 + 2 * 200 + 10));
+        assert_eq!(cost.critical_path_latency, us(1000 + 300 + 10));
+
+        // 2 polynomials * 4096 coefficients * 2 data moduli * 8 bytes
+        assert_eq!(cost.ciphertext_size, 131_072);
+        assert_eq!(cost.public_key_size, 2 * 4096 * 3 * 8);
+        assert_eq!(
+            cost.relinearization_keys_size,
+            Some(2 * cost.public_key_size)
+        );
+        // 23 Galois keys for 4096.
+        assert_eq!(cost.galois_keys_size, Some(23 * 2 * cost.public_key_size));
+
+        // 2 inputs, a relinearized product, a rotation and a sum, plus the
+        // 3 polynomial product and the keys.
+        let ciphertexts = 5 * cost.ciphertext_size + 3 * cost.ciphertext_size / 2;
+
+        assert_eq!(
+            cost.memory,
+            ciphertexts + cost.relinearization_keys_size.unwrap() + cost.galois_keys_size.unwrap()
+        );
+    }
+
+    #[test]
+    fn scales_to_other_parameters() {
+        let program = compiled(program());
+
+        let small = program.estimate_cost_with_timings(&params(4096, 3), &timings());
+        let large = program.estimate_cost_with_timings(&params(8192, 5), &timings());
+
+        assert!(large.latency > 2 * small.latency);
+        assert_eq!(large.ciphertext_size, 4 * small.ciphertext_size);
+        assert_eq!(large.operation_counts, small.operation_counts);
+    }
+
+    #[test]
+    fn default_timings_cover_standard_dimensions() {
+        let timings = OperationTimings::default();
+
+        for n in [1024, 2048, 4096, 8192, 16384, 32768] {
+            assert!(timings.entries().iter().any(|x| x.lattice_dimension == n));
+        }
+    }
+
+    #[test]
+    fn rejects_empty_timing_tables() {
+        assert!(OperationTimings::from_json(r#"{ "entries": [] }"#).is_err());
+
+        let json = timings().to_json().unwrap();
+
+        assert_eq!(OperationTimings::from_json(&json).unwrap(), timings());
+    }
+
+    #[test]
+    fn records_provenance() {
+        assert_eq!(OperationTimings::default().provenance(), None);
+
+        let provenance = TimingsProvenance {
+            machine: "Test CPU".to_owned(),
+            commit: "0123abc".to_owned(),
+        };
+
+        let timings = timings().with_provenance(provenance.clone());
+        let json = timings.to_json().unwrap();
+
+        assert_eq!(
+            OperationTimings::from_json(&json).unwrap().provenance(),
+            Some(&provenance)
+        );
+    }
+}
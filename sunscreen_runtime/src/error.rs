@@ -66,6 +66,14 @@ pub enum Error {
     #[error("Data returned from FHE program doesn't match return signature")]
     ReturnTypeMetadataError,
 
+    /**
+     * The FHE program returns unencrypted values, which
+     * [`run`](crate::Runtime::run) can't return. Use
+     * [`run_with_options`](crate::Runtime::run_with_options) instead.
+     */
+    #[error("The FHE program has plaintext outputs; run it with run_with_options")]
+    PlaintextOutputs,
+
     /**
      * Decryption failed because the cipher text had too much noise.
      */
@@ -109,6 +117,12 @@ pub enum Error {
     #[error("Bincode serialization failed: {0}")]
     BincodeError(Box<String>),
 
+    /**
+     * An error occurred when serializing/deserializing JSON.
+     */
+    #[error("JSON serialization failed: {0}")]
+    JsonError(Box<String>),
+
     /**
      * Called [`inner_as_seal_plaintext`](crate::InnerPlaintext.inner_as_seal_plaintext)
      * on non-Seal plaintext.
@@ -122,6 +136,43 @@ pub enum Error {
     #[error("ZKP error: {0}")]
     ZkpError(#[from] ZkpError),
 
+    /**
+     * The bytes given to [`ApplicationArtifact::from_bytes`](crate::ApplicationArtifact::from_bytes)
+     * aren't an application artifact.
+     */
+    #[error("Malformed application artifact")]
+    MalformedArtifact,
+
+    /**
+     * An application artifact has a different format version than this crate supports.
+     */
+    #[error("Unsupported application artifact version {0}")]
+    ArtifactVersionMismatch(u32),
+
+    /**
+     * The graph of the named FHE program in an application artifact doesn't match its hash.
+     */
+    #[error("FHE program {0} doesn't match its hash")]
+    ArtifactHashMismatch(Box<String>),
+
+    /**
+     * The named FHE program in an application artifact doesn't use the artifact's parameters.
+     */
+    #[error("FHE program {0} has different parameters than the application")]
+    ArtifactParamsMismatch(Box<String>),
+
+    /**
+     * A ciphertext failed validation. See [`Ciphertext::validate`](crate::Ciphertext::validate).
+     */
+    #[error("Invalid ciphertext: {0}")]
+    InvalidCiphertext(Box<String>),
+
+    /**
+     * An I/O error occurred.
+     */
+    #[error("I/O error: {0}")]
+    IoError(Box<String>),
+
     /**
      * An error occurred from incorrect usage of a builder.
      */
@@ -163,6 +214,20 @@ impl Error {
         Self::FheTypeError(Box::new(msg.to_owned()))
     }
 
+    /**
+     * Create an [`Error::InvalidCiphertext`].
+     */
+    pub fn invalid_ciphertext(msg: &str) -> Self {
+        Self::InvalidCiphertext(Box::new(msg.to_owned()))
+    }
+
+    /**
+     * Create an [`Error::IoError`].
+     */
+    pub fn io_error(err: &std::io::Error) -> Self {
+        Self::IoError(Box::new(format!("{}", err)))
+    }
+
     fn unwrap_argument_mismatch_data(&self) -> &(Vec<Type>, Vec<Type>) {
         match self {
             Self::ArgumentMismatch(d) => d,
@@ -184,6 +249,12 @@ impl From<bincode::Error> for Error {
     }
 }
 
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Self::JsonError(Box::new(format!("{}", err)))
+    }
+}
+
 /**
  * Wrapper around [`Result`](std::result::Result) with this crate's error type.
  */
@@ -1,7 +1,8 @@
 use crate::serialization::WithContext;
 
 use seal_fhe::{
-    GaloisKeys, PublicKey as SealPublicKey, RelinearizationKeys, SecretKey as SealSecretKey,
+    GaloisKeys, KeySwitchingKeys, PublicKey as SealPublicKey, RelinearizationKeys,
+    SecretKey as SealSecretKey,
 };
 use serde::{Deserialize, Serialize};
 
@@ -52,6 +53,21 @@ pub struct PublicKey {
  */
 pub struct PrivateKey(pub(crate) WithContext<SealSecretKey>);
 
+#[derive(Clone, Deserialize, PartialEq, Serialize)]
+/**
+ * A key for switching ciphertexts encrypted under one [`PrivateKey`] to
+ * encryptions of the same values under another, without decrypting them.
+ * Create one with
+ * [`generate_key_switching_key`](crate::GenericRuntime::generate_key_switching_key)
+ * and apply it with [`switch_key`](crate::GenericRuntime::switch_key).
+ *
+ * # Remarks
+ * This key is an encryption of the old private key under the new one. As such,
+ * it may be given to whichever party stores the ciphertexts so they can migrate
+ * them to the new key without being able to decrypt them.
+ */
+pub struct KeySwitchingKey(pub(crate) WithContext<KeySwitchingKeys>);
+
 #[cfg(test)]
 mod tests {
     use super::*;
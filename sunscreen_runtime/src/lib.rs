@@ -4,7 +4,9 @@
 //! This crate contains the types and functions for executing a Sunscreen FHE or ZKP program.
 
 mod array;
+mod artifact;
 mod builder;
+mod cost;
 mod error;
 mod keys;
 #[cfg(feature = "linkedproofs")]
@@ -20,7 +22,9 @@ use seal_fhe::{Ciphertext as SealCiphertext, Plaintext as SealPlaintext};
 use serde::{Deserialize, Serialize};
 use sunscreen_zkp_backend::BigInt;
 
+pub use artifact::*;
 pub use builder::*;
+pub use cost::*;
 pub use error::*;
 pub use keys::*;
 #[cfg(feature = "linkedproofs")]
@@ -203,6 +207,62 @@ impl Ciphertext {
     pub fn inner_as_seal_ciphertext(&self) -> Result<&[WithContext<SealCiphertext>]> {
         self.inner.as_seal_ciphertext()
     }
+
+    /**
+     * Checks this ciphertext is well-formed under the given parameters. You should call
+     * this on ciphertexts from untrusted sources (e.g. the network) before running FHE
+     * programs on them, or run with [`RunOptions::strict`].
+     *
+     * # Remarks
+     * Returns [`Error::ParameterMismatch`] if the ciphertext was created under different
+     * parameters. Returns [`Error::InvalidCiphertext`] if
+     * * it doesn't have exactly 2 polynomials.
+     * * its metadata doesn't match `params` or any of its coefficients isn't reduced
+     *   modulo its coefficient modulus.
+     * * it's transparent (i.e. doesn't hide its message), unless the
+     *   `transparent-ciphertexts` feature is enabled.
+     *
+     * This function creates a SEAL context for `params`, which is somewhat expensive.
+     */
+    pub fn validate(&self, params: &Params) -> Result<()> {
+        let context = runtime::make_seal_context(params)?;
+
+        self.validate_with_context(params, &context)
+    }
+
+    pub(crate) fn validate_with_context(
+        &self,
+        params: &Params,
+        context: &seal_fhe::Context,
+    ) -> Result<()> {
+        for c in self.inner_as_seal_ciphertext()? {
+            if c.params != *params {
+                return Err(Error::ParameterMismatch);
+            }
+
+            let num_polynomials = c.data.num_polynomials();
+
+            if num_polynomials != 2 {
+                return Err(Error::invalid_ciphertext(&format!(
+                    "expected 2 polynomials, found {}",
+                    num_polynomials
+                )));
+            }
+
+            if !c.data.is_valid_for(context) {
+                return Err(Error::invalid_ciphertext(
+                    "ciphertext is not valid for the given parameters",
+                ));
+            }
+
+            #[cfg(not(feature = "transparent-ciphertexts"))]
+            if c.data.is_transparent() {
+                return Err(Error::invalid_ciphertext("ciphertext is transparent"));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /**
@@ -226,6 +286,66 @@ pub enum FheProgramInput {
     Plaintext(Box<dyn FheProgramInputTrait>),
 }
 
+#[derive(Clone, Deserialize, Serialize)]
+/**
+ * A return value from an Fhe Program. See [`crate::Runtime::run_with_options`].
+ */
+pub enum FheProgramOutput {
+    /**
+     * The return value is encrypted.
+     */
+    Ciphertext(Ciphertext),
+
+    /**
+     * The return value is unencrypted. Decode it with
+     * [`decode`](crate::Runtime::decode).
+     */
+    Plaintext(Plaintext),
+}
+
+impl FheProgramOutput {
+    /**
+     * Returns the return value's ciphertext, or [`None`] if it's unencrypted.
+     */
+    pub fn ciphertext(&self) -> Option<&Ciphertext> {
+        match self {
+            Self::Ciphertext(c) => Some(c),
+            Self::Plaintext(_) => None,
+        }
+    }
+
+    /**
+     * Returns the return value's plaintext, or [`None`] if it's encrypted.
+     */
+    pub fn plaintext(&self) -> Option<&Plaintext> {
+        match self {
+            Self::Ciphertext(_) => None,
+            Self::Plaintext(p) => Some(p),
+        }
+    }
+}
+
+impl TypeNameInstance for FheProgramOutput {
+    fn type_name_instance(&self) -> Type {
+        match self {
+            Self::Ciphertext(c) => c.data_type.clone(),
+            Self::Plaintext(p) => p.data_type.clone(),
+        }
+    }
+}
+
+impl From<Ciphertext> for FheProgramOutput {
+    fn from(val: Ciphertext) -> Self {
+        Self::Ciphertext(val)
+    }
+}
+
+impl From<Plaintext> for FheProgramOutput {
+    fn from(val: Plaintext) -> Self {
+        Self::Plaintext(val)
+    }
+}
+
 /**
  * Denotes this type can be used as an input to a ZKP
  * program.
@@ -40,6 +40,8 @@ pub struct CallSignature {
      * # Remarks
      * The number of ciphertexts composing the ith return value of the program occupies the ith
      * element of the vector. The length of this vector equals the length of the returns.
+     *
+     * For unencrypted return values, this is instead the number of plaintexts.
      */
     // TODO This field is specific to FHE; should we segment the types here? CallSignature<Fhe|Zkp> ?
     pub num_ciphertexts: Vec<usize>,
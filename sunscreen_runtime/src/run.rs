@@ -1,17 +1,22 @@
 use crate::{InnerPlaintext, SealData};
 use static_assertions::const_assert;
 use sunscreen_compiler_common::{GraphQuery, GraphQueryError};
-use sunscreen_fhe_program::{FheProgram, FheProgramTrait, Literal, Operation::*};
+use sunscreen_fhe_program::{FheProgram, FheProgramTrait, Literal, Operation, Operation::*};
 
 use crossbeam::atomic::AtomicCell;
 use petgraph::{stable_graph::NodeIndex, Direction};
+use serde::{Deserialize, Serialize};
 
 use std::borrow::Cow;
 #[cfg(target_arch = "wasm32")]
 use std::collections::VecDeque;
-#[cfg(not(target_arch = "wasm32"))]
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::future::Future;
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::{Context as TaskContext, Poll, Waker};
+use std::time::{Duration, Instant};
 
 use seal_fhe::{
     Ciphertext, Error as SealError, Evaluator, GaloisKeys, Plaintext, RelinearizationKeys,
@@ -71,6 +76,12 @@ pub enum FheProgramRunFailure {
      */
     #[error("Graph query error {0}")]
     GraphQueryError(#[from] GraphQueryError),
+
+    /**
+     * The run was cancelled through its [`CancellationToken`].
+     */
+    #[error("The FHE program run was cancelled")]
+    Cancelled,
 }
 
 const_assert!(std::mem::size_of::<FheProgramRunFailure>() <= 16);
@@ -81,6 +92,311 @@ impl From<SealError> for FheProgramRunFailure {
     }
 }
 
+#[derive(Debug, Clone, Default)]
+/**
+ * A token for cancelling an FHE program run from another thread.
+ *
+ * # Remarks
+ * Clones of a token share their state, so cancelling any clone cancels
+ * every run holding one. Cancellation is cooperative: nodes already
+ * executing run to completion, but no further nodes start and the run
+ * returns [`FheProgramRunFailure::Cancelled`].
+ */
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /**
+     * Creates a new token that isn't cancelled.
+     */
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+     * Cancels any runs holding this token.
+     */
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    /**
+     * Returns whether this token has been cancelled.
+     */
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/**
+ * The progress of an FHE program run, passed to the callback given to
+ * [`RunOptions::progress`].
+ */
+pub struct RunProgress {
+    /**
+     * The number of nodes in the FHE program that have finished executing.
+     */
+    pub completed: usize,
+
+    /**
+     * The total number of nodes in the FHE program.
+     */
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/**
+ * The time spent executing a single node in an FHE program.
+ */
+pub struct NodeTiming {
+    /**
+     * The index of the node in the FHE program's graph.
+     */
+    pub node: NodeIndex,
+
+    /**
+     * The operation the node performs.
+     */
+    pub operation: Operation,
+
+    /**
+     * When the node started executing, relative to the start of the run.
+     */
+    pub start: Duration,
+
+    /**
+     * How long the node took to execute.
+     */
+    pub duration: Duration,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/**
+ * A per-node timing trace of an FHE program run. Request one with
+ * [`RunOptions::trace`].
+ */
+pub struct RunTrace {
+    /**
+     * The timing of each executed node, ordered by start time.
+     */
+    pub nodes: Vec<NodeTiming>,
+
+    /**
+     * The wall-clock time of the whole run.
+     */
+    pub total: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/**
+ * The noise budget of the ciphertext a single node in an FHE program
+ * produced.
+ */
+pub struct NodeNoiseBudget {
+    /**
+     * The index of the node in the FHE program's graph.
+     */
+    pub node: usize,
+
+    /**
+     * The operation the node performs.
+     */
+    pub operation: Operation,
+
+    /**
+     * The measured invariant noise budget (in bits) of the node's
+     * ciphertext.
+     */
+    pub noise_budget: u32,
+
+    /**
+     * The noise budget (in bits) a noise model predicted for this node, if
+     * set with [`NoiseReport::set_predicted_noise_budgets`].
+     */
+    pub predicted_noise_budget: Option<f64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+/**
+ * The noise budget remaining after every ciphertext-producing node in an FHE
+ * program run. Request one with
+ * [`run_debug`](crate::GenericRuntime::run_debug).
+ *
+ * # Remarks
+ * Use [`set_predicted_noise_budgets`](Self::set_predicted_noise_budgets) to
+ * compare the measurements against a noise model's predictions and
+ * [`to_json`](Self::to_json) to export the report.
+ */
+pub struct NoiseReport {
+    /**
+     * The noise budget of each node producing a ciphertext, ordered by node
+     * index.
+     */
+    pub nodes: Vec<NodeNoiseBudget>,
+}
+
+impl NoiseReport {
+    /**
+     * Attaches predicted noise budgets (in bits) to this report's nodes.
+     * `predicted` is indexed by node index, so `predicted[i]` is the budget
+     * predicted for node `i`. Nodes without a prediction get [`None`].
+     */
+    pub fn set_predicted_noise_budgets(&mut self, predicted: &[f64]) {
+        for n in self.nodes.iter_mut() {
+            n.predicted_noise_budget = predicted.get(n.node).copied();
+        }
+    }
+
+    /**
+     * The node with the least remaining noise budget, if any.
+     */
+    pub fn min_noise_budget(&self) -> Option<&NodeNoiseBudget> {
+        self.nodes.iter().min_by_key(|n| n.noise_budget)
+    }
+
+    /**
+     * Serializes this report as pretty-printed JSON.
+     */
+    pub fn to_json(&self) -> crate::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/**
+ * Measures the invariant noise budget of a ciphertext, given to
+ * [`run_program_with_noise_budget`].
+ */
+pub(crate) type NoiseBudgetFn<'a> = &'a (dyn Fn(&Ciphertext) -> Result<u32, SealError> + Sync);
+
+/**
+ * A callback that receives [`RunProgress`] updates.
+ */
+pub type ProgressCallback = Arc<dyn Fn(RunProgress) + Send + Sync>;
+
+#[derive(Clone, Default)]
+/**
+ * Options for running an FHE program with
+ * [`run_with_options`](crate::GenericRuntime::run_with_options),
+ * [`run_async`](crate::GenericRuntime::run_async),
+ * [`run_batch`](crate::GenericRuntime::run_batch) or
+ * [`run_program_unchecked_with_options`].
+ *
+ * # Remarks
+ * The default options run on rayon's global thread pool without
+ * cancellation, progress reporting, tracing or strict validation, exactly as
+ * [`run`](crate::GenericRuntime::run) does.
+ */
+pub struct RunOptions {
+    thread_pool: Option<Arc<rayon::ThreadPool>>,
+    cancellation_token: Option<CancellationToken>,
+    progress: Option<ProgressCallback>,
+    trace: bool,
+    strict: bool,
+}
+
+impl RunOptions {
+    /**
+     * Creates the default options.
+     */
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+     * Execute the FHE program's nodes on the given thread pool rather than
+     * rayon's global pool.
+     */
+    pub fn thread_pool(mut self, thread_pool: Arc<rayon::ThreadPool>) -> Self {
+        self.thread_pool = Some(thread_pool);
+        self
+    }
+
+    /**
+     * Stop the run early when the given token gets cancelled.
+     */
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    /**
+     * Call the given callback each time a node in the FHE program finishes
+     * executing.
+     *
+     * # Remarks
+     * Nodes execute in parallel, so the callback may be called concurrently
+     * from multiple threads and should return quickly.
+     */
+    pub fn progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(RunProgress) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(callback));
+        self
+    }
+
+    /**
+     * Whether to record a [`RunTrace`] of how long each node took.
+     */
+    pub fn trace(mut self, trace: bool) -> Self {
+        self.trace = trace;
+        self
+    }
+
+    /**
+     * Whether to validate the arguments before running. In strict mode, every
+     * ciphertext argument must pass [`Ciphertext::validate`](crate::Ciphertext::validate)
+     * and every input the FHE program reads must refer to an argument
+     * ciphertext or plaintext as appropriate.
+     *
+     * # Remarks
+     * You should enable this when running on ciphertexts from untrusted
+     * sources.
+     */
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /**
+     * The token that cancels runs using these options, if any.
+     */
+    pub fn get_cancellation_token(&self) -> Option<&CancellationToken> {
+        self.cancellation_token.as_ref()
+    }
+
+    /**
+     * The thread pool runs using these options execute on, if any.
+     */
+    pub fn get_thread_pool(&self) -> Option<&Arc<rayon::ThreadPool>> {
+        self.thread_pool.as_ref()
+    }
+
+    /**
+     * Whether runs using these options validate their arguments first.
+     */
+    pub fn get_strict(&self) -> bool {
+        self.strict
+    }
+}
+
+impl std::fmt::Debug for RunOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RunOptions")
+            .field("thread_pool", &self.thread_pool)
+            .field("cancellation_token", &self.cancellation_token)
+            .field(
+                "progress",
+                &self.progress.as_ref().map(|_| "Fn(RunProgress)"),
+            )
+            .field("trace", &self.trace)
+            .field("strict", &self.strict)
+            .finish()
+    }
+}
+
 /**
  * You probably should instead use [`Runtime::run()`](crate::Runtime::run).
  *
@@ -92,6 +408,7 @@ impl From<SealError> for FheProgramRunFailure {
  * # Remarks
  * The input and outputs of this method are vectors containing [`seal_fhe::Ciphertext`] values, not the
  * high-level [`Ciphertext`] types. You must first unpack them from the high-level types.
+ * As with [`run_program_unchecked_with_options`], plaintext outputs are dropped.
  *
  * # Safety
  * Calling this method on a malformed [`FheProgram`] may
@@ -104,6 +421,84 @@ pub unsafe fn run_program_unchecked<E: Evaluator + Sync + Send>(
     relin_keys: &Option<&RelinearizationKeys>,
     galois_keys: &Option<&GaloisKeys>,
 ) -> Result<Vec<Ciphertext>, FheProgramRunFailure> {
+    let (output, _) = run_program_unchecked_with_options(
+        ir,
+        inputs,
+        evaluator,
+        relin_keys,
+        galois_keys,
+        &RunOptions::default(),
+    )?;
+
+    Ok(output)
+}
+
+/**
+ * You probably should instead use
+ * [`Runtime::run_with_options()`](crate::Runtime::run_with_options).
+ *
+ * The same as [`run_program_unchecked`], but executes according to the
+ * given [`RunOptions`]. Returns the program's outputs and, if
+ * [`RunOptions::trace`] was set, a [`RunTrace`].
+ *
+ * # Remarks
+ * Only the program's ciphertext outputs are returned; its plaintext
+ * outputs are dropped. Use
+ * [`Runtime::run_with_options()`](crate::Runtime::run_with_options) for
+ * programs with plaintext outputs.
+ *
+ * # Safety
+ * Calling this method on a malformed [`FheProgram`] may
+ * result in panics, non-termination, or undefined behavior.
+ */
+pub unsafe fn run_program_unchecked_with_options<E: Evaluator + Sync + Send>(
+    ir: &FheProgram,
+    inputs: &[SealData],
+    evaluator: &E,
+    relin_keys: &Option<&RelinearizationKeys>,
+    galois_keys: &Option<&GaloisKeys>,
+    options: &RunOptions,
+) -> Result<(Vec<Ciphertext>, Option<RunTrace>), FheProgramRunFailure> {
+    let (output, trace, _) = run_program_with_noise_budget(
+        ir,
+        inputs,
+        evaluator,
+        relin_keys,
+        galois_keys,
+        options,
+        None,
+    )?;
+
+    let output = output
+        .into_iter()
+        .filter_map(|x| match x {
+            SealData::Ciphertext(c) => Some(c),
+            SealData::Plaintext(_) => None,
+        })
+        .collect();
+
+    Ok((output, trace))
+}
+
+/**
+ * The same as [`run_program_unchecked_with_options`], but if `noise_budget`
+ * is given, also measures the noise budget of every ciphertext the program
+ * produces and returns them as a [`NoiseReport`].
+ *
+ * # Safety
+ * Calling this method on a malformed [`FheProgram`] may
+ * result in panics, non-termination, or undefined behavior.
+ */
+#[allow(clippy::type_complexity)]
+pub(crate) unsafe fn run_program_with_noise_budget<E: Evaluator + Sync + Send>(
+    ir: &FheProgram,
+    inputs: &[SealData],
+    evaluator: &E,
+    relin_keys: &Option<&RelinearizationKeys>,
+    galois_keys: &Option<&GaloisKeys>,
+    options: &RunOptions,
+    noise_budget: Option<NoiseBudgetFn>,
+) -> Result<(Vec<SealData>, Option<RunTrace>, Option<NoiseReport>), FheProgramRunFailure> {
     fn get_data(
         data: &[AtomicCell<Option<Arc<SealData>>>],
         index: usize,
@@ -157,203 +552,429 @@ pub unsafe fn run_program_unchecked<E: Evaluator + Sync + Send>(
         data.push(AtomicCell::new(None));
     }
 
-    traverse(
-        ir,
-        |index| {
-            let node = &ir.graph[index];
-            let query = GraphQuery::new(&ir.graph.0);
-
-            match &node.operation {
-                InputCiphertext(id) => {
-                    data[index.index()].store(Some(inputs[*id].clone()));
-                }
-                InputPlaintext(id) => {
-                    data[index.index()].store(Some(inputs[*id].clone()));
-                }
-                ShiftLeft => {
-                    let (left, right) = query.get_binary_operands(index)?;
-
-                    let a = get_ciphertext(&data, left.index())?;
-                    let b = match ir.graph[right].operation {
-                        Literal(Literal::U64(v)) => v as i32,
-                        _ => panic!(
-                            "Illegal right operand for ShiftLeft: {:#?}",
-                            ir.graph[right].operation
-                        ),
-                    };
+    // Instant isn't available on every target (e.g. wasm32), so only query
+    // the clock when tracing.
+    let run_start = options.trace.then(Instant::now);
+    let completed = AtomicUsize::new(0);
+    let timings = Mutex::new(vec![]);
+    let noise_budgets = Mutex::new(vec![]);
 
-                    let c = evaluator.rotate_rows(
-                        a,
-                        b,
-                        galois_keys
-                            .as_ref()
-                            .ok_or(FheProgramRunFailure::MissingGaloisKeys)?,
-                    )?;
+    let run_node = |index: NodeIndex| -> Result<(), FheProgramRunFailure> {
+        let node = &ir.graph[index];
+        let query = GraphQuery::new(&ir.graph.0);
 
-                    data[index.index()].store(Some(Arc::new(c.into())));
-                }
-                ShiftRight => {
-                    let (left, right) = query.get_binary_operands(index)?;
-
-                    let a = get_ciphertext(&data, left.index())?;
-                    let b = match ir.graph[right].operation {
-                        Literal(Literal::U64(v)) => v as i32,
-                        _ => panic!(
-                            "Illegal right operand for ShiftLeft: {:#?}",
-                            ir.graph[right].operation
-                        ),
-                    };
+        match &node.operation {
+            InputCiphertext(id) => {
+                data[index.index()].store(Some(inputs[*id].clone()));
+            }
+            InputPlaintext(id) => {
+                data[index.index()].store(Some(inputs[*id].clone()));
+            }
+            ShiftLeft => {
+                let (left, right) = query.get_binary_operands(index)?;
+
+                let a = get_ciphertext(&data, left.index())?;
+                let b = match ir.graph[right].operation {
+                    Literal(Literal::U64(v)) => v as i32,
+                    _ => panic!(
+                        "Illegal right operand for ShiftLeft: {:#?}",
+                        ir.graph[right].operation
+                    ),
+                };
+
+                let c = evaluator.rotate_rows(
+                    a,
+                    b,
+                    galois_keys
+                        .as_ref()
+                        .ok_or(FheProgramRunFailure::MissingGaloisKeys)?,
+                )?;
 
-                    let c = evaluator.rotate_rows(
-                        a,
-                        -b,
-                        galois_keys
-                            .as_ref()
-                            .ok_or(FheProgramRunFailure::MissingGaloisKeys)?,
-                    )?;
+                data[index.index()].store(Some(Arc::new(c.into())));
+            }
+            ShiftRight => {
+                let (left, right) = query.get_binary_operands(index)?;
+
+                let a = get_ciphertext(&data, left.index())?;
+                let b = match ir.graph[right].operation {
+                    Literal(Literal::U64(v)) => v as i32,
+                    _ => panic!(
+                        "Illegal right operand for ShiftLeft: {:#?}",
+                        ir.graph[right].operation
+                    ),
+                };
+
+                let c = evaluator.rotate_rows(
+                    a,
+                    -b,
+                    galois_keys
+                        .as_ref()
+                        .ok_or(FheProgramRunFailure::MissingGaloisKeys)?,
+                )?;
 
-                    data[index.index()].store(Some(Arc::new(c.into())));
-                }
-                Add => {
-                    let (left, right) = query.get_binary_operands(index)?;
+                data[index.index()].store(Some(Arc::new(c.into())));
+            }
+            Add => {
+                let (left, right) = query.get_binary_operands(index)?;
 
-                    let a = get_ciphertext(&data, left.index())?;
-                    let b = get_ciphertext(&data, right.index())?;
+                let a = get_ciphertext(&data, left.index())?;
+                let b = get_ciphertext(&data, right.index())?;
 
-                    let c = evaluator.add(a, b)?;
+                let c = evaluator.add(a, b)?;
 
-                    data[index.index()].store(Some(Arc::new(c.into())));
-                }
-                AddPlaintext => {
-                    let (left, right) = query.get_binary_operands(index)?;
+                data[index.index()].store(Some(Arc::new(c.into())));
+            }
+            AddPlaintext => {
+                let (left, right) = query.get_binary_operands(index)?;
 
-                    let a = get_ciphertext(&data, left.index())?;
-                    let b = get_plaintext(&data, right.index())?;
+                let a = get_ciphertext(&data, left.index())?;
+                let b = get_plaintext(&data, right.index())?;
 
-                    let c = evaluator.add_plain(a, b)?;
+                let c = evaluator.add_plain(a, b)?;
 
-                    data[index.index()].store(Some(Arc::new(c.into())));
-                }
-                Multiply => {
-                    let (left, right) = query.get_binary_operands(index)?;
+                data[index.index()].store(Some(Arc::new(c.into())));
+            }
+            Multiply => {
+                let (left, right) = query.get_binary_operands(index)?;
 
-                    let a = get_ciphertext(&data, left.index())?;
-                    let b = get_ciphertext(&data, right.index())?;
+                let a = get_ciphertext(&data, left.index())?;
+                let b = get_ciphertext(&data, right.index())?;
 
-                    let c = evaluator.multiply(a, b)?;
+                let c = evaluator.multiply(a, b)?;
 
-                    data[index.index()].store(Some(Arc::new(c.into())));
-                }
-                MultiplyPlaintext => {
-                    let (left, right) = query.get_binary_operands(index)?;
+                data[index.index()].store(Some(Arc::new(c.into())));
+            }
+            MultiplyPlaintext => {
+                let (left, right) = query.get_binary_operands(index)?;
 
-                    let a = get_ciphertext(&data, left.index())?;
-                    let b = get_plaintext(&data, right.index())?;
+                let a = get_ciphertext(&data, left.index())?;
+                let b = get_plaintext(&data, right.index())?;
 
-                    let c = evaluator.multiply_plain(a, b)?;
+                let c = evaluator.multiply_plain(a, b)?;
 
-                    data[index.index()].store(Some(Arc::new(c.into())));
-                }
-                SwapRows => {
-                    let galois_keys = galois_keys
-                        .as_ref()
-                        .ok_or(FheProgramRunFailure::MissingGaloisKeys)?;
+                data[index.index()].store(Some(Arc::new(c.into())));
+            }
+            SwapRows => {
+                let galois_keys = galois_keys
+                    .as_ref()
+                    .ok_or(FheProgramRunFailure::MissingGaloisKeys)?;
 
-                    let input = query.get_unary_operand(index)?;
+                let input = query.get_unary_operand(index)?;
 
-                    let x = get_ciphertext(&data, input.index())?;
+                let x = get_ciphertext(&data, input.index())?;
 
-                    let y = evaluator.rotate_columns(x, galois_keys)?;
+                let y = evaluator.rotate_columns(x, galois_keys)?;
 
-                    data[index.index()].store(Some(Arc::new(y.into())));
-                }
-                Relinearize => {
-                    let relin_keys = relin_keys
-                        .as_ref()
-                        .ok_or(FheProgramRunFailure::MissingRelinearizationKeys)?;
+                data[index.index()].store(Some(Arc::new(y.into())));
+            }
+            Relinearize => {
+                let relin_keys = relin_keys
+                    .as_ref()
+                    .ok_or(FheProgramRunFailure::MissingRelinearizationKeys)?;
 
-                    let input = query.get_unary_operand(index)?;
+                let input = query.get_unary_operand(index)?;
 
-                    let a = get_ciphertext(&data, input.index())?;
+                let a = get_ciphertext(&data, input.index())?;
 
-                    let c = evaluator.relinearize(a, relin_keys)?;
+                let c = evaluator.relinearize(a, relin_keys)?;
 
-                    data[index.index()].store(Some(Arc::new(c.into())));
-                }
-                Negate => {
-                    let x_id = query.get_unary_operand(index)?;
+                data[index.index()].store(Some(Arc::new(c.into())));
+            }
+            Negate => {
+                let x_id = query.get_unary_operand(index)?;
 
-                    let x = get_ciphertext(&data, x_id.index())?;
+                let x = get_ciphertext(&data, x_id.index())?;
 
-                    let y = evaluator.negate(x)?;
+                let y = evaluator.negate(x)?;
 
-                    data[index.index()].store(Some(Arc::new(y.into())));
-                }
-                Sub => {
-                    let (left, right) = query.get_binary_operands(index)?;
+                data[index.index()].store(Some(Arc::new(y.into())));
+            }
+            Sub => {
+                let (left, right) = query.get_binary_operands(index)?;
 
-                    let a = get_ciphertext(&data, left.index())?;
-                    let b = get_ciphertext(&data, right.index())?;
+                let a = get_ciphertext(&data, left.index())?;
+                let b = get_ciphertext(&data, right.index())?;
 
-                    let c = evaluator.sub(a, b)?;
+                let c = evaluator.sub(a, b)?;
 
-                    data[index.index()].store(Some(Arc::new(c.into())));
-                }
-                SubPlaintext => {
-                    let (left, right) = query.get_binary_operands(index)?;
+                data[index.index()].store(Some(Arc::new(c.into())));
+            }
+            SubPlaintext => {
+                let (left, right) = query.get_binary_operands(index)?;
 
-                    let a = get_ciphertext(&data, left.index())?;
-                    let b = get_plaintext(&data, right.index())?;
+                let a = get_ciphertext(&data, left.index())?;
+                let b = get_plaintext(&data, right.index())?;
 
-                    let c = evaluator.sub_plain(a, b)?;
+                let c = evaluator.sub_plain(a, b)?;
 
-                    data[index.index()].store(Some(Arc::new(c.into())));
-                }
-                Literal(x) => {
-                    if let Literal::Plaintext(p) = x {
-                        let p = InnerPlaintext::from_bytes(p)
-                            .map_err(|_| FheProgramRunFailure::MalformedPlaintext)?;
-
-                        match p {
-                            InnerPlaintext::Seal(p) => {
-                                // Plaintext literals should always have exactly one plaintext.
-                                if p.len() != 1 {
-                                    return Err(FheProgramRunFailure::MalformedPlaintext);
-                                }
-
-                                data[index.index()].store(Some(Arc::new(p[0].data.clone().into())))
+                data[index.index()].store(Some(Arc::new(c.into())));
+            }
+            Literal(x) => {
+                if let Literal::Plaintext(p) = x {
+                    let p = InnerPlaintext::from_bytes(p)
+                        .map_err(|_| FheProgramRunFailure::MalformedPlaintext)?;
+
+                    match p {
+                        InnerPlaintext::Seal(p) => {
+                            // Plaintext literals should always have exactly one plaintext.
+                            if p.len() != 1 {
+                                return Err(FheProgramRunFailure::MalformedPlaintext);
                             }
-                        };
-                    }
+
+                            data[index.index()].store(Some(Arc::new(p[0].data.clone().into())))
+                        }
+                    };
                 }
-                OutputCiphertext => {
-                    let input = query.get_unary_operand(index)?;
+            }
+            OutputCiphertext | OutputPlaintext => {
+                let input = query.get_unary_operand(index)?;
 
-                    let a = get_data(&data, input.index())?;
+                let a = get_data(&data, input.index())?;
 
-                    data[index.index()].store(Some(a.clone()));
-                }
+                data[index.index()].store(Some(a.clone()));
+            }
+        };
+
+        Ok(())
+    };
+
+    let callback = |index: NodeIndex| {
+        if options
+            .cancellation_token
+            .as_ref()
+            .map(|t| t.is_cancelled())
+            .unwrap_or(false)
+        {
+            return Err(FheProgramRunFailure::Cancelled);
+        }
+
+        let start = run_start.map(|_| Instant::now());
+
+        run_node(index)?;
+
+        if let (Some(run_start), Some(start)) = (run_start, start) {
+            let timing = NodeTiming {
+                node: index,
+                operation: ir.graph[index].operation.clone(),
+                start: start.duration_since(run_start),
+                duration: start.elapsed(),
             };
 
-            Ok(())
-        },
-        None,
-    )?;
+            timings.lock().unwrap().push(timing);
+        }
+
+        if let Some(measure) = noise_budget {
+            if let Ok(SealData::Ciphertext(c)) = get_data(&data, index.index()).map(|d| d.as_ref())
+            {
+                let budget = NodeNoiseBudget {
+                    node: index.index(),
+                    operation: ir.graph[index].operation.clone(),
+                    noise_budget: measure(c)?,
+                    predicted_noise_budget: None,
+                };
+
+                noise_budgets.lock().unwrap().push(budget);
+            }
+        }
 
-    // Attempt to copy ciphertexts to our output vector.
+        if let Some(progress) = &options.progress {
+            progress(RunProgress {
+                completed: completed.fetch_add(1, Ordering::Relaxed) + 1,
+                total: ir.graph.node_count(),
+            });
+        }
+
+        Ok(())
+    };
+
+    match &options.thread_pool {
+        Some(pool) => pool.install(|| traverse(ir, callback, None)),
+        None => traverse(ir, callback, None),
+    }?;
+
+    let trace = run_start.map(|run_start| {
+        let mut nodes = timings.into_inner().unwrap();
+        nodes.sort_by_key(|t| t.start);
+
+        RunTrace {
+            nodes,
+            total: run_start.elapsed(),
+        }
+    });
+
+    let noise_report = noise_budget.map(|_| {
+        let mut nodes = noise_budgets.into_inner().unwrap();
+        nodes.sort_by_key(|n| n.node);
+
+        NoiseReport { nodes }
+    });
+
+    // Attempt to copy outputs to our output vector.
     let output = ir
         .graph
         .node_indices()
         .filter_map(|id| match ir.graph[id].operation {
-            OutputCiphertext => Some(get_ciphertext(&data, id.index())),
+            OutputCiphertext => {
+                Some(get_ciphertext(&data, id.index()).map(|c| c.to_owned().into()))
+            }
+            OutputPlaintext => Some(get_plaintext(&data, id.index()).map(|p| p.to_owned().into())),
             _ => None,
         })
-        .collect::<Result<Vec<&Ciphertext>, FheProgramRunFailure>>()?
-        .drain(0..)
-        .map(|c| c.to_owned())
-        .collect();
+        .collect::<Result<Vec<SealData>, FheProgramRunFailure>>()?;
 
-    Ok(output)
+    Ok((output, trace, noise_report))
+}
+
+#[derive(Clone)]
+/**
+ * The result of running an FHE program with
+ * [`run_with_options`](crate::GenericRuntime::run_with_options) or
+ * [`run_async`](crate::GenericRuntime::run_async).
+ */
+pub struct RunOutput {
+    /**
+     * The FHE program's return values.
+     */
+    pub outputs: Vec<crate::FheProgramOutput>,
+
+    /**
+     * The per-node timing trace, if [`RunOptions::trace`] was set.
+     */
+    pub trace: Option<RunTrace>,
+
+    /**
+     * The per-node noise budgets, if run with
+     * [`run_debug`](crate::GenericRuntime::run_debug).
+     */
+    pub noise_report: Option<NoiseReport>,
+}
+
+struct FheRunHandleState {
+    result: Option<std::thread::Result<crate::Result<RunOutput>>>,
+    waker: Option<Waker>,
+}
+
+/**
+ * A handle to an FHE program running in the background, returned by
+ * [`run_async`](crate::GenericRuntime::run_async).
+ *
+ * # Remarks
+ * The handle is a [`Future`] that resolves to the run's result, so it can
+ * be awaited on any executor. Synchronous callers can instead block with
+ * [`wait`](FheRunHandle::wait). Dropping the handle doesn't stop the run;
+ * call [`cancel`](FheRunHandle::cancel) first.
+ *
+ * If the run panics, the panic resumes in the thread that awaits or waits
+ * on the handle.
+ */
+pub struct FheRunHandle {
+    state: Arc<(Mutex<FheRunHandleState>, Condvar)>,
+    cancellation_token: CancellationToken,
+}
+
+impl FheRunHandle {
+    /**
+     * Runs `job` on the given thread pool (or rayon's global pool) and
+     * returns a handle to its result.
+     */
+    pub(crate) fn spawn<F>(
+        thread_pool: Option<&rayon::ThreadPool>,
+        cancellation_token: CancellationToken,
+        job: F,
+    ) -> Self
+    where
+        F: FnOnce() -> crate::Result<RunOutput> + Send + 'static,
+    {
+        let state = Arc::new((
+            Mutex::new(FheRunHandleState {
+                result: None,
+                waker: None,
+            }),
+            Condvar::new(),
+        ));
+
+        let job_state = state.clone();
+
+        let job = move || {
+            let result = catch_unwind(AssertUnwindSafe(job));
+
+            let (lock, cvar) = job_state.as_ref();
+            let mut state = lock.lock().unwrap();
+
+            state.result = Some(result);
+
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+
+            cvar.notify_all();
+        };
+
+        match thread_pool {
+            Some(pool) => pool.spawn(job),
+            None => rayon::spawn(job),
+        };
+
+        Self {
+            state,
+            cancellation_token,
+        }
+    }
+
+    /**
+     * Cancels the run. See [`CancellationToken`].
+     */
+    pub fn cancel(&self) {
+        self.cancellation_token.cancel();
+    }
+
+    /**
+     * The token that cancels this run.
+     */
+    pub fn cancellation_token(&self) -> &CancellationToken {
+        &self.cancellation_token
+    }
+
+    /**
+     * Returns whether the run has finished, successfully or otherwise.
+     */
+    pub fn is_finished(&self) -> bool {
+        self.state.0.lock().unwrap().result.is_some()
+    }
+
+    /**
+     * Blocks the current thread until the run finishes and returns its
+     * result.
+     */
+    pub fn wait(self) -> crate::Result<RunOutput> {
+        let (lock, cvar) = self.state.as_ref();
+
+        let mut state = cvar
+            .wait_while(lock.lock().unwrap(), |s| s.result.is_none())
+            .unwrap();
+
+        match state.result.take().unwrap() {
+            Ok(result) => result,
+            Err(panic) => resume_unwind(panic),
+        }
+    }
+}
+
+impl Future for FheRunHandle {
+    type Output = crate::Result<RunOutput>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.0.lock().unwrap();
+
+        match state.result.take() {
+            Some(Ok(result)) => Poll::Ready(result),
+            Some(Err(panic)) => resume_unwind(panic),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -879,4 +1500,124 @@ mod tests {
 
         assert_eq!(encoder.decode_unsigned(&o_p).unwrap(), expected);
     }
+
+    fn add_chain(len: usize) -> FheProgram {
+        let mut ir = FheProgram::new(SchemeType::Bfv);
+
+        let a = ir.add_input_ciphertext(0);
+        let mut acc = ir.add_input_ciphertext(1);
+
+        for _ in 0..len {
+            acc = ir.add_add(acc, a);
+        }
+
+        ir.add_output_ciphertext(acc);
+
+        ir
+    }
+
+    #[test]
+    fn can_run_with_progress_and_trace() {
+        let ir = add_chain(8);
+
+        let degree = 4096;
+
+        let (_keygen, context, _public_key, _private_key, encryptor, decryptor, evaluator) =
+            setup_scheme(degree);
+
+        let encoder = BFVEncoder::new(&context).unwrap();
+
+        let pt_0 = encoder.encode_signed(&vec![3; degree as usize]).unwrap();
+        let pt_1 = encoder.encode_signed(&vec![1; degree as usize]).unwrap();
+
+        let ct_0 = encryptor.encrypt(&pt_0).unwrap();
+        let ct_1 = encryptor.encrypt(&pt_1).unwrap();
+
+        let progress = Arc::new(Mutex::new(vec![]));
+        let progress_clone = progress.clone();
+
+        let pool = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(2)
+                .build()
+                .unwrap(),
+        );
+
+        let options = RunOptions::new()
+            .thread_pool(pool)
+            .progress(move |p| progress_clone.lock().unwrap().push(p))
+            .trace(true);
+
+        let (output, trace) = unsafe {
+            run_program_unchecked_with_options(
+                &ir,
+                &[ct_0.into(), ct_1.into()],
+                &evaluator,
+                &None,
+                &None,
+                &options,
+            )
+            .unwrap()
+        };
+
+        let o_p = decryptor.decrypt(&output[0]).unwrap();
+
+        assert_eq!(
+            encoder.decode_signed(&o_p).unwrap(),
+            vec![1 + 8 * 3; degree as usize]
+        );
+
+        let node_count = ir.graph.node_count();
+
+        let mut progress = progress.lock().unwrap().clone();
+        progress.sort_by_key(|p| p.completed);
+
+        assert_eq!(progress.len(), node_count);
+        assert!(progress
+            .iter()
+            .enumerate()
+            .all(|(i, p)| p.completed == i + 1 && p.total == node_count));
+
+        let trace = trace.unwrap();
+
+        assert_eq!(trace.nodes.len(), node_count);
+        assert!(trace.nodes.windows(2).all(|w| w[0].start <= w[1].start));
+        assert!(trace
+            .nodes
+            .iter()
+            .all(|t| t.start + t.duration <= trace.total));
+    }
+
+    #[test]
+    fn cancelled_run_fails() {
+        let ir = add_chain(8);
+
+        let degree = 4096;
+
+        let (_keygen, context, _public_key, _private_key, encryptor, _decryptor, evaluator) =
+            setup_scheme(degree);
+
+        let encoder = BFVEncoder::new(&context).unwrap();
+
+        let pt = encoder.encode_signed(&vec![3; degree as usize]).unwrap();
+        let ct = encryptor.encrypt(&pt).unwrap();
+
+        let token = CancellationToken::new();
+        let options = RunOptions::new().cancellation_token(token.clone());
+
+        token.cancel();
+
+        let result = unsafe {
+            run_program_unchecked_with_options(
+                &ir,
+                &[ct.clone().into(), ct.into()],
+                &evaluator,
+                &None,
+                &None,
+                &options,
+            )
+        };
+
+        assert!(matches!(result, Err(FheProgramRunFailure::Cancelled)));
+    }
 }
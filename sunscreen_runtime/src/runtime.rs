@@ -9,18 +9,20 @@ use crate::ProofBuilder;
 use crate::VerificationBuilder;
 use crate::ZkpProgramInput;
 use crate::{
-    run_program_unchecked, serialization::WithContext, Ciphertext, FheProgramInput,
-    InnerCiphertext, InnerPlaintext, Plaintext, PrivateKey, PublicKey, SealCiphertext, SealData,
-    SealPlaintext, TryFromPlaintext, TryIntoPlaintext, TypeNameInstance,
+    run_program_with_noise_budget, serialization::WithContext, CancellationToken, Ciphertext,
+    FheProgramInput, FheProgramOutput, FheRunHandle, InnerCiphertext, InnerPlaintext,
+    KeySwitchingKey, Plaintext, PrivateKey, PublicKey, RunOptions, RunOutput, SealCiphertext,
+    SealData, SealPlaintext, TryFromPlaintext, TryIntoPlaintext, TypeNameInstance,
 };
 
 use log::trace;
+use rayon::prelude::*;
 use sunscreen_fhe_program::FheProgramTrait;
-use sunscreen_fhe_program::SchemeType;
+use sunscreen_fhe_program::{Operation, SchemeType};
 
 use seal_fhe::{
     AsymmetricComponents, BFVEvaluator, BfvEncryptionParametersBuilder, Context as SealContext,
-    Decryptor, Encryptor, KeyGenerator, Modulus, SymmetricComponents,
+    Decryptor, Encryptor, Evaluator, KeyGenerator, Modulus, SymmetricComponents,
 };
 
 pub use sunscreen_compiler_common::{Type, TypeName};
@@ -135,6 +137,107 @@ impl<T, B> GenericRuntime<T, B> {
     }
 }
 
+/**
+ * Groups the raw ciphertexts and plaintexts an FHE program returned into its
+ * return values.
+ */
+fn pack_outputs(
+    params: &Params,
+    signature: &CallSignature,
+    raw_outputs: Vec<SealData>,
+) -> Result<Vec<FheProgramOutput>> {
+    if signature.returns.len() != signature.num_ciphertexts.len()
+        || signature.num_ciphertexts.iter().sum::<usize>() != raw_outputs.len()
+    {
+        return Err(Error::ReturnTypeMetadataError);
+    }
+
+    let mut raw_outputs = raw_outputs.into_iter();
+    let mut packed_outputs = vec![];
+
+    for (data_type, count) in signature.returns.iter().zip(&signature.num_ciphertexts) {
+        let raw_outputs = raw_outputs.by_ref().take(*count);
+
+        let output = if data_type.is_encrypted {
+            let ciphertexts = raw_outputs
+                .map(|x| match x {
+                    SealData::Ciphertext(c) => Ok(WithContext {
+                        params: params.clone(),
+                        data: c,
+                    }),
+                    SealData::Plaintext(_) => Err(Error::ReturnTypeMetadataError),
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            FheProgramOutput::Ciphertext(Ciphertext {
+                data_type: data_type.clone(),
+                inner: InnerCiphertext::Seal(ciphertexts),
+            })
+        } else {
+            let plaintexts = raw_outputs
+                .map(|x| match x {
+                    SealData::Ciphertext(_) => Err(Error::ReturnTypeMetadataError),
+                    SealData::Plaintext(p) => Ok(WithContext {
+                        params: params.clone(),
+                        data: p,
+                    }),
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            FheProgramOutput::Plaintext(Plaintext {
+                data_type: data_type.clone(),
+                inner: InnerPlaintext::Seal(plaintexts),
+            })
+        };
+
+        packed_outputs.push(output);
+    }
+
+    Ok(packed_outputs)
+}
+
+/**
+ * Unwraps the return values of an FHE program with no plaintext outputs.
+ */
+fn unwrap_ciphertexts(outputs: Vec<FheProgramOutput>) -> Result<Vec<Ciphertext>> {
+    outputs
+        .into_iter()
+        .map(|x| match x {
+            FheProgramOutput::Ciphertext(c) => Ok(c),
+            FheProgramOutput::Plaintext(_) => Err(Error::PlaintextOutputs),
+        })
+        .collect()
+}
+
+/**
+ * Creates the SEAL context for the given parameters.
+ */
+pub(crate) fn make_seal_context(params: &Params) -> Result<SealContext> {
+    match params.scheme_type {
+        SchemeType::Bfv => {
+            let bfv_params = BfvEncryptionParametersBuilder::new()
+                .set_plain_modulus_u64(params.plain_modulus)
+                .set_poly_modulus_degree(params.lattice_dimension)
+                .set_coefficient_modulus(
+                    params
+                        .coeff_modulus
+                        .iter()
+                        .map(|v| Modulus::new(*v).unwrap())
+                        .collect::<Vec<Modulus>>(),
+                )
+                .build()?;
+
+            #[cfg(feature = "insecure-params")]
+            let context = SealContext::new_insecure(&bfv_params, true)?;
+
+            #[cfg(not(feature = "insecure-params"))]
+            let context = SealContext::new(&bfv_params, true, params.security_level)?;
+
+            Ok(context)
+        }
+    }
+}
+
 impl<T, B> GenericRuntime<T, B>
 where
     T: self::marker::Fhe,
@@ -151,6 +254,25 @@ where
         P::try_from_plaintext(&pt, &fhe_data.params)
     }
 
+    /**
+     * Decodes the given plaintext, e.g. an unencrypted return value of an FHE program, into
+     * the type P.
+     */
+    pub fn decode<P>(&self, plaintext: &Plaintext) -> Result<P>
+    where
+        P: TryFromPlaintext + TypeName,
+    {
+        let expected_type = P::type_name();
+
+        if expected_type != plaintext.data_type {
+            return Err(Error::type_mismatch(&expected_type, &plaintext.data_type));
+        }
+
+        let fhe_data = self.runtime_data.unwrap_fhe();
+
+        P::try_from_plaintext(plaintext, &fhe_data.params)
+    }
+
     /**
      * Decrypts the given ciphertext into the type P, mapping over the inner seal decryptions.
      */
@@ -284,6 +406,78 @@ where
         Ok(keys)
     }
 
+    /**
+     * Generates a [`KeySwitchingKey`] for switching ciphertexts encrypted under `old_key` to
+     * `new_key` with [`switch_key`](Self::switch_key).
+     *
+     * # Remarks
+     * Both keys must have been generated under this runtime's parameters.
+     */
+    pub fn generate_key_switching_key(
+        &self,
+        old_key: &PrivateKey,
+        new_key: &PrivateKey,
+    ) -> Result<KeySwitchingKey> {
+        let fhe_data = self.runtime_data.unwrap_fhe();
+
+        if old_key.0.params != fhe_data.params || new_key.0.params != fhe_data.params {
+            return Err(Error::ParameterMismatch);
+        }
+
+        match &fhe_data.context {
+            Context::Seal(context) => {
+                let keygen = KeyGenerator::new_from_secret_key(context, &new_key.0)?;
+
+                Ok(KeySwitchingKey(WithContext {
+                    params: fhe_data.params.clone(),
+                    data: keygen.create_key_switching_keys(context, &old_key.0)?,
+                }))
+            }
+        }
+    }
+
+    /**
+     * Switches the given ciphertext to the private key `key` was generated for, without
+     * decrypting it. This allows rotating private keys without the party storing
+     * the ciphertexts ever seeing the data.
+     *
+     * # Remarks
+     * Key switching consumes a small amount of noise budget, comparable to a
+     * relinearization.
+     */
+    pub fn switch_key(&self, ciphertext: &Ciphertext, key: &KeySwitchingKey) -> Result<Ciphertext> {
+        let fhe_data = self.runtime_data.unwrap_fhe();
+
+        if key.0.params != fhe_data.params {
+            return Err(Error::ParameterMismatch);
+        }
+
+        match (&fhe_data.context, &ciphertext.inner) {
+            (Context::Seal(context), InnerCiphertext::Seal(ciphertexts)) => {
+                let evaluator = BFVEvaluator::new(context)?;
+
+                let ciphertexts = ciphertexts
+                    .iter()
+                    .map(|c| {
+                        if c.params != fhe_data.params {
+                            return Err(Error::ParameterMismatch);
+                        }
+
+                        Ok(WithContext {
+                            params: fhe_data.params.clone(),
+                            data: evaluator.switch_key(&c.data, &key.0.data)?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                Ok(Ciphertext {
+                    data_type: ciphertext.data_type.clone(),
+                    inner: InnerCiphertext::Seal(ciphertexts),
+                })
+            }
+        }
+    }
+
     /**
      * Returns the metadata for this runtime's associated FHE program.
      */
@@ -296,7 +490,6 @@ where
     /**
      * Returns the underlying SEAL context.
      */
-    #[allow(unused)]
     pub(crate) fn context(&self) -> &SealContext {
         match &self.runtime_data.unwrap_fhe().context {
             Context::Seal(seal_ctx) => seal_ctx,
@@ -305,17 +498,385 @@ where
 
     /**
      * Validates and runs the given FHE program. Unless you can guarantee your FHE program is valid,
-     * you should use this method rather than [`run_program_unchecked`].
+     * you should use this method rather than [`run_program_unchecked`](crate::run_program_unchecked).
+     *
+     * # Remarks
+     * Returns [`Error::PlaintextOutputs`] if the FHE program returns any unencrypted values.
+     * Run such programs with [`run_with_options`](Self::run_with_options).
      */
     pub fn run<I>(
         &self,
         fhe_program: &CompiledFheProgram,
-        mut arguments: Vec<I>,
+        arguments: Vec<I>,
         public_key: &PublicKey,
     ) -> Result<Vec<Ciphertext>>
     where
         I: Into<FheProgramInput>,
     {
+        Self::validate_ciphertext_outputs(fhe_program)?;
+
+        let output =
+            self.run_with_options(fhe_program, arguments, public_key, &RunOptions::default())?;
+
+        unwrap_ciphertexts(output.outputs)
+    }
+
+    /**
+     * Validates and runs the given FHE program according to the given [`RunOptions`]. These
+     * allow running on a specific thread pool, cancelling the run, reporting progress and
+     * recording a per-node timing trace.
+     *
+     * # Remarks
+     * Unlike [`run`](Self::run), this supports FHE programs that return unencrypted values.
+     * These are returned as [`FheProgramOutput::Plaintext`], which you can
+     * [`decode`](Self::decode).
+     */
+    pub fn run_with_options<I>(
+        &self,
+        fhe_program: &CompiledFheProgram,
+        arguments: Vec<I>,
+        public_key: &PublicKey,
+        options: &RunOptions,
+    ) -> Result<RunOutput>
+    where
+        I: Into<FheProgramInput>,
+    {
+        let inputs = self.prepare_run(fhe_program, arguments, public_key, options)?;
+        let fhe_data = self.runtime_data.unwrap_fhe();
+
+        match &fhe_data.context {
+            Context::Seal(context) => {
+                let evaluator = BFVEvaluator::new(context)?;
+
+                let relin_key = public_key.relin_key.as_ref().map(|p| &p.data);
+                let galois_key = public_key.galois_key.as_ref().map(|p| &p.data);
+
+                let (raw_outputs, trace, _) = unsafe {
+                    run_program_with_noise_budget(
+                        &fhe_program.fhe_program_fn,
+                        &inputs,
+                        &evaluator,
+                        &relin_key,
+                        &galois_key,
+                        options,
+                        None,
+                    )
+                }?;
+
+                Ok(RunOutput {
+                    outputs: pack_outputs(
+                        &fhe_data.params,
+                        &fhe_program.metadata.signature,
+                        raw_outputs,
+                    )?,
+                    trace,
+                    noise_report: None,
+                })
+            }
+        }
+    }
+
+    /**
+     * The same as [`run_with_options`](Self::run_with_options), but also decrypts every
+     * intermediate ciphertext with `private_key` to measure its noise budget. The returned
+     * [`RunOutput`] contains a [`NoiseReport`](crate::NoiseReport) of the budget remaining after each node.
+     *
+     * # Remarks
+     * This is for debugging where an FHE program loses noise budget and comparing against
+     * the noise models' predictions; it requires the private key and makes runs considerably
+     * slower. Don't use it in production.
+     */
+    pub fn run_debug<I>(
+        &self,
+        fhe_program: &CompiledFheProgram,
+        arguments: Vec<I>,
+        public_key: &PublicKey,
+        private_key: &PrivateKey,
+        options: &RunOptions,
+    ) -> Result<RunOutput>
+    where
+        I: Into<FheProgramInput>,
+    {
+        let fhe_data = self.runtime_data.unwrap_fhe();
+
+        if private_key.0.params != fhe_data.params {
+            return Err(Error::ParameterMismatch);
+        }
+
+        let inputs = self.prepare_run(fhe_program, arguments, public_key, options)?;
+
+        match &fhe_data.context {
+            Context::Seal(context) => {
+                let evaluator = BFVEvaluator::new(context)?;
+                let decryptor = Decryptor::new(context, &private_key.0)?;
+
+                let relin_key = public_key.relin_key.as_ref().map(|p| &p.data);
+                let galois_key = public_key.galois_key.as_ref().map(|p| &p.data);
+
+                let measure = |c: &SealCiphertext| decryptor.invariant_noise_budget(c);
+
+                let (raw_outputs, trace, noise_report) = unsafe {
+                    run_program_with_noise_budget(
+                        &fhe_program.fhe_program_fn,
+                        &inputs,
+                        &evaluator,
+                        &relin_key,
+                        &galois_key,
+                        options,
+                        Some(&measure),
+                    )
+                }?;
+
+                Ok(RunOutput {
+                    outputs: pack_outputs(
+                        &fhe_data.params,
+                        &fhe_program.metadata.signature,
+                        raw_outputs,
+                    )?,
+                    trace,
+                    noise_report,
+                })
+            }
+        }
+    }
+
+    /**
+     * Runs the given FHE program once for each set of arguments in `arguments`. This validates
+     * the program and creates an evaluator once, then runs all the instances in parallel.
+     *
+     * # Remarks
+     * Returns an error if the FHE program itself can't run (e.g. it's malformed,
+     * `public_key` lacks keys it needs or, as with [`run`](Self::run), it returns unencrypted
+     * values). Otherwise, returns one result per argument set in
+     * the same order, so an instance with bad arguments doesn't fail the others.
+     *
+     * Every instance runs with the given `options`. In strict mode, each argument set is
+     * validated separately and fails only its own instance.
+     */
+    pub fn run_batch<I>(
+        &self,
+        fhe_program: &CompiledFheProgram,
+        arguments: Vec<Vec<I>>,
+        public_key: &PublicKey,
+        options: &RunOptions,
+    ) -> Result<Vec<Result<Vec<Ciphertext>>>>
+    where
+        I: Into<FheProgramInput>,
+    {
+        Self::validate_program(fhe_program, public_key)?;
+        Self::validate_ciphertext_outputs(fhe_program)?;
+
+        let fhe_data = self.runtime_data.unwrap_fhe();
+
+        let inputs = arguments
+            .into_iter()
+            .map(|a| self.prepare_arguments(fhe_program, a, options))
+            .collect::<Vec<_>>();
+
+        match &fhe_data.context {
+            Context::Seal(context) => {
+                let evaluator = BFVEvaluator::new(context)?;
+
+                let relin_key = public_key.relin_key.as_ref().map(|p| &p.data);
+                let galois_key = public_key.galois_key.as_ref().map(|p| &p.data);
+
+                let run_all = || {
+                    inputs
+                        .into_par_iter()
+                        .map(|inputs| {
+                            let inputs = inputs?;
+
+                            let (raw_outputs, _, _) = unsafe {
+                                run_program_with_noise_budget(
+                                    &fhe_program.fhe_program_fn,
+                                    &inputs,
+                                    &evaluator,
+                                    &relin_key,
+                                    &galois_key,
+                                    options,
+                                    None,
+                                )
+                            }?;
+
+                            unwrap_ciphertexts(pack_outputs(
+                                &fhe_data.params,
+                                &fhe_program.metadata.signature,
+                                raw_outputs,
+                            )?)
+                        })
+                        .collect::<Vec<_>>()
+                };
+
+                // Fan the instances out on the requested pool too, not just
+                // each instance's nodes.
+                let results = match options.get_thread_pool() {
+                    Some(pool) => pool.install(run_all),
+                    None => run_all(),
+                };
+
+                Ok(results)
+            }
+        }
+    }
+
+    /**
+     * Validates the given FHE program and runs it in the background on the thread pool given
+     * in `options` (or rayon's global pool). Returns an [`FheRunHandle`] that can be awaited,
+     * waited on, or cancelled.
+     *
+     * # Remarks
+     * Validation errors are returned immediately. Errors that occur while running are
+     * returned through the handle.
+     *
+     * Since the run may outlive the borrowed arguments, this method copies the FHE program
+     * and the relinearization and Galois keys it needs.
+     *
+     * If `options` has no [`CancellationToken`], the run gets a new one, accessible through
+     * the handle.
+     */
+    pub fn run_async<I>(
+        &self,
+        fhe_program: &CompiledFheProgram,
+        arguments: Vec<I>,
+        public_key: &PublicKey,
+        options: RunOptions,
+    ) -> Result<FheRunHandle>
+    where
+        I: Into<FheProgramInput>,
+    {
+        let inputs = self.prepare_run(fhe_program, arguments, public_key, &options)?;
+        let fhe_data = self.runtime_data.unwrap_fhe();
+
+        let options = match options.get_cancellation_token() {
+            Some(_) => options,
+            None => options.cancellation_token(CancellationToken::new()),
+        };
+
+        let cancellation_token = options.get_cancellation_token().unwrap().clone();
+
+        match &fhe_data.context {
+            Context::Seal(context) => {
+                let evaluator = BFVEvaluator::new(context)?;
+
+                let relin_key = public_key.relin_key.as_ref().map(|p| p.data.clone());
+                let galois_key = public_key.galois_key.as_ref().map(|p| p.data.clone());
+
+                let program = fhe_program.fhe_program_fn.clone();
+                let signature = fhe_program.metadata.signature.clone();
+                let params = fhe_data.params.clone();
+                let thread_pool = options.get_thread_pool().cloned();
+
+                let job = move || {
+                    let (raw_outputs, trace, _) = unsafe {
+                        run_program_with_noise_budget(
+                            &program,
+                            &inputs,
+                            &evaluator,
+                            &relin_key.as_ref(),
+                            &galois_key.as_ref(),
+                            &options,
+                            None,
+                        )
+                    }?;
+
+                    Ok(RunOutput {
+                        outputs: pack_outputs(&params, &signature, raw_outputs)?,
+                        trace,
+                        noise_report: None,
+                    })
+                };
+
+                Ok(FheRunHandle::spawn(
+                    thread_pool.as_deref(),
+                    cancellation_token,
+                    job,
+                ))
+            }
+        }
+    }
+
+    /**
+     * Validates the FHE program, keys and arguments for a run and unpacks the arguments into
+     * the SEAL values the program consumes.
+     */
+    fn prepare_run<I>(
+        &self,
+        fhe_program: &CompiledFheProgram,
+        arguments: Vec<I>,
+        public_key: &PublicKey,
+        options: &RunOptions,
+    ) -> Result<Vec<SealData>>
+    where
+        I: Into<FheProgramInput>,
+    {
+        Self::validate_program(fhe_program, public_key)?;
+
+        self.prepare_arguments(fhe_program, arguments, options)
+    }
+
+    /**
+     * Unpacks the arguments for a run into the SEAL values the FHE program consumes,
+     * validating them first in strict mode.
+     */
+    fn prepare_arguments<I>(
+        &self,
+        fhe_program: &CompiledFheProgram,
+        arguments: Vec<I>,
+        options: &RunOptions,
+    ) -> Result<Vec<SealData>>
+    where
+        I: Into<FheProgramInput>,
+    {
+        let arguments: Vec<FheProgramInput> = arguments.into_iter().map(|a| a.into()).collect();
+
+        if options.get_strict() {
+            let fhe_data = self.runtime_data.unwrap_fhe();
+
+            for a in &arguments {
+                if let FheProgramInput::Ciphertext(c) = a {
+                    c.validate_with_context(&fhe_data.params, self.context())?;
+                }
+            }
+        }
+
+        let inputs = self.unpack_arguments(fhe_program, arguments)?;
+
+        if options.get_strict() {
+            Self::validate_inputs(fhe_program, &inputs)?;
+        }
+
+        Ok(inputs)
+    }
+
+    /**
+     * Checks every input node in the FHE program refers to an unpacked argument of the right
+     * kind. Ciphertext arguments with too few inner ciphertexts would otherwise cause the
+     * program to read past the end of its inputs.
+     */
+    fn validate_inputs(fhe_program: &CompiledFheProgram, inputs: &[SealData]) -> Result<()> {
+        for node in fhe_program.fhe_program_fn.graph.node_weights() {
+            let valid = match node.operation {
+                Operation::InputCiphertext(id) => {
+                    matches!(inputs.get(id), Some(SealData::Ciphertext(_)))
+                }
+                Operation::InputPlaintext(id) => {
+                    matches!(inputs.get(id), Some(SealData::Plaintext(_)))
+                }
+                _ => true,
+            };
+
+            if !valid {
+                return Err(Error::IncorrectCiphertextCount);
+            }
+        }
+
+        Ok(())
+    }
+
+    /**
+     * Validates the FHE program and checks the given keys suffice to run it.
+     */
+    fn validate_program(fhe_program: &CompiledFheProgram, public_key: &PublicKey) -> Result<()> {
         // We're going to call run_program_unchecked, which
         // can result in undefined behavior, non-termination,
         // or panics on malformed programs. Since this method is safe,
@@ -332,87 +893,78 @@ where
             return Err(Error::MissingGaloisKeys);
         }
 
-        let mut arguments: Vec<FheProgramInput> = arguments.drain(0..).map(|a| a.into()).collect();
-
-        // Check the passed arguments' types match the signature.
-        Self::validate_arguments(&fhe_program.metadata.signature, &arguments)?;
-
         if fhe_program.metadata.signature.num_ciphertexts.len()
             != fhe_program.metadata.signature.returns.len()
         {
             return Err(Error::ReturnTypeMetadataError);
         }
 
-        let fhe_data = self.runtime_data.unwrap_fhe();
+        Ok(())
+    }
 
-        match &fhe_data.context {
-            Context::Seal(context) => {
-                let evaluator = BFVEvaluator::new(context)?;
+    /**
+     * Checks the FHE program returns only encrypted values, as [`run`](Self::run) and
+     * [`run_batch`](Self::run_batch) require.
+     */
+    fn validate_ciphertext_outputs(fhe_program: &CompiledFheProgram) -> Result<()> {
+        if fhe_program
+            .metadata
+            .signature
+            .returns
+            .iter()
+            .any(|t| !t.is_encrypted)
+        {
+            return Err(Error::PlaintextOutputs);
+        }
 
-                let mut inputs: Vec<SealData> = vec![];
+        Ok(())
+    }
 
-                for i in arguments.drain(0..) {
-                    match i {
-                        FheProgramInput::Ciphertext(c) => match c.inner {
-                            InnerCiphertext::Seal(mut c) => {
-                                for j in c.drain(0..) {
-                                    inputs.push(SealData::Ciphertext(j.data));
-                                }
-                            }
-                        },
-                        FheProgramInput::Plaintext(p) => {
-                            let p = p.try_into_plaintext(&fhe_data.params)?;
-
-                            match p.inner {
-                                InnerPlaintext::Seal(mut p) => {
-                                    for j in p.drain(0..) {
-                                        inputs.push(SealData::Plaintext(j.data));
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+    /**
+     * Checks the arguments match the FHE program's signature and unpacks them into the SEAL
+     * values the program consumes.
+     */
+    fn unpack_arguments<I>(
+        &self,
+        fhe_program: &CompiledFheProgram,
+        mut arguments: Vec<I>,
+    ) -> Result<Vec<SealData>>
+    where
+        I: Into<FheProgramInput>,
+    {
+        let mut arguments: Vec<FheProgramInput> = arguments.drain(0..).map(|a| a.into()).collect();
 
-                let relin_key = public_key.relin_key.as_ref().map(|p| &p.data);
-                let galois_key = public_key.galois_key.as_ref().map(|p| &p.data);
+        // Check the passed arguments' types match the signature.
+        Self::validate_arguments(&fhe_program.metadata.signature, &arguments)?;
 
-                let mut raw_ciphertexts = unsafe {
-                    run_program_unchecked(
-                        &fhe_program.fhe_program_fn,
-                        &inputs,
-                        &evaluator,
-                        &relin_key,
-                        &galois_key,
-                    )
-                }?;
+        let fhe_data = self.runtime_data.unwrap_fhe();
 
-                let mut packed_ciphertexts = vec![];
+        let mut inputs: Vec<SealData> = vec![];
 
-                for (i, ciphertext_count) in fhe_program
-                    .metadata
-                    .signature
-                    .num_ciphertexts
-                    .iter()
-                    .enumerate()
-                {
-                    packed_ciphertexts.push(Ciphertext {
-                        data_type: fhe_program.metadata.signature.returns[i].clone(),
-                        inner: InnerCiphertext::Seal(
-                            raw_ciphertexts
-                                .drain(0..*ciphertext_count)
-                                .map(|c| WithContext {
-                                    params: fhe_data.params.clone(),
-                                    data: c,
-                                })
-                                .collect(),
-                        ),
-                    });
+        for i in arguments.drain(0..) {
+            match i {
+                FheProgramInput::Ciphertext(c) => match c.inner {
+                    InnerCiphertext::Seal(mut c) => {
+                        for j in c.drain(0..) {
+                            inputs.push(SealData::Ciphertext(j.data));
+                        }
+                    }
+                },
+                FheProgramInput::Plaintext(p) => {
+                    let p = p.try_into_plaintext(&fhe_data.params)?;
+
+                    match p.inner {
+                        InnerPlaintext::Seal(mut p) => {
+                            for j in p.drain(0..) {
+                                inputs.push(SealData::Plaintext(j.data));
+                            }
+                        }
+                    }
                 }
-
-                Ok(packed_ciphertexts)
             }
         }
+
+        Ok(inputs)
     }
 
     /**
@@ -839,32 +1391,10 @@ impl GenericRuntime<(), ()> {
     }
 
     fn make_fhe_runtime_data(params: &Params) -> Result<FheRuntimeData> {
-        match params.scheme_type {
-            SchemeType::Bfv => {
-                let bfv_params = BfvEncryptionParametersBuilder::new()
-                    .set_plain_modulus_u64(params.plain_modulus)
-                    .set_poly_modulus_degree(params.lattice_dimension)
-                    .set_coefficient_modulus(
-                        params
-                            .coeff_modulus
-                            .iter()
-                            .map(|v| Modulus::new(*v).unwrap())
-                            .collect::<Vec<Modulus>>(),
-                    )
-                    .build()?;
-
-                #[cfg(feature = "insecure-params")]
-                let context = SealContext::new_insecure(&bfv_params, true)?;
-
-                #[cfg(not(feature = "insecure-params"))]
-                let context = SealContext::new(&bfv_params, true, params.security_level)?;
-
-                Ok(FheRuntimeData {
-                    params: params.clone(),
-                    context: Context::Seal(context),
-                })
-            }
-        }
+        Ok(FheRuntimeData {
+            params: params.clone(),
+            context: Context::Seal(make_seal_context(params)?),
+        })
     }
 
     fn make_zkp_runtime_data() -> ZkpRuntimeData {
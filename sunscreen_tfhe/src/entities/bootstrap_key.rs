@@ -5,9 +5,11 @@ use crate::{
     dst::{NoWrapper, OverlaySize},
     entities::{
         GgswCiphertextFftIterator, GgswCiphertextFftIteratorMut, GgswCiphertextFftRef,
-        GgswCiphertextIterator, GgswCiphertextIteratorMut, GgswCiphertextRef,
+        GgswCiphertextIterator, GgswCiphertextIteratorMut, GgswCiphertextNttIterator,
+        GgswCiphertextNttIteratorMut, GgswCiphertextNttRef, GgswCiphertextRef,
         ParallelGgswCiphertextIterator, ParallelGgswCiphertextIteratorMut,
     },
+    math::goldilocks_field::Fg,
     GlweDef, GlweDimension, LweDef, LweDimension, RadixCount, RadixDecomposition, Torus, TorusOps,
 };
 
@@ -37,7 +39,9 @@ impl<S: TorusOps> BootstrapKey<S> {
     /// encrypts a single bit of an LWE secret key. This representation cannot
     /// be directly with the bootstrapping functions, but the FFT version of the
     /// bootstrapping key that can be used with the bootstrapping functions can
-    /// be created by calling the [BootstrapKeyRef::fft] method
+    /// be created by calling the [BootstrapKeyRef::fft] method. Alternatively,
+    /// [BootstrapKeyRef::ntt] creates a [BootstrapKeyNtt], which bootstraps
+    /// with exact products at a higher computational cost.
     pub fn new(lwe_params: &LweDef, glwe_params: &GlweDef, radix: &RadixDecomposition) -> Self {
         let len = BootstrapKeyRef::<S>::size((lwe_params.dim, glwe_params.dim, radix.count));
 
@@ -104,6 +108,22 @@ impl<S: TorusOps> BootstrapKeyRef<S> {
         }
     }
 
+    /// Perform an NTT on the [BootstrapKey] to obtain a [BootstrapKeyNtt].
+    pub fn ntt(
+        &self,
+        result: &mut BootstrapKeyNttRef<Fg>,
+        lwe: &LweDef,
+        glwe: &GlweDef,
+        radix: &RadixDecomposition,
+    ) {
+        self.assert_valid(lwe, glwe, radix);
+        result.assert_valid(lwe, glwe, radix);
+
+        for (s, r) in self.rows(glwe, radix).zip(result.rows_mut(glwe, radix)) {
+            s.ntt(r, glwe, radix);
+        }
+    }
+
     #[inline(always)]
     /// Asserts that this entity is valid under the passed parameters.
     pub fn assert_valid(&self, lwe: &LweDef, glwe: &GlweDef, radix: &RadixDecomposition) {
@@ -194,3 +214,85 @@ impl BootstrapKeyFftRef<Complex<f64>> {
         );
     }
 }
+
+dst! {
+    /// Keys used for bootstrapping with exact arithmetic in the Goldilocks
+    /// field. Used by the bootstrapping functions such as
+    /// [`programmable_bootstrap_univariate_ntt`](crate::ops::bootstrapping::programmable_bootstrap_univariate_ntt).
+    /// The non-NTT variant of this type is [BootstrapKey].
+    ///
+    /// # Remarks
+    /// Unlike [BootstrapKeyFft], bootstrapping with this key introduces no
+    /// floating-point rounding error, which matters for 64-bit tori with
+    /// large decomposition bases. However, each GGSW ciphertext is 8x larger
+    /// and blind rotation is slower.
+    BootstrapKeyNtt,
+    BootstrapKeyNttRef,
+    NoWrapper,
+    (Clone, Debug, Serialize, Deserialize),
+    ()
+}
+
+impl OverlaySize for BootstrapKeyNttRef<Fg> {
+    type Inputs = (LweDimension, GlweDimension, RadixCount);
+
+    fn size(t: Self::Inputs) -> usize {
+        GgswCiphertextNttRef::<Fg>::size((t.1, t.2)) * t.0 .0
+    }
+}
+
+impl BootstrapKeyNtt<Fg> {
+    /// Create a new zero [BootstrapKeyNtt] with the given parameters.
+    pub fn new(lwe_params: &LweDef, glwe_params: &GlweDef, radix: &RadixDecomposition) -> Self {
+        let len = BootstrapKeyNttRef::size((lwe_params.dim, glwe_params.dim, radix.count));
+
+        Self {
+            data: avec![Fg::zero(); len],
+        }
+    }
+}
+
+impl BootstrapKeyNttRef<Fg> {
+    /// Iterate over the rows of the [BootstrapKeyNtt].
+    pub fn rows(
+        &self,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> GgswCiphertextNttIterator<Fg> {
+        let stride = GgswCiphertextNttRef::<Fg>::size((params.dim, radix.count));
+
+        GgswCiphertextNttIterator::new(self.as_slice(), stride)
+    }
+
+    /// Iterate over the rows of the [BootstrapKeyNtt] mutably.
+    pub fn rows_mut(
+        &mut self,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> GgswCiphertextNttIteratorMut<Fg> {
+        let stride = GgswCiphertextNttRef::<Fg>::size((params.dim, radix.count));
+
+        GgswCiphertextNttIteratorMut::new(self.as_mut_slice(), stride)
+    }
+
+    /// Perform an inverse NTT on the [BootstrapKeyNtt] to obtain a [BootstrapKey].
+    pub fn intt<S: TorusOps>(
+        &self,
+        result: &mut BootstrapKeyRef<S>,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+    ) {
+        for (s, r) in self.rows(params, radix).zip(result.rows_mut(params, radix)) {
+            s.intt(r, params, radix);
+        }
+    }
+
+    /// Asserts that the [BootstrapKeyNtt] is valid for the given parameters.
+    #[inline(always)]
+    pub fn assert_valid(&self, lwe: &LweDef, glwe: &GlweDef, radix: &RadixDecomposition) {
+        assert_eq!(
+            self.as_slice().len(),
+            BootstrapKeyNttRef::size((lwe.dim, glwe.dim, radix.count))
+        );
+    }
+}
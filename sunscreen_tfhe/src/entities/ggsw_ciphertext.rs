@@ -2,13 +2,14 @@ use num::{Complex, Zero};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    dst::OverlaySize, ops::ciphertext::external_product_ggsw_glwe, GlweDef, GlweDimension,
-    RadixCount, RadixDecomposition, Torus, TorusOps,
+    dst::OverlaySize, fft::ntt::get_ntt, math::goldilocks_field::Fg,
+    ops::ciphertext::external_product_ggsw_glwe, GlweDef, GlweDimension, RadixCount,
+    RadixDecomposition, Torus, TorusOps,
 };
 
 use super::{
-    GgswCiphertextFftRef, GlevCiphertextIterator, GlevCiphertextIteratorMut, GlevCiphertextRef,
-    GlweCiphertext, GlweCiphertextRef,
+    GgswCiphertextFftRef, GgswCiphertextNttRef, GlevCiphertextIterator, GlevCiphertextIteratorMut,
+    GlevCiphertextRef, GlweCiphertext, GlweCiphertextRef, NTT_LIMB_BITS, NTT_LIMB_COUNT,
 };
 
 dst! {
@@ -109,6 +110,44 @@ where
         }
     }
 
+    /// Compute the NTT of each of the polynomials in the GGSW ciphertext
+    /// after splitting their coefficients into limbs. The result is stored in
+    /// `result`.
+    ///
+    /// # Remarks
+    /// See [`GgswCiphertextNtt`](crate::entities::GgswCiphertextNtt) for the
+    /// representation.
+    pub fn ntt(
+        &self,
+        result: &mut GgswCiphertextNttRef<Fg>,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+    ) {
+        self.assert_valid(params, radix);
+        result.assert_valid(params, radix);
+
+        let degree = params.dim.polynomial_degree.0;
+        let ntt = get_ntt(degree.ilog2() as usize);
+
+        let limb_mask = (0x1u64 << NTT_LIMB_BITS) - 1;
+
+        for (s, r) in self
+            .as_slice()
+            .chunks(degree)
+            .zip(result.as_mut_slice().chunks_mut(degree * NTT_LIMB_COUNT))
+        {
+            for (j, r) in r.chunks_mut(degree).enumerate() {
+                let shift = NTT_LIMB_BITS * j as u32;
+
+                for (r, s) in r.iter_mut().zip(s.iter()) {
+                    *r = Fg::from((s.inner().to_u64() >> shift) & limb_mask);
+                }
+
+                ntt.forward_inplace(r);
+            }
+        }
+    }
+
     #[inline(always)]
     /// Assert that the GGSW ciphertext is valid for the given parameters.
     pub fn assert_valid(&self, glwe: &GlweDef, radix: &RadixDecomposition) {
@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    dst::{NoWrapper, OverlaySize},
+    entities::GgswCiphertextRef,
+    fft::ntt::get_ntt,
+    math::goldilocks_field::Fg,
+    GlweDef, GlweDimension, RadixCount, RadixDecomposition, TorusOps, Zero,
+};
+
+/// The number of bits in each limb of a torus coefficient in a
+/// [`GgswCiphertextNtt`].
+pub const NTT_LIMB_BITS: u32 = 16;
+
+/// The number of limbs each torus coefficient is split into in a
+/// [`GgswCiphertextNtt`]. These cover 64-bit tori.
+pub const NTT_LIMB_COUNT: usize = 4;
+
+dst! {
+    /// The NTT variant of a GGSW ciphertext over the Goldilocks field. See
+    /// [`GgswCiphertext`](crate::entities::GgswCiphertext) for more details.
+    ///
+    /// # Remarks
+    /// The torus coefficients of the GGSW ciphertext don't fit in the
+    /// Goldilocks field along with the products used in an external product.
+    /// As such, each polynomial is split into [`NTT_LIMB_COUNT`] polynomials
+    /// with [`NTT_LIMB_BITS`]-bit coefficients, least-significant first, and
+    /// each of these is stored in NTT form. Products with small decomposed
+    /// polynomials are computed exactly on each limb and recombined mod `q`.
+    GgswCiphertextNtt,
+    GgswCiphertextNttRef,
+    NoWrapper,
+    (Clone, Debug, Serialize, Deserialize),
+    ()
+}
+dst_iter! { GgswCiphertextNttIterator, GgswCiphertextNttIteratorMut, ParallelGgswCiphertextNttIterator, ParallelGgswCiphertextNttIteratorMut, NoWrapper, GgswCiphertextNttRef, ()}
+
+impl OverlaySize for GgswCiphertextNttRef<Fg> {
+    type Inputs = (GlweDimension, RadixCount);
+
+    fn size(t: Self::Inputs) -> usize {
+        let glwe_size = t.0.size.0 + 1;
+
+        glwe_size * glwe_size * t.1 .0 * NTT_LIMB_COUNT * t.0.polynomial_degree.0
+    }
+}
+
+impl GgswCiphertextNtt<Fg> {
+    /// Creates a new GGSW ciphertext with NTT representation.
+    pub fn new(params: &GlweDef, radix: &RadixDecomposition) -> GgswCiphertextNtt<Fg> {
+        let len = GgswCiphertextNttRef::size((params.dim, radix.count));
+
+        GgswCiphertextNtt {
+            data: avec![Fg::zero(); len],
+        }
+    }
+}
+
+impl GgswCiphertextNttRef<Fg> {
+    /// Returns an iterator over the GLWE ciphertexts in the GGSW ciphertext,
+    /// ordered by row and then by decomposition level. Each item contains the
+    /// limbs of each of the GLWE ciphertext's polynomials, with `A`
+    /// polynomials first.
+    pub fn glwe_limbs(&self, params: &GlweDef) -> std::slice::Chunks<Fg> {
+        let stride = (params.dim.size.0 + 1) * NTT_LIMB_COUNT * params.dim.polynomial_degree.0;
+
+        self.as_slice().chunks(stride)
+    }
+
+    /// Computes the inverse NTT of the GGSW ciphertext and stores the
+    /// recombined limbs in `result`.
+    pub fn intt<S: TorusOps>(
+        &self,
+        result: &mut GgswCiphertextRef<S>,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+    ) {
+        self.assert_valid(params, radix);
+        result.assert_valid(params, radix);
+
+        let degree = params.dim.polynomial_degree.0;
+        let ntt = get_ntt(degree.ilog2() as usize);
+
+        let mut limb = vec![Fg::zero(); degree];
+
+        for (s, r) in self
+            .as_slice()
+            .chunks(degree * NTT_LIMB_COUNT)
+            .zip(result.as_mut_slice().chunks_mut(degree))
+        {
+            let mut coeffs = vec![0u64; degree];
+
+            for (j, s) in s.chunks(degree).enumerate() {
+                limb.copy_from_slice(s);
+                ntt.reverse_inplace(&mut limb);
+
+                // Unlike the accumulated products in an external product,
+                // each limb is less than 2^NTT_LIMB_BITS, so it never wraps.
+                for (c, l) in coeffs.iter_mut().zip(limb.iter()) {
+                    *c = c.wrapping_add((l.to_signed() as u64) << (NTT_LIMB_BITS * j as u32));
+                }
+            }
+
+            for (r, c) in r.iter_mut().zip(coeffs) {
+                *r = S::from_u64(c).into();
+            }
+        }
+    }
+
+    #[inline(always)]
+    /// Asserts that this entity is valid under the passed parameters.
+    pub fn assert_valid(&self, glwe: &GlweDef, radix: &RadixDecomposition) {
+        assert_eq!(Self::size((glwe.dim, radix.count)), self.data.len());
+    }
+}
@@ -5,7 +5,8 @@ use crate::{
     entities::GgswCiphertext,
     macros::{impl_binary_op, impl_unary_op},
     ops::encryption::{
-        decrypt_glwe_ciphertext, encrypt_ggsw_ciphertext, encrypt_glwe_ciphertext_secret,
+        decrypt_glwe_ciphertext, encrypt_ggsw_ciphertext,
+        encrypt_glwe_ciphertext_secret_and_return_noise,
     },
     rand::{binary, uniform_torus},
     GlweDef, GlweDimension, PlaintextBits, RadixDecomposition, Torus, TorusOps,
@@ -116,6 +117,21 @@ where
         params: &GlweDef,
         plaintext_bits: PlaintextBits,
     ) -> GlweCiphertext<S>
+    where
+        S: TorusOps,
+    {
+        self.encode_encrypt_glwe_and_return_randomness(plaintext, params, plaintext_bits)
+            .0
+    }
+
+    /// Encodes and encrypts a message as a GLWE ciphertext using a secret key.
+    /// Returns the ciphertext and the noise polynomial used to generate it.
+    pub fn encode_encrypt_glwe_and_return_randomness(
+        &self,
+        plaintext: &PolynomialRef<S>,
+        params: &GlweDef,
+        plaintext_bits: PlaintextBits,
+    ) -> (GlweCiphertext<S>, Polynomial<Torus<S>>)
     where
         S: TorusOps,
     {
@@ -123,9 +139,9 @@ where
 
         let mut ct = GlweCiphertext::new(params);
 
-        encrypt_glwe_ciphertext_secret(&mut ct, &plaintext, self, params);
+        let e = encrypt_glwe_ciphertext_secret_and_return_noise(&mut ct, &plaintext, self, params);
 
-        ct
+        (ct, e)
     }
 
     /// Encodes and encrypts a message as a GGSW ciphertext using a secret key.
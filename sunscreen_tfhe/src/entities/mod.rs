@@ -52,6 +52,9 @@ pub use ggsw_ciphertext::*;
 mod ggsw_ciphertext_fft;
 pub use ggsw_ciphertext_fft::*;
 
+mod ggsw_ciphertext_ntt;
+pub use ggsw_ciphertext_ntt::*;
+
 mod lev_ciphertext;
 pub use lev_ciphertext::*;
 
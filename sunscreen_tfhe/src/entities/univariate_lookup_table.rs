@@ -3,10 +3,14 @@ use sunscreen_math::Zero;
 
 use crate::{
     dst::{FromMutSlice, FromSlice, OverlaySize},
-    entities::PolynomialRef,
-    ops::{bootstrapping::generate_lut, encryption::trivially_encrypt_glwe_ciphertext},
+    entities::{LweCiphertextRef, PolynomialRef, PublicFunctionalKeyswitchKeyRef},
+    ops::{
+        bootstrapping::{generate_lut, spread_lut_values},
+        encryption::trivially_encrypt_glwe_ciphertext,
+        keyswitch::public_functional_keyswitch::public_functional_keyswitch,
+    },
     scratch::allocate_scratch_ref,
-    GlweDef, GlweDimension, PlaintextBits, Torus, TorusOps,
+    GlweDef, GlweDimension, LweDef, PlaintextBits, RadixDecomposition, Torus, TorusOps,
 };
 
 use super::GlweCiphertextRef;
@@ -54,7 +58,17 @@ impl<S: TorusOps> UnivariateLookupTable<S> {
     ///
     /// # Remarks
     /// The result of this should be used with
-    /// [`generalized_programmable_bootstrap`](crate::ops::bootstrapping::generalized_programmable_bootstrap).
+    /// [`generalized_programmable_bootstrap`](crate::ops::bootstrapping::generalized_programmable_bootstrap)
+    /// or [`programmable_bootstrap_many_lut`](crate::ops::bootstrapping::programmable_bootstrap_many_lut).
+    ///
+    /// Packing `v` functions into one table reduces the precision available to
+    /// each function by `ceil(log2(v))` bits, which increases the probability
+    /// of a decryption failure.
+    ///
+    /// # Panics
+    /// If `maps` contains fewer than 2 functions.
+    /// If the polynomial degree in `glwe` can't hold `maps.len()` functions
+    /// over `plaintext_bits`.
     pub fn trivivial_multifunctional<F>(
         maps: &[F],
         glwe: &GlweDef,
@@ -65,6 +79,14 @@ impl<S: TorusOps> UnivariateLookupTable<S> {
     {
         assert!(maps.len() > 1);
 
+        let stride = glwe.dim.polynomial_degree.0 >> plaintext_bits.0;
+
+        assert!(
+            maps.len().next_power_of_two() <= stride / 2,
+            "Polynomial degree too small for {} functions",
+            maps.len()
+        );
+
         let mut lut = UnivariateLookupTable {
             data: avec![Torus::zero(); UnivariateLookupTableRef::<S>::size(glwe.dim)],
         };
@@ -73,6 +95,74 @@ impl<S: TorusOps> UnivariateLookupTable<S> {
 
         lut
     }
+
+    /// Creates the leaves of a tree-based lookup table for a function `map` of
+    /// `num_blocks` blocks, each containing `plaintext_bits` bits.
+    ///
+    /// # Remarks
+    /// `map` receives the blocks least-significant first. Each leaf is a
+    /// univariate function of the least-significant block for a fixed value of
+    /// the remaining blocks, giving `2^(plaintext_bits * (num_blocks - 1))`
+    /// leaves. Leaves are packed `2^log_v` per table so they can share a blind
+    /// rotation (see [`trivivial_multifunctional`](Self::trivivial_multifunctional)).
+    ///
+    /// The result of this should be used with
+    /// [`tree_programmable_bootstrap`](crate::ops::bootstrapping::tree_programmable_bootstrap)
+    /// with the same `log_v`.
+    ///
+    /// # Panics
+    /// If `num_blocks` is zero.
+    /// If `log_v` exceeds `plaintext_bits * (num_blocks - 1)`.
+    /// If the polynomial degree in `glwe` can't hold `2^log_v` functions over
+    /// `plaintext_bits`.
+    pub fn trivial_tree_leaves<F>(
+        map: F,
+        num_blocks: usize,
+        log_v: u32,
+        glwe: &GlweDef,
+        plaintext_bits: PlaintextBits,
+    ) -> Vec<Self>
+    where
+        F: Fn(&[u64]) -> u64,
+    {
+        assert!(num_blocks > 0);
+
+        let p = 0x1u64 << plaintext_bits.0;
+        let num_leaves = 0x1usize << (plaintext_bits.0 as usize * (num_blocks - 1));
+
+        assert!((0x1 << log_v) <= num_leaves);
+
+        let leaf = |leaf_id: usize| {
+            let map = &map;
+
+            move |x: u64| {
+                let mut blocks = vec![x];
+                let mut rest = leaf_id as u64;
+
+                for _ in 1..num_blocks {
+                    blocks.push(rest % p);
+                    rest /= p;
+                }
+
+                map(&blocks)
+            }
+        };
+
+        (0..num_leaves)
+            .step_by(0x1 << log_v)
+            .map(|start| {
+                let maps = (start..start + (0x1 << log_v))
+                    .map(leaf)
+                    .collect::<Vec<_>>();
+
+                if maps.len() == 1 {
+                    Self::trivial_from_fn(&maps[0], glwe, plaintext_bits)
+                } else {
+                    Self::trivivial_multifunctional(&maps, glwe, plaintext_bits)
+                }
+            })
+            .collect()
+    }
 }
 
 impl<S: TorusOps> UnivariateLookupTableRef<S> {
@@ -102,6 +192,47 @@ impl<S: TorusOps> UnivariateLookupTableRef<S> {
         trivially_encrypt_glwe_ciphertext(self.glwe_mut(), poly, glwe);
     }
 
+    /// Fills this lookup table with the messages in `values` by packing them
+    /// into a GLWE ciphertext, where `values[i]` is the result for input `i`.
+    /// Unlike the trivial lookup tables, the entries of the result remain
+    /// encrypted.
+    ///
+    /// # Remarks
+    /// `values` must contain one ciphertext per input in the plaintext space,
+    /// so `values.len()` is the plaintext modulus. Each message should use the
+    /// same encoding as a trivial lookup table entry.
+    ///
+    /// `pksk` is a packing keyswitch key from the key under which `values` are
+    /// encrypted to the GLWE secret key under which the bootstrap key is
+    /// encrypted (see
+    /// [`generate_packing_keyswitch_key`](crate::ops::keyswitch::packing_keyswitch::generate_packing_keyswitch_key)).
+    ///
+    /// # Panics
+    /// If `values.len()` isn't a power of 2 or exceeds the polynomial degree in `glwe`.
+    /// If `pksk` isn't valid under `from_lwe`, `glwe` and `radix`.
+    /// If any of `values` aren't valid under `from_lwe`.
+    pub fn fill_from_lwes(
+        &mut self,
+        values: &[&LweCiphertextRef<S>],
+        pksk: &PublicFunctionalKeyswitchKeyRef<S>,
+        from_lwe: &LweDef,
+        glwe: &GlweDef,
+        radix: &RadixDecomposition,
+    ) {
+        assert!(values.len().is_power_of_two());
+        assert!(values.len() <= glwe.dim.polynomial_degree.0);
+
+        public_functional_keyswitch(
+            self.glwe_mut(),
+            values,
+            pksk,
+            spread_lut_values,
+            from_lwe,
+            glwe,
+            radix,
+        );
+    }
+
     /// Creates a lookup table filled with the same value at every entry.
     pub fn fill_with_constant(&mut self, val: S, glwe: &GlweDef, plaintext_bits: PlaintextBits) {
         self.clear();
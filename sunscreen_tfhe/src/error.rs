@@ -1,4 +1,45 @@
-#[derive(thiserror::Error)]
+use crate::serialization::EntityKind;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+/// Errors that can occur in this crate.
 pub enum Error {
-    OutOfRange
-}
\ No newline at end of file
+    #[error("The given value is out of range.")]
+    /// The given value is out of range.
+    OutOfRange,
+
+    #[error("The data is not a valid serialized entity: {0}")]
+    /// The serialized data is truncated or otherwise malformed.
+    MalformedData(&'static str),
+
+    #[error("Unsupported wire format version {0}.")]
+    /// The serialized data uses a wire format version this library can't read.
+    UnsupportedVersion(u16),
+
+    #[error("Expected a serialized {expected:?}, found {actual:?}.")]
+    /// The serialized data contains a different kind of entity than requested.
+    EntityKindMismatch {
+        /// The kind of entity requested.
+        expected: EntityKind,
+
+        /// The kind of entity in the serialized data.
+        actual: EntityKind,
+    },
+
+    #[error("Expected a {expected}-bit torus, found {actual}-bit.")]
+    /// The serialized data uses a different torus width than requested.
+    TorusWidthMismatch {
+        /// The torus width requested.
+        expected: u32,
+
+        /// The torus width in the serialized data.
+        actual: u32,
+    },
+
+    #[error("The serialized entity's parameters don't match the given parameters.")]
+    /// The serialized entity was created under different parameters than those
+    /// given when loading it.
+    ParameterMismatch,
+}
+
+/// A result in this crate.
+pub type Result<T> = std::result::Result<T, Error>;
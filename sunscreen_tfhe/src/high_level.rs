@@ -53,11 +53,13 @@ pub mod keygen {
         entities::{
             BootstrapKey, CircuitBootstrappingKeyswitchKeys, GlweSecretKey, GlweSecretKeyRef,
             LweKeyswitchKey, LwePublicKey, LweSecretKey, LweSecretKeyRef,
+            PublicFunctionalKeyswitchKey,
         },
         ops::{
             bootstrapping::generate_bootstrap_key,
             keyswitch::{
                 lwe_keyswitch_key::generate_keyswitch_key_lwe,
+                packing_keyswitch::generate_packing_keyswitch_key,
                 private_functional_keyswitch::generate_circuit_bootstrapping_pfks_keys,
             },
         },
@@ -299,6 +301,39 @@ pub mod keygen {
 
         cbs_ksk
     }
+
+    /// Generate a packing keyswitch key, which allows you to pack many
+    /// [`LweCiphertext`](crate::entities::LweCiphertext)s encrypted under `from_sk`
+    /// into a single [`GlweCiphertext`](crate::entities::GlweCiphertext) encrypted
+    /// under `to_sk`. See [`pack_lwes`](super::evaluation::pack_lwes).
+    ///
+    /// # Remarks
+    /// `from_lwe` and `to_glwe` are the parameters under which you generated `from_sk`
+    /// and `to_sk`, respectively.
+    ///
+    /// `radix` specifies the decomposition to use during packing.
+    ///
+    /// # Panics
+    /// If `from_lwe`, `to_glwe`, or `radix` are invalid.
+    /// If `from_lwe` or `to_glwe` don't correspond with `from_sk` or `to_sk`, respectively.
+    ///
+    /// # Security
+    /// The returned key is public and sharing it does not compromise semantic
+    /// security. However, anyone who possesses `to_sk` can easily recover
+    /// `from_sk` using the returned key.
+    pub fn generate_packing_ksk(
+        from_sk: &LweSecretKeyRef<u64>,
+        to_sk: &GlweSecretKeyRef<u64>,
+        from_lwe: &LweDef,
+        to_glwe: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> PublicFunctionalKeyswitchKey<u64> {
+        let mut pksk = PublicFunctionalKeyswitchKey::new(from_lwe, to_glwe, radix);
+
+        generate_packing_keyswitch_key(&mut pksk, from_sk, to_sk, from_lwe, to_glwe, radix);
+
+        pksk
+    }
 }
 
 /// TFHE functionality related to encryption.
@@ -442,6 +477,37 @@ pub mod encryption {
         sk.encode_encrypt_glwe(pt, params, plaintext_bits)
     }
 
+    /// Create a tuple containing a [`GlweCiphertext`] encryption of `pt`
+    /// under `sk` and the noise polynomial used to generate it.
+    ///
+    /// This randomness can be used to produce zero-knowledge proofs.
+    ///
+    /// # Remarks
+    /// `params` should be a same as those used when creating `sk`.
+    /// `pt.len()` should equal `sk.dim.polynomial_degree.0`.
+    ///
+    /// `plaintext_bits` describes how many of the most-significant bits of the [`Torus`]
+    /// polynomial coefficients will contain the message. No coefficient in
+    /// `pt` should exceed `2^plaintext_bits.0`.
+    ///
+    /// # Panics
+    /// If `params` is invalid.
+    /// If `params` doesn't correspond with `sk`
+    /// If `pt` doesn't have the same number of coefficients as
+    /// `params.dim.polynomial_degree.0`.
+    ///
+    /// # Security
+    /// Revealing the returned randomness compromises the confidentiality
+    /// of the returned [`GlweCiphertext`].
+    pub fn encrypt_glwe_and_return_randomness(
+        pt: &PolynomialRef<u64>,
+        sk: &GlweSecretKeyRef<u64>,
+        params: &GlweDef,
+        plaintext_bits: PlaintextBits,
+    ) -> (GlweCiphertext<u64>, Polynomial<Torus<u64>>) {
+        sk.encode_encrypt_glwe_and_return_randomness(pt, params, plaintext_bits)
+    }
+
     /// Create a trivial LWE encryption. Trivial encryptions have no noise and are thus
     /// insecure. However, they are useful for creating public constants in TFHE computations.
     ///
@@ -571,6 +637,35 @@ pub mod encryption {
         sk.decrypt_decode_glwe(ct, params, plaintext_bits)
     }
 
+    /// Decrypt a [GlweCiphertext] produced by [`pack_lwes`](super::evaluation::pack_lwes)
+    /// and return the first `count` messages.
+    ///
+    /// # Remarks
+    /// This is equivalent to calling [`decrypt_glwe`] and taking the first `count`
+    /// coefficients of the result. The `i`-th returned value is the message packed from
+    /// the `i`-th input [`LweCiphertext`].
+    ///
+    /// `plaintext_bits` should match the value used when encrypting the packed
+    /// [`LweCiphertext`]s.
+    ///
+    /// # Panics
+    /// If `params` doesn't correspond with either `ct` or `sk`.
+    /// If `params` is invalid.
+    /// If `count` exceeds the polynomial degree in `params`.
+    pub fn decrypt_packed_lwes(
+        ct: &GlweCiphertextRef<u64>,
+        sk: &GlweSecretKeyRef<u64>,
+        params: &GlweDef,
+        plaintext_bits: PlaintextBits,
+        count: usize,
+    ) -> Vec<u64> {
+        assert!(count <= params.dim.polynomial_degree.0);
+
+        let pt = decrypt_glwe(ct, sk, params, plaintext_bits);
+
+        pt.coeffs()[..count].to_vec()
+    }
+
     /// Create a trivial encryption of `pt` as a [GlweCiphertext]. Trivial encryptions contain
     /// no noise and are thus insecure. However, they are useful as public constants in
     /// a TFHE computation.
@@ -687,9 +782,10 @@ pub mod fft {
 
     use crate::{
         entities::{
-            BootstrapKeyFft, BootstrapKeyRef, GgswCiphertextFft, GgswCiphertextRef,
-            GlweCiphertextFft, GlweCiphertextRef,
+            BootstrapKeyFft, BootstrapKeyNtt, BootstrapKeyRef, GgswCiphertextFft,
+            GgswCiphertextNtt, GgswCiphertextRef, GlweCiphertextFft, GlweCiphertextRef,
         },
+        math::goldilocks_field::Fg,
         GlweDef, LweDef, RadixDecomposition,
     };
 
@@ -760,6 +856,57 @@ pub mod fft {
 
         bsk_fft
     }
+
+    /// Take the Goldilocks NTT of a [`GgswCiphertext`](crate::entities::GgswCiphertext).
+    /// The resulting [`GgswCiphertextNtt`] may be used in
+    /// [`cmux_ntt`](crate::ops::ntt_ops::cmux_ntt) operations.
+    ///
+    /// # Remarks
+    /// `glwe` and `radix` must be the same parameters that produced `ggsw`.
+    ///
+    /// # Panics
+    /// If `glwe` and `radix` don't correspond with `ggsw`.
+    /// If `glwe` or `radix` are invalid.
+    pub fn ntt_ggsw(
+        ggsw: &GgswCiphertextRef<u64>,
+        glwe: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> GgswCiphertextNtt<Fg> {
+        let mut ntt = GgswCiphertextNtt::new(glwe, radix);
+
+        ggsw.ntt(&mut ntt, glwe, radix);
+
+        ntt
+    }
+
+    /// Take the Goldilocks NTT of a [BootstrapKey](crate::entities::BootstrapKey).
+    /// The resulting [`BootstrapKeyNtt`] may be used in
+    /// [`univariate_programmable_bootstrap_ntt`](super::evaluation::univariate_programmable_bootstrap_ntt)
+    /// operations.
+    ///
+    /// # Remarks
+    /// Bootstrapping with a [`BootstrapKeyNtt`] is exact, whereas a
+    /// [`BootstrapKeyFft`] introduces floating-point rounding error that
+    /// grows with the decomposition base. This makes the NTT variant
+    /// preferable for 64-bit tori with large decomposition bases.
+    ///
+    /// `glwe` and `radix` must be the same parameters that produced `bsk`.
+    ///
+    /// # Panics
+    /// If `glwe` and `radix` don't correspond with `bsk`.
+    /// If `glwe` or `radix` are invalid.
+    pub fn ntt_bootstrap_key(
+        bsk: &BootstrapKeyRef<u64>,
+        lwe: &LweDef,
+        glwe: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> BootstrapKeyNtt<Fg> {
+        let mut bsk_ntt = BootstrapKeyNtt::new(lwe, glwe, radix);
+
+        bsk.ntt(&mut bsk_ntt, lwe, glwe, radix);
+
+        bsk_ntt
+    }
 }
 
 /// TFHE operations for performing computation.
@@ -768,10 +915,12 @@ pub mod evaluation {
 
     use crate::{
         entities::{
-            BootstrapKeyFft, BootstrapKeyFftRef, CircuitBootstrappingKeyswitchKeysRef,
-            GgswCiphertext, GgswCiphertextFftRef, GlweCiphertext, GlweCiphertextRef, LweCiphertext,
-            LweCiphertextRef, LweKeyswitchKeyRef, UnivariateLookupTableRef,
+            BootstrapKeyFft, BootstrapKeyFftRef, BootstrapKeyNttRef,
+            CircuitBootstrappingKeyswitchKeysRef, GgswCiphertext, GgswCiphertextFftRef,
+            GlweCiphertext, GlweCiphertextRef, LweCiphertext, LweCiphertextRef, LweKeyswitchKeyRef,
+            PublicFunctionalKeyswitchKeyRef, UnivariateLookupTableRef,
         },
+        math::goldilocks_field::Fg,
         GlweDef, LweDef, RadixDecomposition,
     };
 
@@ -851,6 +1000,37 @@ pub mod evaluation {
         out
     }
 
+    /// Perform a programmable bootstrapping operation with a bootstrapping key
+    /// in Goldilocks NTT form. See [`univariate_programmable_bootstrap`] for
+    /// details.
+    ///
+    /// # Remarks
+    /// See [`ntt_bootstrap_key`](super::fft::ntt_bootstrap_key) for how to
+    /// create `bsk` and when to prefer it over a
+    /// [`BootstrapKeyFft`].
+    ///
+    /// # Panics
+    /// If `lwe`, `glwe`, or `radix` parameters are invalid.
+    /// If `input` doesn't correspond to `lwe` parameters.
+    /// If `bsk` doesn't correspond to `lwe`, `glwe`, `radix` parameters.
+    /// If `lut` doesn't correspond to `glwe` parameters.
+    pub fn univariate_programmable_bootstrap_ntt(
+        input: &LweCiphertextRef<u64>,
+        lut: &UnivariateLookupTableRef<u64>,
+        bsk: &BootstrapKeyNttRef<Fg>,
+        lwe: &LweDef,
+        glwe: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> LweCiphertext<u64> {
+        let mut out = LweCiphertext::new(&glwe.as_lwe_def());
+
+        crate::ops::bootstrapping::programmable_bootstrap_univariate_ntt(
+            &mut out, input, lut, bsk, lwe, glwe, radix,
+        );
+
+        out
+    }
+
     #[allow(clippy::too_many_arguments)]
     /// Perform a circuit bootstrapping operation. Circuit bootstrapping takes
     /// `input` [LweCiphertext] encrypted under a [LweSecretKey](crate::entities::LweSecretKey)
@@ -973,4 +1153,122 @@ pub mod evaluation {
 
         result
     }
+
+    /// Pack `inputs` into a single [`GlweCiphertext`], where the message in `inputs[i]`
+    /// becomes the `i`-th coefficient of the result's message. The remaining coefficients
+    /// encrypt zero.
+    ///
+    /// # Remarks
+    /// Packing reduces the size of many results by roughly a factor of the polynomial
+    /// degree in `to_glwe`, which is useful for storage or transport.
+    ///
+    /// `inputs` must be encrypted under the `from_sk` used to generate `pksk` with
+    /// [`generate_packing_ksk`](super::keygen::generate_packing_ksk). The result is
+    /// encrypted under that function's `to_sk`.
+    ///
+    /// To decrypt the packed messages, use
+    /// [`decrypt_packed_lwes`](super::encryption::decrypt_packed_lwes). To recover
+    /// the `i`-th message as an [`LweCiphertext`], use [`sample_extract`] on coefficient `i`.
+    ///
+    /// # Panics
+    /// If `inputs.len()` exceeds the polynomial degree in `to_glwe`.
+    /// If `pksk` isn't valid under `from_lwe`, `to_glwe` and `radix`.
+    /// If any input isn't valid under `from_lwe`.
+    /// If `from_lwe`, `to_glwe`, or `radix` are invalid.
+    pub fn pack_lwes(
+        inputs: &[&LweCiphertextRef<u64>],
+        pksk: &PublicFunctionalKeyswitchKeyRef<u64>,
+        from_lwe: &LweDef,
+        to_glwe: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> GlweCiphertext<u64> {
+        let mut result = GlweCiphertext::new(to_glwe);
+
+        crate::ops::keyswitch::packing_keyswitch::pack_lwe_ciphertexts(
+            &mut result,
+            inputs,
+            pksk,
+            from_lwe,
+            to_glwe,
+            radix,
+        );
+
+        result
+    }
+
+    /// Obliviously select `table[i]`, where `i` is the integer whose bits are encrypted
+    /// in `selectors`, least-significant bit first.
+    ///
+    /// # Remarks
+    /// Each selector is a [`GgswCiphertext`] encrypting 0 or 1, such as the result of a
+    /// [`circuit_bootstrap`] followed by [`fft_ggsw`](super::fft::fft_ggsw). Entries past
+    /// the end of `table` encrypt zero. To look up a plaintext table, encrypt its entries
+    /// with [`trivial_glwe`](super::encryption::trivial_glwe).
+    ///
+    /// See [`cmux_tree_lookup`](crate::ops::bootstrapping::cmux_tree_lookup) for more details.
+    ///
+    /// # Panics
+    /// If `table` is empty or contains more than `2^selectors.len()` entries.
+    /// If `params` or `radix` are invalid.
+    /// If any entry of `table` isn't valid under `params`.
+    /// If any of `selectors` isn't valid under `params` and `radix`.
+    pub fn cmux_tree_lookup(
+        table: &[&GlweCiphertextRef<u64>],
+        selectors: &[&GgswCiphertextFftRef<Complex<f64>>],
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+    ) -> GlweCiphertext<u64> {
+        let mut result = GlweCiphertext::new(params);
+
+        crate::ops::bootstrapping::cmux_tree_lookup(&mut result, table, selectors, params, radix);
+
+        result
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    /// Obliviously select `table[i]`, where `i` is the integer whose bits are encrypted
+    /// in `index_bits`, least-significant bit first.
+    ///
+    /// # Remarks
+    /// This [`circuit_bootstrap`]s each of `index_bits` and uses the results as the
+    /// selectors of a [`cmux_tree_lookup`]. The parameters have the same meaning as in
+    /// [`circuit_bootstrap`]. `table` is encrypted under the GLWE secret key with
+    /// `glwe_1` parameters.
+    ///
+    /// See [`vertical_lookup`](crate::ops::bootstrapping::vertical_lookup) for more details.
+    ///
+    /// # Panics
+    /// If `table` is empty or contains more than `2^index_bits.len()` entries.
+    /// Under the same conditions as [`circuit_bootstrap`].
+    /// If any entry of `table` isn't valid under `glwe_1`.
+    pub fn vertical_lookup(
+        table: &[&GlweCiphertextRef<u64>],
+        index_bits: &[&LweCiphertextRef<u64>],
+        bsk: &BootstrapKeyFftRef<Complex<f64>>,
+        cbsksk: &CircuitBootstrappingKeyswitchKeysRef<u64>,
+        lwe_0: &LweDef,
+        glwe_1: &GlweDef,
+        glwe_2: &GlweDef,
+        pbs_radix: &RadixDecomposition,
+        cbs_radix: &RadixDecomposition,
+        pfks_radix: &RadixDecomposition,
+    ) -> GlweCiphertext<u64> {
+        let mut result = GlweCiphertext::new(glwe_1);
+
+        crate::ops::bootstrapping::vertical_lookup(
+            &mut result,
+            table,
+            index_bits,
+            bsk,
+            cbsksk,
+            lwe_0,
+            glwe_1,
+            glwe_2,
+            pbs_radix,
+            cbs_radix,
+            pfks_radix,
+        );
+
+        result
+    }
 }
@@ -22,10 +22,16 @@ pub use math::*;
 
 mod macros;
 
+mod error;
+pub use error::*;
+
 /// Random number generation.
 pub mod rand;
 mod scratch;
 
+/// A versioned binary wire format for TFHE keys and ciphertexts.
+pub mod serialization;
+
 /// A high-level API for interfacing with TFHE. Allocates, computes with and returns
 /// objects as you would expect from a Rust API.
 pub mod high_level;
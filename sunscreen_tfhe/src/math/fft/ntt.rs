@@ -0,0 +1,246 @@
+use std::sync::Once;
+
+use crate::{math::goldilocks_field::Fg, FrequencyTransform, Inverse, One, RootOfUnity};
+
+static NTT_CACHE_INIT: Once = Once::new();
+static mut NTT_CACHE: Vec<NegacyclicNtt> = vec![];
+
+/// Get a [NegacyclicNtt] for a given log N.
+pub fn get_ntt(log_n: usize) -> &'static NegacyclicNtt {
+    // Can NTT powers of 2 from N=1 up to 4096.
+    assert!(log_n < 13);
+
+    NTT_CACHE_INIT.call_once(|| {
+        for i in 0..13 {
+            unsafe { NTT_CACHE.push(NegacyclicNtt::new(0x1 << i)) };
+        }
+    });
+
+    unsafe { &NTT_CACHE[log_n] }
+}
+
+/// Perform a number theoretic transform over the Goldilocks field with a
+/// twist so points can be used for negacyclic convolution.
+///
+/// # Remarks
+/// Unlike [`TwistedFft`](crate::fft::negacyclic::TwistedFft), this transform
+/// is exact. Pointwise multiplying the transforms of two polynomials and
+/// taking the inverse transform yields their product mod `(X^N + 1, p)`.
+///
+/// The forward transform is a Cooley-Tukey butterfly that merges the twist by
+/// the `2N`-th root of unity into its twiddle factors and produces its output
+/// in bit-reversed order. The reverse transform is a Gentleman-Sande
+/// butterfly that consumes bit-reversed input. See "Speeding up the Number
+/// Theoretic Transform for Faster Ideal Lattice-Based Cryptography" by
+/// Longa and Naehrig.
+pub struct NegacyclicNtt {
+    psi_rev: Vec<Fg>,
+    psi_inv_rev: Vec<Fg>,
+    n_inv: Fg,
+}
+
+impl NegacyclicNtt {
+    /// Create a new [NegacyclicNtt] with the given size.
+    pub fn new(n: usize) -> Self {
+        assert!(n.is_power_of_two());
+
+        let psi = Fg::nth_root_of_unity(2 * n as u64);
+        let psi_inv = psi.inverse();
+
+        let log_n = n.ilog2();
+
+        let bit_rev = |i: usize| {
+            if log_n == 0 {
+                0
+            } else {
+                i.reverse_bits() >> (usize::BITS - log_n)
+            }
+        };
+
+        let powers = |root: Fg| {
+            let mut pows = Vec::with_capacity(n);
+            let mut cur = Fg::one();
+
+            for _ in 0..n {
+                pows.push(cur);
+                cur = cur * root;
+            }
+
+            (0..n).map(|i| pows[bit_rev(i)]).collect::<Vec<_>>()
+        };
+
+        Self {
+            psi_rev: powers(psi),
+            psi_inv_rev: powers(psi_inv),
+            n_inv: Fg::from(n as u64).inverse(),
+        }
+    }
+
+    /// The size of the transform.
+    pub fn len(&self) -> usize {
+        self.psi_rev.len()
+    }
+
+    /// Whether the transform has size zero. Always false.
+    pub fn is_empty(&self) -> bool {
+        self.psi_rev.is_empty()
+    }
+
+    /// Perform the forward transform in place.
+    pub fn forward_inplace(&self, a: &mut [Fg]) {
+        let n = self.len();
+        assert_eq!(a.len(), n);
+
+        let mut t = n;
+        let mut m = 1;
+
+        while m < n {
+            t /= 2;
+
+            for i in 0..m {
+                let j_1 = 2 * i * t;
+                let s = self.psi_rev[m + i];
+
+                for j in j_1..j_1 + t {
+                    let u = a[j];
+                    let v = a[j + t] * s;
+
+                    a[j] = u + v;
+                    a[j + t] = u - v;
+                }
+            }
+
+            m *= 2;
+        }
+    }
+
+    /// Perform the reverse transform in place.
+    pub fn reverse_inplace(&self, a: &mut [Fg]) {
+        let n = self.len();
+        assert_eq!(a.len(), n);
+
+        let mut t = 1;
+        let mut m = n;
+
+        while m > 1 {
+            let h = m / 2;
+
+            for i in 0..h {
+                let j_1 = 2 * i * t;
+                let s = self.psi_inv_rev[h + i];
+
+                for j in j_1..j_1 + t {
+                    let u = a[j];
+                    let v = a[j + t];
+
+                    a[j] = u + v;
+                    a[j + t] = (u - v) * s;
+                }
+            }
+
+            t *= 2;
+            m = h;
+        }
+
+        for x in a.iter_mut() {
+            *x = *x * self.n_inv;
+        }
+    }
+}
+
+impl FrequencyTransform for NegacyclicNtt {
+    type BaseRepr = Fg;
+    type FrequencyRepr = Fg;
+
+    fn forward(&self, data: &[Self::BaseRepr], output: &mut [Self::FrequencyRepr]) {
+        output.copy_from_slice(data);
+        self.forward_inplace(output);
+    }
+
+    fn reverse(&self, data: &[Self::FrequencyRepr], output: &mut [Self::BaseRepr]) {
+        output.copy_from_slice(data);
+        self.reverse_inplace(output);
+    }
+}
+
+/// Compute `c += a * b` pointwise, where `a` and `b` are the transforms of
+/// two polynomials.
+pub fn pointwise_mad(c: &mut [Fg], a: &[Fg], b: &[Fg]) {
+    assert_eq!(c.len(), a.len());
+    assert_eq!(c.len(), b.len());
+
+    for (c, (a, b)) in c.iter_mut().zip(a.iter().zip(b.iter())) {
+        *c = a.mad(*b, *c);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{thread_rng, RngCore};
+
+    use crate::Zero;
+
+    use super::*;
+
+    fn naive_negacyclic_mul(a: &[Fg], b: &[Fg]) -> Vec<Fg> {
+        let n = a.len();
+        let mut c = vec![Fg::zero(); n];
+
+        for i in 0..n {
+            for j in 0..n {
+                let prod = a[i] * b[j];
+
+                if i + j < n {
+                    c[i + j] = c[i + j] + prod;
+                } else {
+                    c[i + j - n] = c[i + j - n] - prod;
+                }
+            }
+        }
+
+        c
+    }
+
+    fn random_poly(n: usize) -> Vec<Fg> {
+        (0..n).map(|_| Fg::from(thread_rng().next_u64())).collect()
+    }
+
+    #[test]
+    fn can_roundtrip_ntt() {
+        for log_n in 0..11 {
+            let ntt = get_ntt(log_n);
+
+            let a = random_poly(0x1 << log_n);
+            let mut a_ntt = vec![Fg::zero(); a.len()];
+            let mut actual = vec![Fg::zero(); a.len()];
+
+            ntt.forward(&a, &mut a_ntt);
+            ntt.reverse(&a_ntt, &mut actual);
+
+            assert_eq!(actual, a);
+        }
+    }
+
+    #[test]
+    fn can_negacyclic_multiply_ntt() {
+        for log_n in 0..9 {
+            let ntt = get_ntt(log_n);
+            let n = 0x1 << log_n;
+
+            let a = random_poly(n);
+            let b = random_poly(n);
+
+            let mut a_ntt = vec![Fg::zero(); n];
+            let mut b_ntt = vec![Fg::zero(); n];
+            let mut c_ntt = vec![Fg::zero(); n];
+            let mut actual = vec![Fg::zero(); n];
+
+            ntt.forward(&a, &mut a_ntt);
+            ntt.forward(&b, &mut b_ntt);
+            pointwise_mad(&mut c_ntt, &a_ntt, &b_ntt);
+            ntt.reverse(&c_ntt, &mut actual);
+
+            assert_eq!(actual, naive_negacyclic_mul(&a, &b));
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use std::ops::{Add, Mul, Neg, Sub};
 
 use num::traits::{WrappingAdd, WrappingMul, WrappingNeg, WrappingSub};
+use serde::{Deserialize, Serialize};
 use sunscreen_math::{refify_binary_op, One, Zero};
 
 use crate::{Inverse, Pow, RootOfUnity};
@@ -8,7 +9,7 @@ use crate::{Inverse, Pow, RootOfUnity};
 /// 2^64 - 2^32 + 1
 pub const GOLDILOCKS_PRIME: u64 = 0xFFFFFFFF00000001;
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 #[repr(transparent)]
 /// A value in the Goldilocks field (F_p where p = 2^64 - 2^32 + 1).
 /// See
@@ -44,6 +45,7 @@ impl Fg {
     }
 
     #[inline]
+    /// Compute `self + rhs` and don't reduce the result.
     pub fn unreduced_add(self, rhs: Self) -> Fg96 {
         let (c, carry) = self.0.overflowing_add(rhs.0);
 
@@ -54,11 +56,13 @@ impl Fg {
     }
 
     #[inline]
+    /// Compute `self - rhs` and don't reduce the result.
     pub fn unreduced_sub(self, rhs: Self) -> Fg96 {
         self.unreduced_add(Fg(GOLDILOCKS_PRIME - rhs.0))
     }
 
     #[inline]
+    /// Compute `self * rhs` and don't reduce the result.
     pub fn unreduced_mul(self, rhs: Self) -> Fg159 {
         let res = self.0 as u128 * rhs.0 as u128;
 
@@ -78,6 +82,30 @@ impl Fg {
     pub fn mad(self, b: Self, c: Self) -> Self {
         self.unreduced_mad(b, c).reduce()
     }
+
+    #[inline]
+    /// Maps a signed integer into the field, where negative values `-x`
+    /// become `p - x`.
+    pub fn from_signed(x: i64) -> Self {
+        if x < 0 {
+            Self(GOLDILOCKS_PRIME - x.unsigned_abs())
+        } else {
+            Self(x as u64)
+        }
+    }
+
+    #[inline]
+    /// Returns the representative of `self` in `[-(p - 1) / 2, (p - 1) / 2]`.
+    ///
+    /// # Remarks
+    /// This is the inverse of [`Fg::from_signed`] for values in this range.
+    pub fn to_signed(self) -> i64 {
+        if self.0 > GOLDILOCKS_PRIME / 2 {
+            -((GOLDILOCKS_PRIME - self.0) as i64)
+        } else {
+            self.0 as i64
+        }
+    }
 }
 
 impl From<u64> for Fg {
@@ -183,6 +211,18 @@ impl Zero for Fg {
     }
 }
 
+impl num::Zero for Fg {
+    #[inline]
+    fn zero() -> Self {
+        Fg(0)
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
 impl One for Fg {
     #[inline]
     fn one() -> Self {
@@ -348,6 +388,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn can_roundtrip_signed_fg() {
+        for x in [0, 1, -1, i64::MAX / 2, -(i64::MAX / 2)] {
+            assert_eq!(Fg::from_signed(x).to_signed(), x);
+        }
+
+        for _ in 0..1000 {
+            let x = (thread_rng().next_u64() >> 2) as i64 - (0x1 << 61);
+
+            assert_eq!(Fg::from_signed(x).to_signed(), x);
+            assert_eq!(Fg::from_signed(x) + Fg::from_signed(-x), Fg::zero());
+        }
+    }
+
     #[test]
     fn nth_root_of_unity() {
         for i in 1..16u64 {
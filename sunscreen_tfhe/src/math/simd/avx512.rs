@@ -0,0 +1,318 @@
+use num::Complex;
+
+use std::{arch::asm, sync::OnceLock};
+
+use super::scalar;
+
+#[inline(always)]
+/// Whether the CPU supports the AVX-512F instructions used by the kernels in
+/// this module.
+///
+/// # Remarks
+/// Unlike checking CPUID alone, this also ensures the OS has enabled the
+/// AVX-512 register state, without which these instructions fault.
+pub fn avx_512_available() -> bool {
+    static AVX512_AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+    *AVX512_AVAILABLE.get_or_init(|| std::arch::is_x86_feature_detected!("avx512f"))
+}
+
+/// Compute vector `c += a * b` over &[Complex<f64>].
+///
+/// # Safety
+/// The CPU must support AVX-512F (see [`avx_512_available`]).
+///
+/// # Panics
+/// If `c.len() != a.len() != b.len()`
+pub unsafe fn complex_mad(c: &mut [Complex<f64>], a: &[Complex<f64>], b: &[Complex<f64>]) {
+    assert_eq!(c.len(), a.len());
+    assert_eq!(b.len(), a.len());
+
+    let split = a.len() - a.len() % 8;
+
+    complex_mad_avx_512_unchecked(&mut c[..split], &a[..split], &b[..split]);
+    scalar::complex_mad(&mut c[split..], &a[split..], &b[split..]);
+}
+
+/// Compute vector `c = a + b` over &[u64] with wrapping arithmetic.
+///
+/// # Safety
+/// The CPU must support AVX-512F (see [`avx_512_available`]).
+///
+/// # Panics
+/// If `c.len() != a.len() != b.len()`
+pub unsafe fn torus_add(c: &mut [u64], a: &[u64], b: &[u64]) {
+    assert_eq!(c.len(), a.len());
+    assert_eq!(b.len(), a.len());
+
+    let split = a.len() - a.len() % 8;
+
+    let a_ptr = a.as_ptr();
+    let b_ptr = b.as_ptr();
+    let c_ptr = c.as_mut_ptr();
+
+    let mut i = 0;
+
+    while i < split {
+        asm!(
+            "vmovdqu64 zmm0, [{a_ptr}+8*{i}]",
+            "vpaddq zmm0, zmm0, [{b_ptr}+8*{i}]",
+            "vmovdqu64 [{c_ptr}+8*{i}], zmm0",
+            a_ptr = in(reg) a_ptr,
+            b_ptr = in(reg) b_ptr,
+            c_ptr = in(reg) c_ptr,
+            i = in(reg) i,
+            out("zmm0") _,
+        );
+
+        i += 8;
+    }
+
+    scalar::torus_add(&mut c[split..], &a[split..], &b[split..]);
+}
+
+/// Compute vector `c = a - b` over &[u64] with wrapping arithmetic.
+///
+/// # Safety
+/// The CPU must support AVX-512F (see [`avx_512_available`]).
+///
+/// # Panics
+/// If `c.len() != a.len() != b.len()`
+pub unsafe fn torus_sub(c: &mut [u64], a: &[u64], b: &[u64]) {
+    assert_eq!(c.len(), a.len());
+    assert_eq!(b.len(), a.len());
+
+    let split = a.len() - a.len() % 8;
+
+    let a_ptr = a.as_ptr();
+    let b_ptr = b.as_ptr();
+    let c_ptr = c.as_mut_ptr();
+
+    let mut i = 0;
+
+    while i < split {
+        asm!(
+            "vmovdqu64 zmm0, [{a_ptr}+8*{i}]",
+            "vpsubq zmm0, zmm0, [{b_ptr}+8*{i}]",
+            "vmovdqu64 [{c_ptr}+8*{i}], zmm0",
+            a_ptr = in(reg) a_ptr,
+            b_ptr = in(reg) b_ptr,
+            c_ptr = in(reg) c_ptr,
+            i = in(reg) i,
+            out("zmm0") _,
+        );
+
+        i += 8;
+    }
+
+    scalar::torus_sub(&mut c[split..], &a[split..], &b[split..]);
+}
+
+/// Compute vector `c += a * s` over &[u64] with wrapping arithmetic.
+///
+/// # Remarks
+/// AVX-512F has no 64-bit multiply, so we assemble the low 64 bits of the
+/// product from 32-bit multiplies as
+/// `lo(a) * lo(s) + ((hi(a) * lo(s) + lo(a) * hi(s)) << 32)`. IFMA's 52-bit
+/// multiplier doesn't reduce the number of multiplies needed for a 64-bit
+/// product, so we don't use it.
+///
+/// # Safety
+/// The CPU must support AVX-512F (see [`avx_512_available`]).
+///
+/// # Panics
+/// If `c.len() != a.len()`
+pub unsafe fn torus_scalar_mad(c: &mut [u64], a: &[u64], s: u64) {
+    assert_eq!(c.len(), a.len());
+
+    let split = a.len() - a.len() % 8;
+
+    let a_ptr = a.as_ptr();
+    let c_ptr = c.as_mut_ptr();
+
+    let mut i = 0;
+
+    while i < split {
+        asm!(
+            "vpbroadcastq zmm1, {s}",          // s
+            "vpsrlq zmm2, zmm1, 32",           // hi(s)
+            "vmovdqu64 zmm0, [{a_ptr}+8*{i}]", // a
+            "vpsrlq zmm3, zmm0, 32",           // hi(a)
+            "vpmuludq zmm3, zmm3, zmm1",       // hi(a) * lo(s)
+            "vpmuludq zmm4, zmm0, zmm2",       // lo(a) * hi(s)
+            "vpaddq zmm3, zmm3, zmm4",
+            "vpsllq zmm3, zmm3, 32",
+            "vpmuludq zmm4, zmm0, zmm1", // lo(a) * lo(s)
+            "vpaddq zmm3, zmm3, zmm4",   // a * s
+            "vpaddq zmm3, zmm3, [{c_ptr}+8*{i}]",
+            "vmovdqu64 [{c_ptr}+8*{i}], zmm3",
+            a_ptr = in(reg) a_ptr,
+            c_ptr = in(reg) c_ptr,
+            i = in(reg) i,
+            s = in(reg) s,
+            out("zmm0") _,
+            out("zmm1") _,
+            out("zmm2") _,
+            out("zmm3") _,
+            out("zmm4") _,
+        );
+
+        i += 8;
+    }
+
+    scalar::torus_scalar_mad(&mut c[split..], &a[split..], s);
+}
+
+/// Compute vector `c += a * b` over `&[Complex<f64>]`.
+/// This function is very unsafe.
+///
+/// # Safety
+/// The CPU must support AVX-512F.
+///
+/// the lengths of c, a, and b must be the equal or UB may result.
+/// the lengths of c, a, and b must be a multiple of 8 or UB will result.
+#[inline(always)]
+unsafe fn complex_mad_avx_512_unchecked(
+    c: &mut [Complex<f64>],
+    a: &[Complex<f64>],
+    b: &[Complex<f64>],
+) {
+    let mut i = 0;
+
+    // Complex<T> is declared as repr(C), so the location of re and im are guaranteed
+    // at address offsets 0 and 8 for Complex<f64>. This allows us to treat
+    // &[Complex<f64>] as &[f64] for the below asm snippet.
+    let a_ptr = a.as_ptr() as *const f64;
+    let b_ptr = b.as_ptr() as *const f64;
+    let c_ptr = c.as_ptr() as *mut f64;
+
+    // Each complex is 2 f64 values.
+    while i < 2 * c.len() {
+        // AVX512 isn't currently available on stable, so write some goddamn assembly
+        // code I guess ¯\_(ツ)_/¯
+        //
+        // This snippet reads 2 vectors of 4 complex numbers from a, b, c and computes
+        // stores the complex multiply-add result to c. Thus, it iterates over 16 f64
+        // elements from each vector at a time.
+        asm!(
+            // Load 2 zmm registers of Complex<f64> from a
+            "vmovupd zmm0, [{a_ptr}+8*{i}]",
+            "vmovupd zmm1, [{a_ptr}+8*{i}+64]",
+            "vshufpd zmm2, zmm0, zmm1, $0",   // Extract the re(a) into zmm2
+            "vshufpd zmm3, zmm0, zmm1, $255", // Extract the im(a) into zmm3
+            // Load 2 zmm registers of Complex<f64> from b
+            "vmovupd zmm0, [{b_ptr}+8*{i}]",
+            "vmovupd zmm1, [{b_ptr}+8*{i}+64]",
+            "vshufpd zmm4, zmm0, zmm1, $0",   // Extract the re(b) into zmm4
+            "vshufpd zmm5, zmm0, zmm1, $255", // Extract the im(b) into zmm5
+            // Load 2 zmm registers of Complex<f64> from c
+            "vmovupd zmm0, [{c_ptr}+8*{i}]",
+            "vmovupd zmm1, [{c_ptr}+8*{i}+64]",
+            "vshufpd zmm6, zmm0, zmm1, $0",   // Extract the re(c) into zmm6
+            "vshufpd zmm7, zmm0, zmm1, $255", // Extract the im(c) into zmm7
+            "vfmadd231pd zmm6, zmm2, zmm4",   // re(c) += re(a) * re(b)
+            "vfmadd231pd zmm7, zmm2, zmm5",   // im(c) += re(a) * im(b)
+            "vfnmadd231pd zmm6, zmm3, zmm5",  // re(c) -= im(a) * im(b)
+            "vfmadd231pd zmm7, zmm3, zmm4",   // im(c) += im(a) * re(b)
+            "vshufpd zmm0, zmm6, zmm7, $0",   // Repack the lower 4 Complex<f64>s
+            "vshufpd zmm1, zmm6, zmm7, $255", // Repack the upper 4 Complex<f64>s
+            "vmovupd [{c_ptr}+8*{i}], zmm0",    // Write the repacked values back.
+            "vmovupd [{c_ptr}+8*{i}+64], zmm1", // Write the repacked values back.
+            a_ptr = in(reg) a_ptr,
+            b_ptr = in(reg) b_ptr,
+            c_ptr = in(reg) c_ptr,
+            i = in(reg) i,
+            out("zmm0") _, // Indicate our clobbers
+            out("zmm1") _,
+            out("zmm2") _,
+            out("zmm3") _,
+            out("zmm4") _,
+            out("zmm5") _,
+            out("zmm6") _,
+            out("zmm7") _,
+        );
+
+        i += 16;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aligned_vec::AVec;
+    use rand::{thread_rng, RngCore};
+
+    use super::*;
+
+    fn random_u64s(len: usize) -> Vec<u64> {
+        (0..len).map(|_| thread_rng().next_u64()).collect()
+    }
+
+    #[test]
+    fn can_scalar_mad_complex_f64_slice() {
+        if !avx_512_available() {
+            return;
+        }
+
+        // Not a multiple of 8, so we also exercise the scalar remainder.
+        let len = 1030;
+
+        let vals_0 = (0..len).map(|x| x as f64).collect::<Vec<_>>();
+        let vals_1 = (len..2 * len).map(|x| x as f64).collect::<Vec<_>>();
+        let vals_2 = (2 * len..3 * len).map(|x| x as f64).collect::<Vec<_>>();
+
+        let a =
+            AVec::<Complex<f64>>::from_iter(64, vals_0.chunks(2).map(|x| Complex::new(x[0], x[1])));
+        let b =
+            AVec::<Complex<f64>>::from_iter(64, vals_1.chunks(2).map(|x| Complex::new(x[0], x[1])));
+        let mut expected =
+            AVec::<Complex<f64>>::from_iter(64, vals_2.chunks(2).map(|x| Complex::new(x[0], x[1])));
+
+        let mut actual = expected.clone();
+
+        unsafe { complex_mad(&mut actual, &a, &b) };
+        scalar::complex_mad(&mut expected, &a, &b);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn can_add_sub_torus_slice() {
+        if !avx_512_available() {
+            return;
+        }
+
+        let a = random_u64s(1027);
+        let b = random_u64s(1027);
+
+        let mut expected = vec![0; a.len()];
+        let mut actual = vec![0; a.len()];
+
+        scalar::torus_add(&mut expected, &a, &b);
+        unsafe { torus_add(&mut actual, &a, &b) };
+
+        assert_eq!(expected, actual);
+
+        scalar::torus_sub(&mut expected, &a, &b);
+        unsafe { torus_sub(&mut actual, &a, &b) };
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn can_scalar_mad_torus_slice() {
+        if !avx_512_available() {
+            return;
+        }
+
+        let a = random_u64s(1027);
+        let s = thread_rng().next_u64();
+
+        let mut expected = random_u64s(a.len());
+        let mut actual = expected.clone();
+
+        scalar::torus_scalar_mad(&mut expected, &a, s);
+        unsafe { torus_scalar_mad(&mut actual, &a, s) };
+
+        assert_eq!(expected, actual);
+    }
+}
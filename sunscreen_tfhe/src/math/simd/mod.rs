@@ -1,17 +1,201 @@
+//! Vectorized kernels with runtime dispatch. Each kernel has a portable
+//! implementation in `scalar` and, where the CPU supports them, AVX-512F
+//! (x86-64) and NEON (AArch64) implementations.
+
+use num::Complex;
+
+use crate::{Torus, TorusOps};
+
 mod scalar;
-#[cfg(not(all(
-    any(target_arch = "x86", target_arch = "x86_64"),
-    target_feature = "avx2"
-)))]
-pub use scalar::*;
-
-#[cfg(all(
-    any(target_arch = "x86", target_arch = "x86_64"),
-    target_feature = "avx2"
-))]
-mod avx2;
-#[cfg(all(
-    any(target_arch = "x86", target_arch = "x86_64"),
-    target_feature = "avx2"
-))]
-pub use avx2::*;
+
+#[cfg(target_arch = "x86_64")]
+mod avx512;
+
+#[cfg(target_arch = "aarch64")]
+mod neon;
+
+/// Compute vector `c += a * b` over &[Complex<f64>].
+///
+/// # Panics
+/// If `c.len() != a.len() != b.len()`
+#[inline(always)]
+pub fn complex_mad(c: &mut [Complex<f64>], a: &[Complex<f64>], b: &[Complex<f64>]) {
+    assert_eq!(c.len(), a.len());
+    assert_eq!(b.len(), a.len());
+
+    #[cfg(target_arch = "x86_64")]
+    if avx512::avx_512_available() {
+        return unsafe { avx512::complex_mad(c, a, b) };
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    if neon::neon_available() {
+        return unsafe { neon::complex_mad(c, a, b) };
+    }
+
+    scalar::complex_mad(c, a, b)
+}
+
+/// Compute vector `c = a + b` over the torus.
+///
+/// # Panics
+/// If `c.len() != a.len() != b.len()`
+#[inline(always)]
+pub fn torus_add<S: TorusOps>(c: &mut [Torus<S>], a: &[Torus<S>], b: &[Torus<S>]) {
+    assert_eq!(c.len(), a.len());
+    assert_eq!(b.len(), a.len());
+
+    if S::BITS != u64::BITS {
+        for ((c, a), b) in c.iter_mut().zip(a.iter()).zip(b.iter()) {
+            *c = a + b;
+        }
+
+        return;
+    }
+
+    let c = bytemuck::cast_slice_mut::<_, u64>(c);
+    let a = bytemuck::cast_slice::<_, u64>(a);
+    let b = bytemuck::cast_slice::<_, u64>(b);
+
+    #[cfg(target_arch = "x86_64")]
+    if avx512::avx_512_available() {
+        return unsafe { avx512::torus_add(c, a, b) };
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    if neon::neon_available() {
+        return unsafe { neon::torus_add(c, a, b) };
+    }
+
+    scalar::torus_add(c, a, b)
+}
+
+/// Compute vector `c = a - b` over the torus.
+///
+/// # Panics
+/// If `c.len() != a.len() != b.len()`
+#[inline(always)]
+pub fn torus_sub<S: TorusOps>(c: &mut [Torus<S>], a: &[Torus<S>], b: &[Torus<S>]) {
+    assert_eq!(c.len(), a.len());
+    assert_eq!(b.len(), a.len());
+
+    if S::BITS != u64::BITS {
+        for ((c, a), b) in c.iter_mut().zip(a.iter()).zip(b.iter()) {
+            *c = a - b;
+        }
+
+        return;
+    }
+
+    let c = bytemuck::cast_slice_mut::<_, u64>(c);
+    let a = bytemuck::cast_slice::<_, u64>(a);
+    let b = bytemuck::cast_slice::<_, u64>(b);
+
+    #[cfg(target_arch = "x86_64")]
+    if avx512::avx_512_available() {
+        return unsafe { avx512::torus_sub(c, a, b) };
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    if neon::neon_available() {
+        return unsafe { neon::torus_sub(c, a, b) };
+    }
+
+    scalar::torus_sub(c, a, b)
+}
+
+/// Compute vector `c += a * s` over the torus, where `s` is an integer.
+///
+/// # Panics
+/// If `c.len() != a.len()`
+#[inline(always)]
+pub fn torus_scalar_mad<S: TorusOps>(c: &mut [Torus<S>], a: &[Torus<S>], s: S) {
+    assert_eq!(c.len(), a.len());
+
+    if S::BITS != u64::BITS {
+        let s = &s;
+
+        for (c, a) in c.iter_mut().zip(a.iter()) {
+            *c += a * s;
+        }
+
+        return;
+    }
+
+    let c = bytemuck::cast_slice_mut::<_, u64>(c);
+    let a = bytemuck::cast_slice::<_, u64>(a);
+    let s = s.to_u64();
+
+    #[cfg(target_arch = "x86_64")]
+    if avx512::avx_512_available() {
+        return unsafe { avx512::torus_scalar_mad(c, a, s) };
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    if neon::neon_available() {
+        return unsafe { neon::torus_scalar_mad(c, a, s) };
+    }
+
+    scalar::torus_scalar_mad(c, a, s)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{thread_rng, RngCore};
+
+    use super::*;
+
+    #[test]
+    fn torus_kernels_match_scalar() {
+        let a = (0..1027)
+            .map(|_| Torus::from(thread_rng().next_u64()))
+            .collect::<Vec<_>>();
+        let b = (0..1027)
+            .map(|_| Torus::from(thread_rng().next_u64()))
+            .collect::<Vec<_>>();
+        let s = thread_rng().next_u64();
+
+        let mut actual = vec![Torus::from(0u64); a.len()];
+
+        torus_add(&mut actual, &a, &b);
+        assert!(actual
+            .iter()
+            .zip(a.iter().zip(b.iter()))
+            .all(|(c, (a, b))| *c == a + b));
+
+        torus_sub(&mut actual, &a, &b);
+        assert!(actual
+            .iter()
+            .zip(a.iter().zip(b.iter()))
+            .all(|(c, (a, b))| *c == a - b));
+
+        let mut expected = actual.clone();
+
+        torus_scalar_mad(&mut actual, &a, s);
+
+        for (c, a) in expected.iter_mut().zip(a.iter()) {
+            *c += a * &s;
+        }
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn torus_kernels_support_u32() {
+        let a = (0..17u32).map(Torus::from).collect::<Vec<_>>();
+        let b = (0..17u32)
+            .map(|x| Torus::from(u32::MAX - x))
+            .collect::<Vec<_>>();
+
+        let mut actual = vec![Torus::from(0u32); a.len()];
+
+        torus_add(&mut actual, &a, &b);
+        assert!(actual.iter().all(|x| *x == Torus::from(u32::MAX)));
+
+        torus_scalar_mad(&mut actual, &a, 2);
+        assert!(actual
+            .iter()
+            .enumerate()
+            .all(|(i, x)| *x == Torus::from(u32::MAX.wrapping_add(2 * i as u32))));
+    }
+}
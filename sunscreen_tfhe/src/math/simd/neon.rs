@@ -0,0 +1,227 @@
+use num::Complex;
+
+use core::arch::aarch64::*;
+use std::sync::OnceLock;
+
+use super::scalar;
+
+#[inline(always)]
+/// Whether the CPU supports the NEON instructions used by the kernels in this
+/// module.
+///
+/// # Remarks
+/// NEON is part of the AArch64 baseline, but we check anyway so the kernels
+/// remain sound on exotic targets that disable it.
+pub fn neon_available() -> bool {
+    static NEON_AVAILABLE: OnceLock<bool> = OnceLock::new();
+
+    *NEON_AVAILABLE.get_or_init(|| std::arch::is_aarch64_feature_detected!("neon"))
+}
+
+/// Compute vector `c += a * b` over &[Complex<f64>].
+///
+/// # Remarks
+/// A `float64x2_t` holds exactly one `Complex<f64>`, which is declared
+/// `repr(C)` with `re` followed by `im`. We compute
+/// `c += re(a) * [re(b), im(b)] + im(a) * [-im(b), re(b)]`.
+///
+/// # Safety
+/// The CPU must support NEON (see [`neon_available`]).
+///
+/// # Panics
+/// If `c.len() != a.len() != b.len()`
+#[target_feature(enable = "neon")]
+pub unsafe fn complex_mad(c: &mut [Complex<f64>], a: &[Complex<f64>], b: &[Complex<f64>]) {
+    assert_eq!(c.len(), a.len());
+    assert_eq!(b.len(), a.len());
+
+    let a_ptr = a.as_ptr() as *const f64;
+    let b_ptr = b.as_ptr() as *const f64;
+    let c_ptr = c.as_mut_ptr() as *mut f64;
+
+    let neg_re = [-1.0f64, 1.0];
+    let neg_re = vld1q_f64(neg_re.as_ptr());
+
+    for i in 0..c.len() {
+        let a = vld1q_f64(a_ptr.add(2 * i));
+        let b = vld1q_f64(b_ptr.add(2 * i));
+        let c = vld1q_f64(c_ptr.add(2 * i));
+
+        // [-im(b), re(b)]
+        let b_rot = vmulq_f64(vextq_f64::<1>(b, b), neg_re);
+
+        let c = vfmaq_laneq_f64::<0>(c, b, a);
+        let c = vfmaq_laneq_f64::<1>(c, b_rot, a);
+
+        vst1q_f64(c_ptr.add(2 * i), c);
+    }
+}
+
+/// Compute vector `c = a + b` over &[u64] with wrapping arithmetic.
+///
+/// # Safety
+/// The CPU must support NEON (see [`neon_available`]).
+///
+/// # Panics
+/// If `c.len() != a.len() != b.len()`
+#[target_feature(enable = "neon")]
+pub unsafe fn torus_add(c: &mut [u64], a: &[u64], b: &[u64]) {
+    assert_eq!(c.len(), a.len());
+    assert_eq!(b.len(), a.len());
+
+    let split = a.len() - a.len() % 2;
+
+    let a_ptr = a.as_ptr();
+    let b_ptr = b.as_ptr();
+    let c_ptr = c.as_mut_ptr();
+
+    for i in (0..split).step_by(2) {
+        let sum = vaddq_u64(vld1q_u64(a_ptr.add(i)), vld1q_u64(b_ptr.add(i)));
+
+        vst1q_u64(c_ptr.add(i), sum);
+    }
+
+    scalar::torus_add(&mut c[split..], &a[split..], &b[split..]);
+}
+
+/// Compute vector `c = a - b` over &[u64] with wrapping arithmetic.
+///
+/// # Safety
+/// The CPU must support NEON (see [`neon_available`]).
+///
+/// # Panics
+/// If `c.len() != a.len() != b.len()`
+#[target_feature(enable = "neon")]
+pub unsafe fn torus_sub(c: &mut [u64], a: &[u64], b: &[u64]) {
+    assert_eq!(c.len(), a.len());
+    assert_eq!(b.len(), a.len());
+
+    let split = a.len() - a.len() % 2;
+
+    let a_ptr = a.as_ptr();
+    let b_ptr = b.as_ptr();
+    let c_ptr = c.as_mut_ptr();
+
+    for i in (0..split).step_by(2) {
+        let diff = vsubq_u64(vld1q_u64(a_ptr.add(i)), vld1q_u64(b_ptr.add(i)));
+
+        vst1q_u64(c_ptr.add(i), diff);
+    }
+
+    scalar::torus_sub(&mut c[split..], &a[split..], &b[split..]);
+}
+
+/// Compute vector `c += a * s` over &[u64] with wrapping arithmetic.
+///
+/// # Remarks
+/// NEON has no 64-bit multiply, so we assemble the low 64 bits of the product
+/// from 32-bit widening multiplies as
+/// `lo(a) * lo(s) + ((hi(a) * lo(s) + lo(a) * hi(s)) << 32)`.
+///
+/// # Safety
+/// The CPU must support NEON (see [`neon_available`]).
+///
+/// # Panics
+/// If `c.len() != a.len()`
+#[target_feature(enable = "neon")]
+pub unsafe fn torus_scalar_mad(c: &mut [u64], a: &[u64], s: u64) {
+    assert_eq!(c.len(), a.len());
+
+    let split = a.len() - a.len() % 2;
+
+    let s_lo = s as u32;
+    let s_hi = (s >> 32) as u32;
+
+    let a_ptr = a.as_ptr();
+    let c_ptr = c.as_mut_ptr();
+
+    for i in (0..split).step_by(2) {
+        let a = vld1q_u64(a_ptr.add(i));
+
+        let a_lo = vmovn_u64(a);
+        let a_hi = vshrn_n_u64::<32>(a);
+
+        let cross = vmlal_n_u32(vmull_n_u32(a_hi, s_lo), a_lo, s_hi);
+        let prod = vaddq_u64(vmull_n_u32(a_lo, s_lo), vshlq_n_u64::<32>(cross));
+
+        vst1q_u64(c_ptr.add(i), vaddq_u64(vld1q_u64(c_ptr.add(i)), prod));
+    }
+
+    scalar::torus_scalar_mad(&mut c[split..], &a[split..], s);
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{thread_rng, RngCore};
+
+    use super::*;
+
+    fn random_u64s(len: usize) -> Vec<u64> {
+        (0..len).map(|_| thread_rng().next_u64()).collect()
+    }
+
+    #[test]
+    fn can_mad_complex_f64_slice() {
+        if !neon_available() {
+            return;
+        }
+
+        let vals = (0..3 * 1030).map(|x| x as f64).collect::<Vec<_>>();
+
+        let mut chunks = vals
+            .chunks(2)
+            .map(|x| Complex::new(x[0], x[1]))
+            .collect::<Vec<_>>();
+        let b = chunks.split_off(2 * 515);
+        let a = chunks.split_off(515);
+
+        let mut expected = chunks;
+        let mut actual = expected.clone();
+
+        unsafe { complex_mad(&mut actual, &a, &b) };
+        scalar::complex_mad(&mut expected, &a, &b);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn can_add_sub_torus_slice() {
+        if !neon_available() {
+            return;
+        }
+
+        let a = random_u64s(1027);
+        let b = random_u64s(1027);
+
+        let mut expected = vec![0; a.len()];
+        let mut actual = vec![0; a.len()];
+
+        scalar::torus_add(&mut expected, &a, &b);
+        unsafe { torus_add(&mut actual, &a, &b) };
+
+        assert_eq!(expected, actual);
+
+        scalar::torus_sub(&mut expected, &a, &b);
+        unsafe { torus_sub(&mut actual, &a, &b) };
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn can_scalar_mad_torus_slice() {
+        if !neon_available() {
+            return;
+        }
+
+        let a = random_u64s(1027);
+        let s = thread_rng().next_u64();
+
+        let mut expected = random_u64s(a.len());
+        let mut actual = expected.clone();
+
+        scalar::torus_scalar_mad(&mut expected, &a, s);
+        unsafe { torus_scalar_mad(&mut actual, &a, s) };
+
+        assert_eq!(expected, actual);
+    }
+}
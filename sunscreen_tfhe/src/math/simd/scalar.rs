@@ -6,6 +6,24 @@ pub fn complex_mad(c: &mut [Complex<f64>], a: &[Complex<f64>], b: &[Complex<f64>
     }
 }
 
+pub fn torus_add(c: &mut [u64], a: &[u64], b: &[u64]) {
+    for ((c, a), b) in c.iter_mut().zip(a.iter()).zip(b.iter()) {
+        *c = a.wrapping_add(*b);
+    }
+}
+
+pub fn torus_sub(c: &mut [u64], a: &[u64], b: &[u64]) {
+    for ((c, a), b) in c.iter_mut().zip(a.iter()).zip(b.iter()) {
+        *c = a.wrapping_sub(*b);
+    }
+}
+
+pub fn torus_scalar_mad(c: &mut [u64], a: &[u64], s: u64) {
+    for (c, a) in c.iter_mut().zip(a.iter()) {
+        *c = c.wrapping_add(a.wrapping_mul(s));
+    }
+}
+
 #[cfg(test)]
 mod test {
     use rand::{thread_rng, RngCore};
@@ -6,3 +6,9 @@ pub use circuit_bootstrapping::*;
 
 mod programmable_bootstrapping;
 pub use programmable_bootstrapping::*;
+
+mod tree_bootstrapping;
+pub use tree_bootstrapping::*;
+
+mod vertical_lookup;
+pub use vertical_lookup::*;
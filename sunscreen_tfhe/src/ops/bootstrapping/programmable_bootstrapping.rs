@@ -2,12 +2,14 @@ use num::Complex;
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 
 use crate::{
-    dst::FromMutSlice,
+    dst::{FromMutSlice, OverlaySize},
     entities::{
-        BivariateLookupTableRef, BootstrapKeyFftRef, BootstrapKeyRef, GlweCiphertextRef,
-        GlweSecretKeyRef, LweCiphertextRef, LweSecretKeyRef, Polynomial, PolynomialRef,
+        BivariateLookupTableRef, BootstrapKeyFftRef, BootstrapKeyNttRef, BootstrapKeyRef,
+        GgswCiphertextFftRef, GgswCiphertextNttRef, GlweCiphertextRef, GlweSecretKeyRef,
+        LweCiphertextListRef, LweCiphertextRef, LweSecretKeyRef, Polynomial, PolynomialRef,
         UnivariateLookupTableRef,
     },
+    math::goldilocks_field::Fg,
     ops::{
         bootstrapping::rotate_glwe_positive_monomial_negacyclic,
         ciphertext::{
@@ -16,6 +18,7 @@ use crate::{
         },
         encryption::encrypt_ggsw_ciphertext_scalar,
         fft_ops::cmux,
+        ntt_ops::cmux_ntt,
     },
     scratch::allocate_scratch_ref,
     CarryBits, GlweDef, LweDef, PlaintextBits, RadixDecomposition, Torus, TorusOps,
@@ -181,6 +184,32 @@ pub(crate) fn generate_lut<S, F>(
     c.rotate_left(stride / 2);
 }
 
+/// Fills `output` with a lookup table whose entry for input `i` is the torus
+/// element `values[i]`, using the same layout as [`generate_lut`].
+///
+/// # Remarks
+/// This map is linear in `values`, so it may be used with
+/// [`public_functional_keyswitch`](crate::ops::keyswitch::public_functional_keyswitch::public_functional_keyswitch)
+/// to build lookup tables with encrypted entries.
+pub(crate) fn spread_lut_values<S: TorusOps>(
+    output: &mut PolynomialRef<Torus<S>>,
+    values: &[Torus<S>],
+) {
+    let stride = output.coeffs().len() / values.len();
+    let c = output.coeffs_mut();
+
+    for (block, v) in c.chunks_mut(stride).zip(values.iter()) {
+        block.iter_mut().for_each(|c| *c = *v);
+    }
+
+    // Negate the first half of the first entry and rotate, as in generate_lut.
+    c[0..stride / 2].iter_mut().for_each(|c| {
+        *c = num::traits::WrappingNeg::wrapping_neg(c);
+    });
+
+    c.rotate_left(stride / 2);
+}
+
 /// Programmable bootstrapping with a univariate function.
 ///
 /// The LUT this is a table that maps two inputs into a single output.  For
@@ -314,6 +343,44 @@ pub fn programmable_bootstrap_univariate<S>(
     sample_extract(output, glwe, 0, glwe_params);
 }
 
+/// The NTT variant of [`programmable_bootstrap_univariate`], which uses a
+/// [`BootstrapKeyNtt`](crate::entities::BootstrapKeyNtt) to perform the
+/// blind rotation with exact arithmetic in the Goldilocks field.
+///
+/// # Remarks
+/// Floating-point rounding in the FFT used by
+/// [`programmable_bootstrap_univariate`] adds noise that grows with the
+/// decomposition base and can be significant for 64-bit tori. The NTT variant
+/// adds no such noise in exchange for a slower blind rotation. Create the
+/// key with [`BootstrapKeyRef::ntt`].
+pub fn programmable_bootstrap_univariate_ntt<S>(
+    output: &mut LweCiphertextRef<S>,
+    input: &LweCiphertextRef<S>,
+    lut: &UnivariateLookupTableRef<S>,
+    bootstrap_key: &BootstrapKeyNttRef<Fg>,
+    lwe_params: &LweDef,
+    glwe_params: &GlweDef,
+    radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    allocate_scratch_ref!(glwe, GlweCiphertextRef<S>, (glwe_params.dim));
+
+    generalized_programmable_bootstrap_ntt(
+        glwe,
+        input,
+        lut,
+        bootstrap_key,
+        0,
+        0,
+        lwe_params,
+        glwe_params,
+        radix,
+    );
+
+    sample_extract(output, glwe, 0, glwe_params);
+}
+
 #[allow(clippy::too_many_arguments)]
 /// A generalized version of programmable bootstrapping.
 /// Computes a function `lut` of the encrypted `input`.
@@ -357,6 +424,116 @@ pub fn generalized_programmable_bootstrap<S>(
     input.assert_valid(lwe_params);
     output.assert_valid(glwe_params);
 
+    blind_rotate_lut(
+        output,
+        input,
+        lut,
+        bootstrap_key.rows(glwe_params, radix),
+        log_chi,
+        log_v,
+        lwe_params,
+        glwe_params,
+        radix,
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
+/// The NTT variant of [`generalized_programmable_bootstrap`], which uses a
+/// [`BootstrapKeyNtt`](crate::entities::BootstrapKeyNtt) to perform the
+/// blind rotation with exact arithmetic in the Goldilocks field.
+///
+/// # Remarks
+/// See [`generalized_programmable_bootstrap`] for details.
+pub fn generalized_programmable_bootstrap_ntt<S>(
+    output: &mut GlweCiphertextRef<S>,
+    input: &LweCiphertextRef<S>,
+    lut: &UnivariateLookupTableRef<S>,
+    bootstrap_key: &BootstrapKeyNttRef<Fg>,
+    log_chi: u32,
+    log_v: u32,
+    lwe_params: &LweDef,
+    glwe_params: &GlweDef,
+    radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    lwe_params.assert_valid();
+    glwe_params.assert_valid();
+    radix.assert_valid::<S>();
+    bootstrap_key.assert_valid(lwe_params, glwe_params, radix);
+    lut.assert_valid(glwe_params);
+    input.assert_valid(lwe_params);
+    output.assert_valid(glwe_params);
+
+    blind_rotate_lut(
+        output,
+        input,
+        lut,
+        bootstrap_key.rows(glwe_params, radix),
+        log_chi,
+        log_v,
+        lwe_params,
+        glwe_params,
+        radix,
+    );
+}
+
+/// A GGSW ciphertext in a representation that can select between two GLWE
+/// ciphertexts.
+trait CmuxSelector {
+    fn cmux<S: TorusOps>(
+        &self,
+        c: &mut GlweCiphertextRef<S>,
+        d_0: &GlweCiphertextRef<S>,
+        d_1: &GlweCiphertextRef<S>,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+    );
+}
+
+impl CmuxSelector for GgswCiphertextFftRef<Complex<f64>> {
+    fn cmux<S: TorusOps>(
+        &self,
+        c: &mut GlweCiphertextRef<S>,
+        d_0: &GlweCiphertextRef<S>,
+        d_1: &GlweCiphertextRef<S>,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+    ) {
+        cmux(c, d_0, d_1, self, params, radix);
+    }
+}
+
+impl CmuxSelector for GgswCiphertextNttRef<Fg> {
+    fn cmux<S: TorusOps>(
+        &self,
+        c: &mut GlweCiphertextRef<S>,
+        d_0: &GlweCiphertextRef<S>,
+        d_1: &GlweCiphertextRef<S>,
+        params: &GlweDef,
+        radix: &RadixDecomposition,
+    ) {
+        cmux_ntt(c, d_0, d_1, self, params, radix);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+/// Modulus switch `input` and blind rotate `lut` by its phase using the
+/// bootstrap key rows `bsk_rows`. The caller validates the arguments.
+fn blind_rotate_lut<'a, S, G>(
+    output: &mut GlweCiphertextRef<S>,
+    input: &LweCiphertextRef<S>,
+    lut: &UnivariateLookupTableRef<S>,
+    bsk_rows: impl Iterator<Item = &'a G>,
+    log_chi: u32,
+    log_v: u32,
+    lwe_params: &LweDef,
+    glwe_params: &GlweDef,
+    radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+    G: CmuxSelector + ?Sized + 'a,
+{
     // Steps:
     // 1. Modulus switch the ciphertext to 2N.
     // 2. Use a cmux tree to blind rotate V using the elements of the bootstrap key (the input LWE secret key bits).
@@ -390,7 +567,7 @@ pub fn generalized_programmable_bootstrap<S>(
 
     // Perform the cmux tree from the bootstrap key with the relation
     // V_n = V_{n-1} ^ X^{a_{n-1} s_{n-1}}
-    for (a_i, index_select) in ct_a.iter().zip(bootstrap_key.rows(glwe_params, radix)) {
+    for (a_i, index_select) in ct_a.iter().zip(bsk_rows) {
         let tmp = output.to_owned();
 
         // This operation performs a copy so the rotated_ct doesn't need to be
@@ -402,7 +579,71 @@ pub fn generalized_programmable_bootstrap<S>(
             glwe_params,
         );
 
-        cmux(output, &tmp, rotated_ct, index_select, glwe_params, radix);
+        index_select.cmux(output, &tmp, rotated_ct, glwe_params, radix);
+    }
+}
+
+/// Programmable bootstrapping that evaluates several univariate functions of
+/// `input` with a single blind rotation, writing the `i`-th function's result
+/// to the `i`-th ciphertext in `output`.
+///
+/// # Remarks
+/// Create `lut` with
+/// [`UnivariateLookupTable::trivivial_multifunctional`](crate::entities::UnivariateLookupTable::trivivial_multifunctional).
+/// `output` must contain one ciphertext per function used to create `lut`.
+///
+/// Blind rotation dominates the cost of bootstrapping, so evaluating `v`
+/// functions this way costs about as much as a single
+/// [`programmable_bootstrap_univariate`]. In exchange, modulus switching
+/// discards `ceil(log2(v))` additional bits of `input`, which increases the
+/// probability of a decryption failure.
+///
+/// As with [`programmable_bootstrap_univariate`], the outputs are encrypted
+/// under the LWE key extracted from the GLWE secret key used for the
+/// bootstrapping key.
+///
+/// # Panics
+/// If `output` is empty or doesn't contain a whole number of ciphertexts under
+/// `glwe_params.as_lwe_def()`.
+/// If `lwe_params`, `glwe_params`, or `radix` are invalid.
+/// If `input`, `lut` or `bootstrap_key` don't correspond to these parameters.
+pub fn programmable_bootstrap_many_lut<S>(
+    output: &mut LweCiphertextListRef<S>,
+    input: &LweCiphertextRef<S>,
+    lut: &UnivariateLookupTableRef<S>,
+    bootstrap_key: &BootstrapKeyFftRef<Complex<f64>>,
+    lwe_params: &LweDef,
+    glwe_params: &GlweDef,
+    radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    let extracted_lwe = glwe_params.as_lwe_def();
+    let ct_len = LweCiphertextRef::<S>::size(extracted_lwe.dim);
+
+    let num_fns = output.as_slice().len() / ct_len;
+
+    assert!(num_fns > 0);
+    assert_eq!(output.as_slice().len() % ct_len, 0);
+
+    let log_v = num_fns.next_power_of_two().ilog2();
+
+    allocate_scratch_ref!(glwe, GlweCiphertextRef<S>, (glwe_params.dim));
+
+    generalized_programmable_bootstrap(
+        glwe,
+        input,
+        lut,
+        bootstrap_key,
+        0,
+        log_v,
+        lwe_params,
+        glwe_params,
+        radix,
+    );
+
+    for (i, ct) in output.ciphertexts_mut(&extracted_lwe).enumerate() {
+        sample_extract(ct, glwe, i, glwe_params);
     }
 }
 
@@ -630,7 +871,7 @@ mod tests {
             encryption::{decrypt_ggsw_ciphertext, encrypt_lwe_ciphertext},
             keyswitch::lwe_keyswitch_key::generate_keyswitch_key_lwe,
         },
-        RoundedDiv, GLWE_1_1024_80, LWE_512_80,
+        RadixCount, RadixLog, RoundedDiv, GLWE_1_1024_80, LWE_512_80,
     };
 
     use super::*;
@@ -785,6 +1026,51 @@ mod tests {
         bootstrap_helper(|x| (x + 3) % 8);
     }
 
+    #[test]
+    fn can_bootstrap_ntt() {
+        let bits = PlaintextBits(3);
+        let lwe = TEST_LWE_DEF_1;
+        let glwe = GLWE_1_1024_80;
+        let radix = RadixDecomposition {
+            count: RadixCount(2),
+            radix_log: RadixLog(20),
+        };
+
+        let lwe_sk = keygen::generate_binary_lwe_sk(&lwe);
+        let glwe_sk = keygen::generate_binary_glwe_sk(&glwe);
+
+        let bsk = keygen::generate_bootstrapping_key(&lwe_sk, &glwe_sk, &lwe, &glwe, &radix);
+        let bsk = fft::ntt_bootstrap_key(&bsk, &lwe, &glwe, &radix);
+
+        let map = |x| (x * 3 + 1) % 8;
+        let lut = UnivariateLookupTable::trivial_from_fn(map, &glwe, bits);
+
+        for msg in 0..(1 << bits.0) {
+            let ct = encryption::encrypt_lwe_secret(msg, &lwe_sk, &lwe, PlaintextBits(bits.0 + 1));
+
+            let mut output = LweCiphertext::new(&glwe.as_lwe_def());
+
+            programmable_bootstrap_univariate_ntt(
+                &mut output,
+                &ct,
+                &lut,
+                &bsk,
+                &lwe,
+                &glwe,
+                &radix,
+            );
+
+            let actual = encryption::decrypt_lwe(
+                &output,
+                glwe_sk.to_lwe_secret_key(),
+                &glwe.as_lwe_def(),
+                bits,
+            );
+
+            assert_eq!(actual, map(msg));
+        }
+    }
+
     fn bivariate_bootstrap_helper(map: impl Fn(u64, u64) -> u64) {
         let lwe = TEST_LWE_DEF_1;
         let glwe = TEST_GLWE_DEF_1;
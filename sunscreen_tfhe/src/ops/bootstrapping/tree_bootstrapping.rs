@@ -0,0 +1,271 @@
+use num::Complex;
+
+use crate::{
+    dst::FromMutSlice,
+    entities::{
+        BootstrapKeyFftRef, LweCiphertext, LweCiphertextListRef, LweCiphertextRef,
+        PublicFunctionalKeyswitchKeyRef, UnivariateLookupTableRef,
+    },
+    ops::bootstrapping::{programmable_bootstrap_many_lut, programmable_bootstrap_univariate},
+    scratch::allocate_scratch_ref,
+    GlweDef, LweDef, PlaintextBits, RadixDecomposition, TorusOps,
+};
+
+#[allow(clippy::too_many_arguments)]
+/// Programmable bootstrapping of a function of several blocks, each encrypted
+/// in its own ciphertext. This allows evaluating functions whose combined
+/// input is too wide for a single lookup table.
+///
+/// # Remarks
+/// `inputs` contains the blocks least-significant first, each encrypting
+/// `plaintext_bits` of message with a padding bit. `leaf_luts` must come from
+/// [`UnivariateLookupTable::trivial_tree_leaves`](crate::entities::UnivariateLookupTable::trivial_tree_leaves) with the same
+/// `inputs.len()`, `log_v` and `plaintext_bits`.
+///
+/// This implements the tree-based approach of "Revisiting the functional
+/// bootstrap in TFHE" by Guimarães et al. For `k` blocks and `p =
+/// 2^plaintext_bits`:
+///
+/// 1. For each of the `p^(k-1)` values of the remaining blocks, we bootstrap
+///    `inputs[0]` through the corresponding leaf function, evaluating
+///    `2^log_v` leaves per blind rotation with
+///    [`programmable_bootstrap_many_lut`].
+/// 2. For each block `i > 0`, we pack every group of `p` results that differ
+///    only in block `i` into an encrypted lookup table (see
+///    [`UnivariateLookupTableRef::fill_from_lwes`]) and bootstrap `inputs[i]`
+///    through it. This selects the result for the encrypted value of block
+///    `i`, dividing the number of results by `p`.
+///
+/// After the last block, a single result remains, which we write to `output`.
+///
+/// `pksk` is a packing keyswitch key from the LWE key extracted from the GLWE
+/// secret key of `bootstrap_key` to that GLWE secret key, using
+/// `pksk_radix`. `output` is encrypted under the extracted LWE key, as with
+/// [`programmable_bootstrap_univariate`].
+///
+/// # Panics
+/// If `inputs` is empty.
+/// If `leaf_luts` doesn't contain `2^(plaintext_bits * (inputs.len() - 1) - log_v)` tables.
+/// If `lwe_params`, `glwe_params`, `radix` or `pksk_radix` are invalid.
+/// If `inputs`, `output`, `bootstrap_key`, `pksk` or any of `leaf_luts`
+/// don't correspond to these parameters.
+pub fn tree_programmable_bootstrap<S>(
+    output: &mut LweCiphertextRef<S>,
+    inputs: &[&LweCiphertextRef<S>],
+    leaf_luts: &[&UnivariateLookupTableRef<S>],
+    bootstrap_key: &BootstrapKeyFftRef<Complex<f64>>,
+    pksk: &PublicFunctionalKeyswitchKeyRef<S>,
+    log_v: u32,
+    lwe_params: &LweDef,
+    glwe_params: &GlweDef,
+    radix: &RadixDecomposition,
+    pksk_radix: &RadixDecomposition,
+    plaintext_bits: PlaintextBits,
+) where
+    S: TorusOps,
+{
+    assert!(!inputs.is_empty());
+
+    let extracted_lwe = glwe_params.as_lwe_def();
+    let p = 0x1usize << plaintext_bits.0;
+    let num_leaves = 0x1usize << (plaintext_bits.0 as usize * (inputs.len() - 1));
+    let leaves_per_lut = 0x1usize << log_v;
+
+    assert_eq!(leaf_luts.len() * leaves_per_lut, num_leaves);
+    output.assert_valid(&extracted_lwe);
+
+    // 1. Evaluate every leaf on the least-significant block.
+    allocate_scratch_ref!(
+        leaf_outputs,
+        LweCiphertextListRef<S>,
+        (extracted_lwe.dim, leaves_per_lut)
+    );
+
+    let mut results = Vec::with_capacity(num_leaves);
+
+    for lut in leaf_luts {
+        programmable_bootstrap_many_lut(
+            leaf_outputs,
+            inputs[0],
+            lut,
+            bootstrap_key,
+            lwe_params,
+            glwe_params,
+            radix,
+        );
+
+        results.extend(
+            leaf_outputs
+                .ciphertexts(&extracted_lwe)
+                .map(|ct| ct.to_owned()),
+        );
+    }
+
+    // 2. Select among the results using each remaining block.
+    allocate_scratch_ref!(lut, UnivariateLookupTableRef<S>, (glwe_params.dim));
+
+    for input in inputs.iter().skip(1) {
+        results = results
+            .chunks(p)
+            .map(|values| {
+                let values = values.iter().map(|x| x.as_ref()).collect::<Vec<_>>();
+
+                lut.fill_from_lwes(&values, pksk, &extracted_lwe, glwe_params, pksk_radix);
+
+                let mut selected = LweCiphertext::new(&extracted_lwe);
+
+                programmable_bootstrap_univariate(
+                    &mut selected,
+                    input,
+                    lut,
+                    bootstrap_key,
+                    lwe_params,
+                    glwe_params,
+                    radix,
+                );
+
+                selected
+            })
+            .collect();
+    }
+
+    assert_eq!(results.len(), 1);
+
+    output.as_mut_slice().copy_from_slice(results[0].as_slice());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        entities::{LweCiphertextList, UnivariateLookupTable},
+        high_level::{encryption, fft, keygen, TEST_GLWE_DEF_1, TEST_LWE_DEF_1, TEST_RADIX},
+        PlaintextBits, GLWE_1_1024_80,
+    };
+
+    use super::*;
+
+    fn tree_bootstrap_helper(
+        map: impl Fn(&[u64]) -> u64,
+        num_blocks: usize,
+        log_v: u32,
+        plaintext_bits: PlaintextBits,
+    ) {
+        let lwe = TEST_LWE_DEF_1;
+        let glwe = TEST_GLWE_DEF_1;
+        let radix = TEST_RADIX;
+        let padded_bits = PlaintextBits(plaintext_bits.0 + 1);
+
+        let lwe_sk = keygen::generate_binary_lwe_sk(&lwe);
+        let glwe_sk = keygen::generate_binary_glwe_sk(&glwe);
+
+        let bsk = keygen::generate_bootstrapping_key(&lwe_sk, &glwe_sk, &lwe, &glwe, &radix);
+        let bsk = fft::fft_bootstrap_key(&bsk, &lwe, &glwe, &radix);
+
+        let pksk = keygen::generate_packing_ksk(
+            glwe_sk.to_lwe_secret_key(),
+            &glwe_sk,
+            &glwe.as_lwe_def(),
+            &glwe,
+            &radix,
+        );
+
+        let leaves = UnivariateLookupTable::<u64>::trivial_tree_leaves(
+            &map,
+            num_blocks,
+            log_v,
+            &glwe,
+            plaintext_bits,
+        );
+        let leaf_refs = leaves.iter().map(|x| x.as_ref()).collect::<Vec<_>>();
+
+        let p = 0x1 << plaintext_bits.0;
+
+        for x in 0..(p as u64).pow(num_blocks as u32) {
+            let blocks = (0..num_blocks)
+                .map(|i| (x / (p as u64).pow(i as u32)) % p as u64)
+                .collect::<Vec<_>>();
+
+            let cts = blocks
+                .iter()
+                .map(|b| encryption::encrypt_lwe_secret(*b, &lwe_sk, &lwe, padded_bits))
+                .collect::<Vec<_>>();
+            let ct_refs = cts.iter().map(|x| x.as_ref()).collect::<Vec<_>>();
+
+            let mut output = LweCiphertext::new(&glwe.as_lwe_def());
+
+            tree_programmable_bootstrap(
+                &mut output,
+                &ct_refs,
+                &leaf_refs,
+                &bsk,
+                &pksk,
+                log_v,
+                &lwe,
+                &glwe,
+                &radix,
+                &radix,
+                plaintext_bits,
+            );
+
+            let actual = encryption::decrypt_lwe(
+                &output,
+                glwe_sk.to_lwe_secret_key(),
+                &glwe.as_lwe_def(),
+                plaintext_bits,
+            );
+
+            assert_eq!(actual, map(&blocks), "blocks {blocks:?}");
+        }
+    }
+
+    #[test]
+    fn can_tree_bootstrap_two_blocks() {
+        tree_bootstrap_helper(|x| (x[0] + 3 * x[1]) % 4, 2, 0, PlaintextBits(2));
+    }
+
+    #[test]
+    fn can_tree_bootstrap_with_many_lut_leaves() {
+        // Majority of 3 bits.
+        tree_bootstrap_helper(
+            |x| (x.iter().sum::<u64>() >= 2) as u64,
+            3,
+            1,
+            PlaintextBits(1),
+        );
+    }
+
+    #[test]
+    fn can_bootstrap_many_lut() {
+        let lwe = TEST_LWE_DEF_1;
+        let glwe = GLWE_1_1024_80;
+        let radix = TEST_RADIX;
+        let bits = PlaintextBits(2);
+
+        let lwe_sk = keygen::generate_binary_lwe_sk(&lwe);
+        let glwe_sk = keygen::generate_binary_glwe_sk(&glwe);
+
+        let bsk = keygen::generate_bootstrapping_key(&lwe_sk, &glwe_sk, &lwe, &glwe, &radix);
+        let bsk = fft::fft_bootstrap_key(&bsk, &lwe, &glwe, &radix);
+
+        let maps = [|x| x, |x| (x + 1) % 4, |x| (x * 3) % 4];
+        let lut = UnivariateLookupTable::<u64>::trivivial_multifunctional(&maps, &glwe, bits);
+
+        for x in 0..4 {
+            let input = encryption::encrypt_lwe_secret(x, &lwe_sk, &lwe, PlaintextBits(3));
+            let mut output = LweCiphertextList::new(&glwe.as_lwe_def(), maps.len());
+
+            programmable_bootstrap_many_lut(&mut output, &input, &lut, &bsk, &lwe, &glwe, &radix);
+
+            for (ct, map) in output.ciphertexts(&glwe.as_lwe_def()).zip(maps.iter()) {
+                let actual = encryption::decrypt_lwe(
+                    ct,
+                    glwe_sk.to_lwe_secret_key(),
+                    &glwe.as_lwe_def(),
+                    bits,
+                );
+
+                assert_eq!(actual, map(x));
+            }
+        }
+    }
+}
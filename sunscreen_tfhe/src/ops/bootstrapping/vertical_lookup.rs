@@ -0,0 +1,302 @@
+use num::Complex;
+
+use crate::{
+    entities::{
+        BootstrapKeyFftRef, CircuitBootstrappingKeyswitchKeysRef, GgswCiphertext,
+        GgswCiphertextFft, GgswCiphertextFftRef, GlweCiphertext, GlweCiphertextRef,
+        LweCiphertextRef,
+    },
+    ops::{bootstrapping::circuit_bootstrap, fft_ops::cmux},
+    GlweDef, LweDef, RadixDecomposition, TorusOps,
+};
+
+/// Select `table[i]`, where `i` is the integer whose bits are encrypted in
+/// `selectors`, least-significant bit first. The result is stored in `output`.
+///
+/// # Remarks
+/// This evaluates a binary tree of [`cmux`] operations. The first level uses
+/// `selectors[0]` to choose between each pair of adjacent entries, the next
+/// level uses `selectors[1]` to choose between each pair of results, and so
+/// on. Looking up a table of `2^d` entries thus costs `2^d - 1` CMUX
+/// operations and adds the noise of `d` CMUX operations to the selected
+/// entry.
+///
+/// Entries past the end of `table` encrypt zero. To look up a plaintext
+/// table, trivially encrypt its entries (see
+/// [`trivially_encrypt_glwe_ciphertext`](crate::ops::encryption::trivially_encrypt_glwe_ciphertext)).
+///
+/// Each selector must encrypt 0 or 1. Neither the index nor the selected
+/// entry is revealed, making this an oblivious read from `table`.
+///
+/// # Panics
+/// If `table` is empty or contains more than `2^selectors.len()` entries.
+/// If `params` or `radix` are invalid.
+/// If `output` or any entry of `table` isn't valid under `params`.
+/// If any of `selectors` isn't valid under `params` and `radix`.
+pub fn cmux_tree_lookup<S>(
+    output: &mut GlweCiphertextRef<S>,
+    table: &[&GlweCiphertextRef<S>],
+    selectors: &[&GgswCiphertextFftRef<Complex<f64>>],
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    params.assert_valid();
+    radix.assert_valid::<S>();
+    output.assert_valid(params);
+
+    assert!(!table.is_empty());
+    assert!(selectors.len() < usize::BITS as usize);
+    assert!(table.len() <= 0x1 << selectors.len());
+
+    for entry in table {
+        entry.assert_valid(params);
+    }
+
+    let zero = GlweCiphertext::<S>::new(params);
+
+    let mut level = table.iter().map(|x| (*x).to_owned()).collect::<Vec<_>>();
+
+    for sel in selectors {
+        level = level
+            .chunks(2)
+            .map(|pair| {
+                let mut selected = GlweCiphertext::new(params);
+
+                let d_1 = pair.get(1).unwrap_or(&zero);
+
+                cmux(&mut selected, &pair[0], d_1, sel, params, radix);
+
+                selected
+            })
+            .collect();
+    }
+
+    output.clone_from_ref(&level[0]);
+}
+
+#[allow(clippy::too_many_arguments)]
+/// Select `table[i]`, where `i` is the integer whose bits are encrypted in
+/// `index_bits`, least-significant bit first. The result is stored in
+/// `output`.
+///
+/// # Remarks
+/// This [`circuit_bootstrap`]s each bit in `index_bits` into a
+/// [`GgswCiphertext`] and uses the results as the selectors of a
+/// [`cmux_tree_lookup`]. See those functions for more details.
+///
+/// `table` and `output` are GLWE ciphertexts under `glwe_1` parameters,
+/// encrypted under the same key as the [`GgswCiphertext`]s produced by
+/// circuit bootstrapping. Each bit in `index_bits` is an LWE ciphertext under
+/// `lwe_0` parameters encrypting 0 or 1 with 1 plaintext bit.
+///
+/// Circuit bootstrapping resets the noise in each selector, so the index may
+/// be the result of other computations.
+///
+/// # Panics
+/// If `table` is empty or contains more than `2^index_bits.len()` entries.
+/// If `pbs_radix`, `cbs_radix`, `pfks_radix`, `lwe_0`, `glwe_1` or `glwe_2` are invalid.
+/// If `bsk` or `cbsksk` don't correspond to these parameters, as in [`circuit_bootstrap`].
+/// If `output` or any entry of `table` isn't valid under `glwe_1`.
+/// If any of `index_bits` isn't valid under `lwe_0`.
+pub fn vertical_lookup<S>(
+    output: &mut GlweCiphertextRef<S>,
+    table: &[&GlweCiphertextRef<S>],
+    index_bits: &[&LweCiphertextRef<S>],
+    bsk: &BootstrapKeyFftRef<Complex<f64>>,
+    cbsksk: &CircuitBootstrappingKeyswitchKeysRef<S>,
+    lwe_0: &LweDef,
+    glwe_1: &GlweDef,
+    glwe_2: &GlweDef,
+    pbs_radix: &RadixDecomposition,
+    cbs_radix: &RadixDecomposition,
+    pfks_radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    let mut ggsw = GgswCiphertext::new(glwe_1, cbs_radix);
+
+    let selectors = index_bits
+        .iter()
+        .map(|bit| {
+            circuit_bootstrap(
+                &mut ggsw, bit, bsk, cbsksk, lwe_0, glwe_1, glwe_2, pbs_radix, cbs_radix,
+                pfks_radix,
+            );
+
+            let mut ggsw_fft = GgswCiphertextFft::new(glwe_1, cbs_radix);
+            ggsw.fft(&mut ggsw_fft, glwe_1, cbs_radix);
+
+            ggsw_fft
+        })
+        .collect::<Vec<_>>();
+
+    let selector_refs = selectors.iter().map(|x| x.as_ref()).collect::<Vec<_>>();
+
+    cmux_tree_lookup(output, table, &selector_refs, glwe_1, cbs_radix);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        entities::Polynomial,
+        high_level::{encryption, fft, keygen, TEST_GLWE_DEF_1, TEST_RADIX},
+        PlaintextBits, RadixCount, RadixLog, GLWE_1_1024_80, GLWE_5_256_80, LWE_512_80,
+    };
+
+    use super::*;
+
+    fn make_table(
+        len: usize,
+        params: &GlweDef,
+        plaintext_bits: PlaintextBits,
+    ) -> Vec<Polynomial<u64>> {
+        let degree = params.dim.polynomial_degree.0;
+
+        (0..len)
+            .map(|i| {
+                Polynomial::new(
+                    &(0..degree)
+                        .map(|j| ((i * 3 + j) % (0x1 << plaintext_bits.0)) as u64)
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn can_cmux_tree_lookup() {
+        let params = TEST_GLWE_DEF_1;
+        let radix = TEST_RADIX;
+        let bits = PlaintextBits(4);
+
+        let sk = keygen::generate_binary_glwe_sk(&params);
+
+        // 6 entries with 3 selector bits, so the last 2 entries are implicitly 0.
+        let table = make_table(6, &params, bits);
+
+        let encrypted = table
+            .iter()
+            .map(|pt| encryption::encrypt_glwe(pt, &sk, &params, bits))
+            .collect::<Vec<_>>();
+        let trivial = table
+            .iter()
+            .map(|pt| encryption::trivial_glwe(pt, &params, bits))
+            .collect::<Vec<_>>();
+
+        for index in 0..8 {
+            let selectors = (0..3)
+                .map(|i| {
+                    let ggsw = encryption::encrypt_ggsw(
+                        (index >> i) & 0x1,
+                        &sk,
+                        &params,
+                        &radix,
+                        PlaintextBits(1),
+                    );
+
+                    fft::fft_ggsw(&ggsw, &params, &radix)
+                })
+                .collect::<Vec<_>>();
+            let selectors = selectors.iter().map(|x| x.as_ref()).collect::<Vec<_>>();
+
+            for table_cts in [&encrypted, &trivial] {
+                let table_refs = table_cts.iter().map(|x| x.as_ref()).collect::<Vec<_>>();
+                let mut output = GlweCiphertext::new(&params);
+
+                cmux_tree_lookup(&mut output, &table_refs, &selectors, &params, &radix);
+
+                let actual = encryption::decrypt_glwe(&output, &sk, &params, bits);
+
+                match table.get(index as usize) {
+                    Some(expected) => assert_eq!(&actual, expected),
+                    None => assert!(actual.coeffs().iter().all(|x| *x == 0)),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn can_vertical_lookup() {
+        let pbs_radix = RadixDecomposition {
+            count: RadixCount(2),
+            radix_log: RadixLog(16),
+        };
+        let cbs_radix = RadixDecomposition {
+            count: RadixCount(2),
+            radix_log: RadixLog(5),
+        };
+        let pfks_radix = RadixDecomposition {
+            count: RadixCount(3),
+            radix_log: RadixLog(11),
+        };
+
+        let level_2_params = GLWE_5_256_80;
+        let level_1_params = GLWE_1_1024_80;
+        let level_0_params = LWE_512_80;
+        let bits = PlaintextBits(4);
+
+        let sk_0 = keygen::generate_binary_lwe_sk(&level_0_params);
+        let sk_1 = keygen::generate_binary_glwe_sk(&level_1_params);
+        let sk_2 = keygen::generate_binary_glwe_sk(&level_2_params);
+
+        let bsk = keygen::generate_bootstrapping_key(
+            &sk_0,
+            &sk_2,
+            &level_0_params,
+            &level_2_params,
+            &pbs_radix,
+        );
+        let bsk = fft::fft_bootstrap_key(&bsk, &level_0_params, &level_2_params, &pbs_radix);
+
+        let cbsksk = keygen::generate_cbs_ksk(
+            sk_2.to_lwe_secret_key(),
+            &sk_1,
+            &level_2_params.as_lwe_def(),
+            &level_1_params,
+            &pfks_radix,
+        );
+
+        let table = make_table(4, &level_1_params, bits);
+        let table_cts = table
+            .iter()
+            .map(|pt| encryption::encrypt_glwe(pt, &sk_1, &level_1_params, bits))
+            .collect::<Vec<_>>();
+        let table_refs = table_cts.iter().map(|x| x.as_ref()).collect::<Vec<_>>();
+
+        let index = 2u64;
+
+        let index_bits = (0..2)
+            .map(|i| {
+                encryption::encrypt_lwe_secret(
+                    (index >> i) & 0x1,
+                    &sk_0,
+                    &level_0_params,
+                    PlaintextBits(1),
+                )
+            })
+            .collect::<Vec<_>>();
+        let index_refs = index_bits.iter().map(|x| x.as_ref()).collect::<Vec<_>>();
+
+        let mut output = GlweCiphertext::new(&level_1_params);
+
+        vertical_lookup(
+            &mut output,
+            &table_refs,
+            &index_refs,
+            &bsk,
+            &cbsksk,
+            &level_0_params,
+            &level_1_params,
+            &level_2_params,
+            &pbs_radix,
+            &cbs_radix,
+            &pfks_radix,
+        );
+
+        let actual = encryption::decrypt_glwe(&output, &sk_1, &level_1_params, bits);
+
+        assert_eq!(actual, table[index as usize]);
+    }
+}
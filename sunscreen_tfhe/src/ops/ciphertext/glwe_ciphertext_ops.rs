@@ -3,11 +3,9 @@ use crate::{
     entities::{
         GgswCiphertextRef, GlweCiphertext, GlweCiphertextRef, LweCiphertextRef, PolynomialRef,
     },
+    math::simd,
     ops::ciphertext::decomposed_polynomial_glev_mad,
-    polynomial::{
-        polynomial_add, polynomial_external_mad, polynomial_negate, polynomial_scalar_mad,
-        polynomial_sub,
-    },
+    polynomial::{polynomial_external_mad, polynomial_negate, polynomial_scalar_mad},
     radix::PolynomialRadixIterator,
     scratch::allocate_scratch_ref,
     GlweDef, RadixDecomposition, TorusOps,
@@ -84,18 +82,13 @@ pub fn add_glwe_ciphertexts<S>(
 ) where
     S: TorusOps,
 {
-    let (c_a, c_b) = c.a_b_mut(params);
-    let (a_a, a_b) = a.a_b(params);
-    let (b_a, b_b) = b.a_b(params);
-
-    assert_eq!(c_a.len(), a_a.len());
-    assert_eq!(c_a.len(), b_a.len());
-
-    for (c, (a, b)) in c_a.zip(a_a.zip(b_a)) {
-        polynomial_add(c, a, b);
-    }
+    c.assert_valid(params);
+    a.assert_valid(params);
+    b.assert_valid(params);
 
-    polynomial_add(c_b, a_b, b_b);
+    // The A and B polynomials are contiguous, so we can operate on the
+    // ciphertexts as a whole.
+    simd::torus_add(c.as_mut_slice(), a.as_slice(), b.as_slice());
 }
 
 /// Subtract two GLWE ciphertexts together, storing the result in `c`.
@@ -107,18 +100,13 @@ pub fn sub_glwe_ciphertexts<S>(
 ) where
     S: TorusOps,
 {
-    let (c_a, c_b) = c.a_b_mut(params);
-    let (a_a, a_b) = a.a_b(params);
-    let (b_a, b_b) = b.a_b(params);
-
-    assert_eq!(c_a.len(), a_a.len());
-    assert_eq!(c_a.len(), b_a.len());
-
-    for (c, (a, b)) in c_a.zip(a_a.zip(b_a)) {
-        polynomial_sub(c, a, b);
-    }
+    c.assert_valid(params);
+    a.assert_valid(params);
+    b.assert_valid(params);
 
-    polynomial_sub(c_b, a_b, b_b);
+    // The A and B polynomials are contiguous, so we can operate on the
+    // ciphertexts as a whole.
+    simd::torus_sub(c.as_mut_slice(), a.as_slice(), b.as_slice());
 }
 
 /// Homomorphically compute -ct.
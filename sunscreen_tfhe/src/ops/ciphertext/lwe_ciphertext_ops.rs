@@ -1,4 +1,4 @@
-use crate::{entities::LweCiphertextRef, LweDef, Torus, TorusOps};
+use crate::{entities::LweCiphertextRef, math::simd, LweDef, Torus, TorusOps};
 
 /// Add the coefficients of a to the coefficients of c in place.
 pub fn add_lwe_inplace<S>(c: &mut LweCiphertextRef<S>, a: &LweCiphertextRef<S>, params: &LweDef)
@@ -53,16 +53,10 @@ pub(crate) fn scalar_mul_ciphertext_mad<S>(
 ) where
     S: TorusOps,
 {
-    let (c_a, c_b) = c.a_b_mut(params);
-    let (a_a, a_b) = a.a_b(params);
-
-    assert_eq!(c_a.len(), a_a.len());
-
-    for (c, a) in c_a.iter_mut().zip(a_a.iter()) {
-        *c += a * scalar;
-    }
+    c.assert_valid(params);
+    a.assert_valid(params);
 
-    *c_b += a_b * scalar;
+    simd::torus_scalar_mad(c.as_mut_slice(), a.as_slice(), *scalar);
 }
 
 /// Perform modulus switching on a ciphertext. We are assuming that moduli are
@@ -26,6 +26,20 @@ pub fn encrypt_glwe_ciphertext_secret_generic<S>(
     params: &GlweDef,
 ) where
     S: TorusOps,
+{
+    encrypt_glwe_ciphertext_secret_and_return_noise(c, msg, sk, params);
+}
+
+/// Encrypt `msg` into a into the given GLWE ciphertext `c` using the secret key `sk.`
+/// Returns the noise polynomial used to generate the ciphertext.
+pub(crate) fn encrypt_glwe_ciphertext_secret_and_return_noise<S>(
+    c: &mut GlweCiphertextRef<S>,
+    msg: &PolynomialRef<Torus<S>>,
+    sk: &GlweSecretKeyRef<S>,
+    params: &GlweDef,
+) -> Polynomial<Torus<S>>
+where
+    S: TorusOps,
 {
     let mut tmp = Polynomial::zero(params.dim.polynomial_degree.0);
 
@@ -55,6 +69,8 @@ pub fn encrypt_glwe_ciphertext_secret_generic<S>(
 
     // b = A * S + m + e
     polynomial_add_assign(b, &e);
+
+    e
 }
 
 /// Encrypt `msg` into a into the given GLWE ciphertext `c` using the secret key `sk.`
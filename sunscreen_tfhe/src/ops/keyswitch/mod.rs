@@ -4,6 +4,9 @@ pub mod private_functional_keyswitch;
 /// Methods for performing a public functional keyswitch (PuFKS)
 pub mod public_functional_keyswitch;
 
+/// Methods for packing LWE ciphertexts into a GLWE ciphertext.
+pub mod packing_keyswitch;
+
 /// Generate LWE keyswitch keys.
 pub mod lwe_keyswitch_key;
 
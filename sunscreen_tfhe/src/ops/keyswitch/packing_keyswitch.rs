@@ -0,0 +1,163 @@
+use crate::{
+    entities::{
+        GlweCiphertextRef, GlweSecretKeyRef, LweCiphertextRef, LweSecretKeyRef, PolynomialRef,
+        PublicFunctionalKeyswitchKeyRef,
+    },
+    ops::keyswitch::public_functional_keyswitch::{
+        generate_public_functional_keyswitch_key, public_functional_keyswitch,
+    },
+    GlweDef, LweDef, RadixDecomposition, Torus, TorusOps,
+};
+
+/// Generate a packing keyswitch key, which is used to pack a list of LWE
+/// ciphertexts into a single GLWE ciphertext.
+///
+/// # Remarks
+/// A packing keyswitch key is a
+/// [`PublicFunctionalKeyswitchKey`](crate::entities::PublicFunctionalKeyswitchKey)
+/// and this function is a thin wrapper around
+/// [`generate_public_functional_keyswitch_key`]. Allocate `output` with
+/// [`PublicFunctionalKeyswitchKey::new`](crate::entities::PublicFunctionalKeyswitchKey::new)
+/// using the same parameters.
+///
+/// See [`pack_lwe_ciphertexts`] for more details.
+pub fn generate_packing_keyswitch_key<S: TorusOps>(
+    output: &mut PublicFunctionalKeyswitchKeyRef<S>,
+    from_sk: &LweSecretKeyRef<S>,
+    to_sk: &GlweSecretKeyRef<S>,
+    from_lwe: &LweDef,
+    to_glwe: &GlweDef,
+    radix: &RadixDecomposition,
+) {
+    generate_public_functional_keyswitch_key(output, from_sk, to_sk, from_lwe, to_glwe, radix);
+}
+
+/// Pack a list of LWE ciphertexts into a single GLWE ciphertext, where the
+/// message in `inputs[i]` becomes the `i`-th coefficient of the GLWE
+/// ciphertext's message. The remaining coefficients encrypt zero.
+///
+/// # Remarks
+/// This is a [`public_functional_keyswitch`] where the function places each
+/// LWE message in its own coefficient. As such, the noise in each
+/// coefficient is the noise in the corresponding input plus the noise
+/// introduced by the keyswitch.
+///
+/// A GLWE ciphertext with `N` coefficients is roughly `N` times smaller than
+/// `N` LWE ciphertexts, making packing useful for storing or transmitting
+/// many results. To recover the `i`-th message as an LWE ciphertext under the
+/// GLWE secret key, perform a
+/// [`sample_extract`](crate::ops::ciphertext::sample_extract) on coefficient
+/// `i`.
+///
+/// # Panics
+/// If `inputs.len()` exceeds the polynomial degree in `to_glwe`.
+/// If `pksk` isn't valid under `from_lwe`, `to_glwe` and `radix`.
+/// If any input isn't valid under `from_lwe`.
+/// If `output` isn't valid under `to_glwe`.
+pub fn pack_lwe_ciphertexts<S: TorusOps>(
+    output: &mut GlweCiphertextRef<S>,
+    inputs: &[&LweCiphertextRef<S>],
+    pksk: &PublicFunctionalKeyswitchKeyRef<S>,
+    from_lwe: &LweDef,
+    to_glwe: &GlweDef,
+    radix: &RadixDecomposition,
+) {
+    fn place_in_coefficients<S: TorusOps>(poly: &mut PolynomialRef<Torus<S>>, tori: &[Torus<S>]) {
+        poly.clear();
+
+        for (c, t) in poly.coeffs_mut().iter_mut().zip(tori.iter()) {
+            *c = *t;
+        }
+    }
+
+    public_functional_keyswitch(
+        output,
+        inputs,
+        pksk,
+        place_in_coefficients,
+        from_lwe,
+        to_glwe,
+        radix,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        entities::{GlweCiphertext, LweCiphertext, PublicFunctionalKeyswitchKey},
+        high_level::{encryption, keygen, TEST_GLWE_DEF_1, TEST_LWE_DEF_1, TEST_RADIX},
+        ops::ciphertext::sample_extract,
+        PlaintextBits,
+    };
+
+    use super::*;
+
+    #[test]
+    fn can_pack_lwe_ciphertexts() {
+        let lwe_params = TEST_LWE_DEF_1;
+        let glwe_params = TEST_GLWE_DEF_1;
+        let radix = TEST_RADIX;
+        let plaintext_bits = PlaintextBits(4);
+
+        let lwe_sk = keygen::generate_binary_lwe_sk(&lwe_params);
+        let glwe_sk = keygen::generate_binary_glwe_sk(&glwe_params);
+
+        let mut pksk = PublicFunctionalKeyswitchKey::new(&lwe_params, &glwe_params, &radix);
+
+        generate_packing_keyswitch_key(
+            &mut pksk,
+            &lwe_sk,
+            &glwe_sk,
+            &lwe_params,
+            &glwe_params,
+            &radix,
+        );
+
+        let pts = (0..glwe_params.dim.polynomial_degree.0 as u64 / 2)
+            .map(|x| x % (0x1 << plaintext_bits.0))
+            .collect::<Vec<_>>();
+
+        let lwes = pts
+            .iter()
+            .map(|x| encryption::encrypt_lwe_secret(*x, &lwe_sk, &lwe_params, plaintext_bits))
+            .collect::<Vec<_>>();
+        let lwe_refs = lwes.iter().map(|x| x.as_ref()).collect::<Vec<_>>();
+
+        let mut packed = GlweCiphertext::new(&glwe_params);
+
+        pack_lwe_ciphertexts(
+            &mut packed,
+            &lwe_refs,
+            &pksk,
+            &lwe_params,
+            &glwe_params,
+            &radix,
+        );
+
+        let actual = encryption::decrypt_glwe(&packed, &glwe_sk, &glwe_params, plaintext_bits);
+
+        for (i, a) in actual.coeffs().iter().enumerate() {
+            let expected = pts.get(i).copied().unwrap_or_default();
+
+            assert_eq!(*a, expected);
+        }
+
+        let extract_sk = glwe_sk.to_lwe_secret_key();
+
+        for (i, pt) in pts.iter().enumerate() {
+            let mut extracted = LweCiphertext::new(&glwe_params.as_lwe_def());
+
+            sample_extract(&mut extracted, &packed, i, &glwe_params);
+
+            assert_eq!(
+                encryption::decrypt_lwe(
+                    &extracted,
+                    extract_sk,
+                    &glwe_params.as_lwe_def(),
+                    plaintext_bits
+                ),
+                *pt
+            );
+        }
+    }
+}
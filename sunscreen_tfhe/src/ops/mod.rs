@@ -1,6 +1,9 @@
 /// Ciphertext operations where one of the operands is in FFT form.
 pub mod fft_ops;
 
+/// Ciphertext operations where one of the operands is in Goldilocks NTT form.
+pub mod ntt_ops;
+
 /// Methods for key switching a ciphertext from one key to another, potentially
 /// switching the parameters at the same time.
 pub mod keyswitch;
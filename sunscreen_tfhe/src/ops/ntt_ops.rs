@@ -0,0 +1,328 @@
+use crate::{
+    dst::FromMutSlice,
+    entities::{
+        GgswCiphertextNttRef, GlweCiphertextRef, PolynomialRef, NTT_LIMB_BITS, NTT_LIMB_COUNT,
+    },
+    fft::ntt::{get_ntt, pointwise_mad},
+    math::goldilocks_field::{Fg, GOLDILOCKS_PRIME},
+    ops::ciphertext::sub_glwe_ciphertexts,
+    radix::PolynomialRadixIterator,
+    scratch::{allocate_scratch, allocate_scratch_ref},
+    GlweDef, RadixDecomposition, Torus, TorusOps, Zero,
+};
+
+/// Compute `c += a \[*\] b` where
+/// * `a` is a GLWE ciphertext
+/// * `b` is a GGSW ciphertext in NTT form
+/// * `\[*\]` is the external product operator GGSW \[*\] GLWE -> GLWE`
+///
+/// # Remarks
+/// Unlike [`glwe_ggsw_mad`](crate::ops::fft_ops::glwe_ggsw_mad), this computes
+/// the external product exactly and accumulates directly into a GLWE
+/// ciphertext in the torus domain.
+///
+/// We compute the product of each decomposed polynomial with each limb of the
+/// GGSW polynomials (see
+/// [`GgswCiphertextNtt`](crate::entities::GgswCiphertextNtt)) over the
+/// Goldilocks field. So long as the sum of these products over all rows and
+/// levels doesn't wrap around the field modulus, lifting the result back to
+/// the integers and recombining the limbs produces the exact product mod `q`.
+///
+/// # Panics
+/// If `params` and `radix` allow this sum to reach `p/2`, where `p` is the
+/// Goldilocks prime. See [`assert_exact_ntt_product`].
+pub fn glwe_ggsw_mad_ntt<S>(
+    c: &mut GlweCiphertextRef<S>,
+    a: &GlweCiphertextRef<S>,
+    b_ntt: &GgswCiphertextNttRef<Fg>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    params.assert_valid();
+    radix.assert_valid::<S>();
+    c.assert_valid(params);
+    a.assert_valid(params);
+    b_ntt.assert_valid(params, radix);
+    assert_exact_ntt_product(params, radix);
+
+    let degree = params.dim.polynomial_degree.0;
+    let ntt = get_ntt(degree.ilog2() as usize);
+
+    let mut acc = allocate_scratch::<Fg>((params.dim.size.0 + 1) * NTT_LIMB_COUNT * degree);
+    let acc = acc.as_mut_slice();
+    acc.fill(Fg::zero());
+
+    let mut digit = allocate_scratch::<Fg>(degree);
+    let digit = digit.as_mut_slice();
+
+    allocate_scratch_ref!(scratch, PolynomialRef<S>, (params.dim.polynomial_degree));
+    allocate_scratch_ref!(cur_radix, PolynomialRef<S>, (params.dim.polynomial_degree));
+
+    let (a_a, a_b) = a.a_b(params);
+    let mut b_glwes = b_ntt.glwe_limbs(params);
+
+    // Sign extend the decomposed digits, which lie in [-B/2, B/2).
+    let sign_shift = (u64::BITS - S::BITS) as usize;
+
+    for a_i in a_a.chain(std::iter::once(a_b)) {
+        let mut decomp = PolynomialRadixIterator::new(a_i, scratch, radix);
+
+        let row = (&mut b_glwes).take(radix.count.0).collect::<Vec<_>>();
+
+        // Note the reverse of the GLWE ciphertexts here! The decomposition
+        // iterator returns the decomposed values in the opposite order.
+        for b in row.iter().rev() {
+            decomp.write_next(cur_radix);
+
+            for (d, r) in digit.iter_mut().zip(cur_radix.coeffs().iter()) {
+                let signed = ((r.to_u64() << sign_shift) as i64) >> sign_shift;
+
+                *d = Fg::from_signed(signed);
+            }
+
+            ntt.forward_inplace(digit);
+
+            for (acc, b) in acc.chunks_mut(degree).zip(b.chunks(degree)) {
+                pointwise_mad(acc, digit, b);
+            }
+        }
+    }
+
+    for (c, acc) in c
+        .as_mut_slice()
+        .chunks_mut(degree)
+        .zip(acc.chunks_mut(degree * NTT_LIMB_COUNT))
+    {
+        for (j, limb) in acc.chunks_mut(degree).enumerate() {
+            ntt.reverse_inplace(limb);
+
+            let shift = NTT_LIMB_BITS * j as u32;
+
+            for (c, l) in c.iter_mut().zip(limb.iter()) {
+                let val = (l.to_signed() as u64).wrapping_shl(shift);
+
+                *c += Torus::from(S::from_u64(val));
+            }
+        }
+    }
+}
+
+/// Asserts that [`glwe_ggsw_mad_ntt`] computes exact products under `params`
+/// and `radix`.
+///
+/// # Remarks
+/// Each coefficient of an accumulated limb sums `(k + 1) * count * N` products
+/// of a decomposed digit, whose magnitude is at most `2^(radix_log - 1)`, and
+/// an unsigned limb less than `2^NTT_LIMB_BITS`. The magnitude of this sum
+/// must be less than `p/2` to recover its sign.
+///
+/// # Panics
+/// If the bound on the sum isn't less than `p/2`.
+pub fn assert_exact_ntt_product(params: &GlweDef, radix: &RadixDecomposition) {
+    let terms = ((params.dim.size.0 + 1) * radix.count.0 * params.dim.polynomial_degree.0) as u128;
+    let max_digit = 0x1u128 << (radix.radix_log.0 - 1);
+    let max_limb = (0x1u128 << NTT_LIMB_BITS) - 1;
+
+    let bound = terms
+        .checked_mul(max_digit)
+        .and_then(|x| x.checked_mul(max_limb));
+
+    assert!(
+        bound.is_some_and(|x| x < (GOLDILOCKS_PRIME / 2) as u128),
+        "Radix decomposition is too large for exact NTT products"
+    );
+}
+
+/// Performs a CMUX operation using a GGSW ciphertext in NTT form. The result
+/// is stored in `c`.
+///
+/// # Remarks
+/// This is the exact analogue of [`cmux`](crate::ops::fft_ops::cmux). See that
+/// function for more details.
+pub fn cmux_ntt<S>(
+    c: &mut GlweCiphertextRef<S>,
+    d_0: &GlweCiphertextRef<S>,
+    d_1: &GlweCiphertextRef<S>,
+    b_ntt: &GgswCiphertextNttRef<Fg>,
+    params: &GlweDef,
+    radix: &RadixDecomposition,
+) where
+    S: TorusOps,
+{
+    params.assert_valid();
+    radix.assert_valid::<S>();
+    c.assert_valid(params);
+    d_0.assert_valid(params);
+    d_1.assert_valid(params);
+    b_ntt.assert_valid(params, radix);
+
+    allocate_scratch_ref!(diff, GlweCiphertextRef<S>, (params.dim));
+
+    sub_glwe_ciphertexts(diff, d_1, d_0, params);
+
+    c.clone_from_ref(d_0);
+
+    glwe_ggsw_mad_ntt(c, diff, b_ntt, params, radix);
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{thread_rng, RngCore};
+
+    use crate::{
+        entities::{GgswCiphertextNtt, GlweCiphertext, Polynomial},
+        high_level::*,
+        ops::ciphertext::external_product_ggsw_glwe,
+        PlaintextBits, RadixCount, RadixLog,
+    };
+
+    use super::*;
+
+    #[test]
+    fn ntt_external_product_matches_naive() {
+        let params = TEST_GLWE_DEF_1;
+        let bits = PlaintextBits(1);
+
+        // A large decomposition base, where the FFT product loses precision.
+        let radix = RadixDecomposition {
+            count: RadixCount(2),
+            radix_log: RadixLog(24),
+        };
+
+        let sk = keygen::generate_binary_glwe_sk(&params);
+
+        for _ in 0..10 {
+            let sel = thread_rng().next_u64() % 2;
+            let ggsw = encryption::encrypt_ggsw(sel, &sk, &params, &radix, bits);
+
+            let pt = (0..params.dim.polynomial_degree.0)
+                .map(|_| thread_rng().next_u64() % 2)
+                .collect::<Polynomial<_>>();
+            let glwe = encryption::encrypt_glwe(&pt, &sk, &params, bits);
+
+            let mut ggsw_ntt = GgswCiphertextNtt::new(&params, &radix);
+            ggsw.ntt(&mut ggsw_ntt, &params, &radix);
+
+            let mut actual = GlweCiphertext::new(&params);
+            glwe_ggsw_mad_ntt(&mut actual, &glwe, &ggsw_ntt, &params, &radix);
+
+            let expected = external_product_ggsw_glwe(&ggsw, &glwe, &params, &radix);
+
+            assert_eq!(actual.as_slice(), expected.as_slice());
+        }
+    }
+
+    #[test]
+    fn ntt_external_product_matches_naive_at_largest_radix() {
+        let params = TEST_GLWE_DEF_1;
+        let bits = PlaintextBits(1);
+
+        // 3 * 128 * 2^38 * (2^16 - 1) is just under p/2, while a 40-bit radix
+        // exceeds it.
+        let radix = RadixDecomposition {
+            count: RadixCount(1),
+            radix_log: RadixLog(39),
+        };
+
+        let sk = keygen::generate_binary_glwe_sk(&params);
+
+        for _ in 0..10 {
+            let sel = thread_rng().next_u64() % 2;
+            let ggsw = encryption::encrypt_ggsw(sel, &sk, &params, &radix, bits);
+
+            let pt = (0..params.dim.polynomial_degree.0)
+                .map(|_| thread_rng().next_u64() % 2)
+                .collect::<Polynomial<_>>();
+            let glwe = encryption::encrypt_glwe(&pt, &sk, &params, bits);
+
+            let mut ggsw_ntt = GgswCiphertextNtt::new(&params, &radix);
+            ggsw.ntt(&mut ggsw_ntt, &params, &radix);
+
+            let mut actual = GlweCiphertext::new(&params);
+            glwe_ggsw_mad_ntt(&mut actual, &glwe, &ggsw_ntt, &params, &radix);
+
+            let expected = external_product_ggsw_glwe(&ggsw, &glwe, &params, &radix);
+
+            assert_eq!(actual.as_slice(), expected.as_slice());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn ntt_external_product_rejects_inexact_radix() {
+        let params = TEST_GLWE_DEF_1;
+
+        // The largest radix RadixDecomposition::assert_valid allows.
+        let radix = RadixDecomposition {
+            count: RadixCount(1),
+            radix_log: RadixLog(64),
+        };
+
+        let glwe = GlweCiphertext::<u64>::new(&params);
+        let ggsw_ntt = GgswCiphertextNtt::new(&params, &radix);
+
+        let mut c = GlweCiphertext::new(&params);
+        glwe_ggsw_mad_ntt(&mut c, &glwe, &ggsw_ntt, &params, &radix);
+    }
+
+    #[test]
+    fn can_roundtrip_ggsw_ntt() {
+        let params = TEST_GLWE_DEF_1;
+        let radix = TEST_RADIX;
+
+        let sk = keygen::generate_binary_glwe_sk(&params);
+        let ggsw = encryption::encrypt_ggsw(1, &sk, &params, &radix, PlaintextBits(1));
+
+        let mut ggsw_ntt = GgswCiphertextNtt::new(&params, &radix);
+        ggsw.ntt(&mut ggsw_ntt, &params, &radix);
+
+        let mut actual = ggsw.clone();
+        actual
+            .as_mut_slice()
+            .iter_mut()
+            .for_each(|x| *x = Torus::from(0));
+        ggsw_ntt.intt(&mut actual, &params, &radix);
+
+        assert_eq!(actual.as_slice(), ggsw.as_slice());
+    }
+
+    #[test]
+    fn can_cmux_ntt() {
+        let glwe = TEST_GLWE_DEF_1;
+        let sk = keygen::generate_binary_glwe_sk(&glwe);
+        let radix = TEST_RADIX;
+        let bits = PlaintextBits(1);
+
+        for _ in 0..20 {
+            let sel = thread_rng().next_u64() % 2;
+
+            let sel_ct = encryption::encrypt_ggsw(sel, &sk, &glwe, &radix, bits);
+
+            let a = (0..glwe.dim.polynomial_degree.0)
+                .map(|_| thread_rng().next_u64() % 2)
+                .collect::<Polynomial<_>>();
+            let a_ct = encryption::encrypt_glwe(&a, &sk, &glwe, bits);
+
+            let b = (0..glwe.dim.polynomial_degree.0)
+                .map(|_| thread_rng().next_u64() % 2)
+                .collect::<Polynomial<_>>();
+            let b_ct = encryption::encrypt_glwe(&b, &sk, &glwe, bits);
+
+            let sel_ntt = fft::ntt_ggsw(&sel_ct, &glwe, &radix);
+
+            let mut res_ct = GlweCiphertext::new(&glwe);
+
+            cmux_ntt(&mut res_ct, &a_ct, &b_ct, &sel_ntt, &glwe, &radix);
+
+            let actual = encryption::decrypt_glwe(&res_ct, &sk, &glwe, bits);
+
+            if sel == 1 {
+                assert_eq!(actual, b);
+            } else {
+                assert_eq!(actual, a);
+            }
+        }
+    }
+}
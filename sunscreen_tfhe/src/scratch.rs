@@ -9,7 +9,7 @@ use std::{
     rc::Rc,
 };
 
-use crate::{Torus, TorusOps};
+use crate::{math::goldilocks_field::Fg, Torus, TorusOps};
 
 thread_local! {
     static SCRATCH: RefCell<Option<Scratch>> = const { RefCell::new(None) };
@@ -60,6 +60,7 @@ unsafe impl Pod for f32 {}
 unsafe impl Pod for f64 {}
 unsafe impl<T> Pod for Complex<T> where T: Float + FftNum {}
 unsafe impl<S> Pod for Torus<S> where S: TorusOps {}
+unsafe impl Pod for Fg {}
 
 /// Allocate a scratch buffer in a cache efficient manner. Freed scratch
 /// buffers are reused in subsequent allocations.
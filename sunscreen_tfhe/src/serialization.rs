@@ -0,0 +1,1216 @@
+//! TFHE entities don't carry the parameters that define their layout, so their
+//! serde representation is little more than a flat list of torus elements. Loading
+//! such data under the wrong parameters silently produces garbage. This module
+//! defines a stable, versioned binary format whose header records the parameters
+//! under which an entity was created. Loading validates this header against the
+//! parameters the caller expects.
+//!
+//! # Format
+//! All integers are little-endian.
+//!
+//! | Size      | Contents                                                      |
+//! |-----------|---------------------------------------------------------------|
+//! | 4         | Magic bytes `STFH`                                            |
+//! | 2         | Format version ([`WIRE_FORMAT_VERSION`])                      |
+//! | 1         | [`EntityKind`]                                                |
+//! | 1         | Torus width in bits (32 or 64)                                |
+//! | 1         | Flags. Bit 0 indicates seeded compression                     |
+//! | 1         | Number of parameter records `P`                               |
+//! | variable  | `P` parameter records                                         |
+//! | 32        | ChaCha20 seed, present only under seeded compression          |
+//! | 8         | Number of torus elements `E`                                  |
+//! | `E * w`   | Torus elements, each `w = width / 8` bytes                    |
+//!
+//! Each parameter record begins with a one-byte tag followed by the fields of the
+//! parameter as 8-byte values:
+//! * `0`: [`LweDef`] as the LWE dimension and the bits of its `f64` standard deviation.
+//! * `1`: [`GlweDef`] as the GLWE size, polynomial degree and the bits of its `f64`
+//!   standard deviation.
+//! * `2`: [`RadixDecomposition`] as its digit count and radix log.
+//!
+//! # Seeded compression
+//! Entities encrypted under a secret key (e.g. [`BootstrapKey`] and
+//! [`LweKeyswitchKey`]) consist of many LWE or GLWE ciphertexts whose masks are
+//! uniformly random. [`SeededWireFormat::to_compressed_bytes`] re-randomizes these
+//! masks from a ChaCha20 stream and writes only the stream's seed and the
+//! ciphertexts' bodies, shrinking the payload by roughly a factor of the mask
+//! length. Loading regenerates the masks from the seed, drawing one 64-bit value per
+//! mask element in order.
+//!
+//! Re-randomizing the mask preserves every ciphertext's phase, so the decompressed
+//! entity encrypts the same messages with the same noise as the original.
+
+use num::Zero;
+use rand::{thread_rng, Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+use crate::{
+    dst::{FromSlice, OverlaySize},
+    entities::{
+        BootstrapKey, BootstrapKeyRef, GgswCiphertext, GgswCiphertextRef, GlweCiphertext,
+        GlweCiphertextRef, GlweSecretKey, GlweSecretKeyRef, LweCiphertext, LweCiphertextRef,
+        LweKeyswitchKey, LweKeyswitchKeyRef, LwePublicKey, LwePublicKeyRef, LweSecretKey,
+        LweSecretKeyRef, Polynomial,
+    },
+    ops::encryption::decrypt_glwe_ciphertext,
+    polynomial::polynomial_external_mad,
+    Error, GlweDef, LweDef, RadixDecomposition, Result, Torus, TorusOps,
+};
+
+/// The current version of the wire format.
+pub const WIRE_FORMAT_VERSION: u16 = 1;
+
+const MAGIC: [u8; 4] = *b"STFH";
+
+const FLAG_SEEDED: u8 = 0x1;
+
+const SEED_LEN: usize = 32;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u8)]
+/// The kind of entity contained in serialized data.
+pub enum EntityKind {
+    /// An [`LweCiphertext`].
+    LweCiphertext = 1,
+
+    /// A [`GlweCiphertext`].
+    GlweCiphertext = 2,
+
+    /// A [`GgswCiphertext`].
+    GgswCiphertext = 3,
+
+    /// An [`LweSecretKey`].
+    LweSecretKey = 4,
+
+    /// A [`GlweSecretKey`].
+    GlweSecretKey = 5,
+
+    /// An [`LwePublicKey`].
+    LwePublicKey = 6,
+
+    /// An [`LweKeyswitchKey`].
+    LweKeyswitchKey = 7,
+
+    /// A [`BootstrapKey`].
+    BootstrapKey = 8,
+}
+
+impl TryFrom<u8> for EntityKind {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        Ok(match value {
+            1 => Self::LweCiphertext,
+            2 => Self::GlweCiphertext,
+            3 => Self::GgswCiphertext,
+            4 => Self::LweSecretKey,
+            5 => Self::GlweSecretKey,
+            6 => Self::LwePublicKey,
+            7 => Self::LweKeyswitchKey,
+            8 => Self::BootstrapKey,
+            _ => return Err(Error::MalformedData("unknown entity kind")),
+        })
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+/// A parameter recorded in the header of a serialized entity.
+pub enum ParamRecord {
+    /// LWE parameters.
+    Lwe(LweDef),
+
+    /// GLWE parameters.
+    Glwe(GlweDef),
+
+    /// Radix decomposition parameters.
+    Radix(RadixDecomposition),
+}
+
+impl ParamRecord {
+    fn write(&self, out: &mut Vec<u8>) {
+        match self {
+            Self::Lwe(lwe) => {
+                out.push(0);
+                write_u64(out, lwe.dim.0 as u64);
+                write_u64(out, lwe.std.0.to_bits());
+            }
+            Self::Glwe(glwe) => {
+                out.push(1);
+                write_u64(out, glwe.dim.size.0 as u64);
+                write_u64(out, glwe.dim.polynomial_degree.0 as u64);
+                write_u64(out, glwe.std.0.to_bits());
+            }
+            Self::Radix(radix) => {
+                out.push(2);
+                write_u64(out, radix.count.0 as u64);
+                write_u64(out, radix.radix_log.0 as u64);
+            }
+        }
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self> {
+        use crate::{
+            rand::Stddev, GlweDimension, GlweSize, LweDimension, PolynomialDegree, RadixCount,
+            RadixLog,
+        };
+
+        Ok(match reader.u8()? {
+            0 => Self::Lwe(LweDef {
+                dim: LweDimension(reader.usize()?),
+                std: Stddev(f64::from_bits(reader.u64()?)),
+            }),
+            1 => Self::Glwe(GlweDef {
+                dim: GlweDimension {
+                    size: GlweSize(reader.usize()?),
+                    polynomial_degree: PolynomialDegree(reader.usize()?),
+                },
+                std: Stddev(f64::from_bits(reader.u64()?)),
+            }),
+            2 => Self::Radix(RadixDecomposition {
+                count: RadixCount(reader.usize()?),
+                radix_log: RadixLog(reader.usize()?),
+            }),
+            _ => return Err(Error::MalformedData("unknown parameter tag")),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+/// The header of a serialized entity.
+pub struct Header {
+    /// The wire format version of the data.
+    pub version: u16,
+
+    /// The kind of entity serialized.
+    pub kind: EntityKind,
+
+    /// The number of bits in the entity's torus elements.
+    pub torus_bits: u32,
+
+    /// The parameters under which the entity was created.
+    pub params: Vec<ParamRecord>,
+
+    /// The seed from which to derive ciphertext masks, if the entity uses
+    /// seeded compression.
+    pub seed: Option<[u8; SEED_LEN]>,
+}
+
+/// Reads the [`Header`] of a serialized entity without loading it.
+///
+/// # Remarks
+/// This is useful for inspecting the parameters of data whose contents are
+/// unknown. To load an entity, use [`WireFormat::from_bytes`], which validates
+/// the header against the expected parameters.
+pub fn read_header(bytes: &[u8]) -> Result<Header> {
+    let mut reader = Reader::new(bytes);
+
+    read_header_from(&mut reader)
+}
+
+fn read_header_from(reader: &mut Reader) -> Result<Header> {
+    if reader.bytes(MAGIC.len())? != MAGIC {
+        return Err(Error::MalformedData("missing magic bytes"));
+    }
+
+    let version = u16::from_le_bytes(reader.bytes(2)?.try_into().unwrap());
+
+    if version != WIRE_FORMAT_VERSION {
+        return Err(Error::UnsupportedVersion(version));
+    }
+
+    let kind = EntityKind::try_from(reader.u8()?)?;
+    let torus_bits = reader.u8()? as u32;
+    let flags = reader.u8()?;
+
+    if flags & !FLAG_SEEDED != 0 {
+        return Err(Error::MalformedData("unknown flags"));
+    }
+
+    let num_params = reader.u8()?;
+    let params = (0..num_params)
+        .map(|_| ParamRecord::read(reader))
+        .collect::<Result<Vec<_>>>()?;
+
+    let seed = if flags & FLAG_SEEDED != 0 {
+        Some(reader.bytes(SEED_LEN)?.try_into().unwrap())
+    } else {
+        None
+    };
+
+    Ok(Header {
+        version,
+        kind,
+        torus_bits,
+        params,
+        seed,
+    })
+}
+
+/// Serialization of an entity to and from the wire format.
+pub trait WireFormat: Sized {
+    /// The parameters that define the entity's layout.
+    type Params;
+
+    /// The kind recorded in the header of a serialized entity.
+    const KIND: EntityKind;
+
+    /// Serializes this entity, recording `params` in the header.
+    ///
+    /// # Panics
+    /// If this entity isn't valid under `params`.
+    fn to_bytes(&self, params: &Self::Params) -> Vec<u8>;
+
+    /// Deserializes an entity, which may use seeded compression.
+    ///
+    /// # Errors
+    /// If the data is malformed or uses an unsupported version.
+    /// If the data contains a different kind of entity or torus width.
+    /// If the parameters recorded in the data don't match `params`.
+    fn from_bytes(bytes: &[u8], params: &Self::Params) -> Result<Self>;
+}
+
+/// Seeded compression for entities encrypted under a secret key.
+pub trait SeededWireFormat: WireFormat {
+    /// The secret key under which the entity is encrypted.
+    type SecretKey: ?Sized;
+
+    /// Serializes this entity using seeded compression. See the
+    /// [module documentation](self) for details.
+    ///
+    /// # Remarks
+    /// Deserialize the result with [`WireFormat::from_bytes`].
+    ///
+    /// # Panics
+    /// If this entity isn't valid under `params`.
+    /// If `sk` isn't valid under `params`.
+    ///
+    /// # Security
+    /// `sk` must be the key under which this entity is encrypted. Otherwise, the
+    /// compression re-randomizes each ciphertext into garbage that leaks information
+    /// about its phase under `sk`.
+    fn to_compressed_bytes(&self, sk: &Self::SecretKey, params: &Self::Params) -> Vec<u8>;
+}
+
+/// Describes how an entity's data splits into ciphertexts whose masks can be
+/// derived from a seed.
+#[derive(Copy, Clone)]
+enum MaskLayout {
+    Lwe(LweDef),
+    Glwe(GlweDef),
+}
+
+impl MaskLayout {
+    fn mask_len(&self) -> usize {
+        match self {
+            Self::Lwe(lwe) => lwe.dim.0,
+            Self::Glwe(glwe) => glwe.dim.size.0 * glwe.dim.polynomial_degree.0,
+        }
+    }
+
+    fn body_len(&self) -> usize {
+        match self {
+            Self::Lwe(_) => 1,
+            Self::Glwe(glwe) => glwe.dim.polynomial_degree.0,
+        }
+    }
+
+    fn ciphertext_len(&self) -> usize {
+        self.mask_len() + self.body_len()
+    }
+}
+
+fn fill_mask<S: TorusOps>(rng: &mut ChaCha20Rng, mask: &mut [Torus<S>]) {
+    for a in mask {
+        *a = Torus::from(S::from_u64(rng.next_u64()));
+    }
+}
+
+fn write_u64(out: &mut Vec<u8>, x: u64) {
+    out.extend_from_slice(&x.to_le_bytes());
+}
+
+fn write_entity<S: TorusOps>(
+    kind: EntityKind,
+    params: &[ParamRecord],
+    seed: Option<&[u8; SEED_LEN]>,
+    data: &[S],
+) -> Vec<u8> {
+    let width = S::BITS as usize / 8;
+    let mut out = Vec::with_capacity(64 + data.len() * width);
+
+    out.extend_from_slice(&MAGIC);
+    out.extend_from_slice(&WIRE_FORMAT_VERSION.to_le_bytes());
+    out.push(kind as u8);
+    out.push(S::BITS as u8);
+    out.push(if seed.is_some() { FLAG_SEEDED } else { 0 });
+    out.push(params.len() as u8);
+
+    for p in params {
+        p.write(&mut out);
+    }
+
+    if let Some(seed) = seed {
+        out.extend_from_slice(seed);
+    }
+
+    write_u64(&mut out, data.len() as u64);
+
+    for x in data {
+        out.extend_from_slice(&x.to_u64().to_le_bytes()[..width]);
+    }
+
+    out
+}
+
+/// Validates the header in `bytes` and returns the entity's torus elements,
+/// regenerating any seeded masks.
+fn read_entity<S: TorusOps>(
+    bytes: &[u8],
+    kind: EntityKind,
+    params: &[ParamRecord],
+    len: usize,
+    layout: Option<MaskLayout>,
+) -> Result<Vec<S>> {
+    let mut reader = Reader::new(bytes);
+    let header = read_header_from(&mut reader)?;
+
+    if header.kind != kind {
+        return Err(Error::EntityKindMismatch {
+            expected: kind,
+            actual: header.kind,
+        });
+    }
+
+    if header.torus_bits != S::BITS {
+        return Err(Error::TorusWidthMismatch {
+            expected: S::BITS,
+            actual: header.torus_bits,
+        });
+    }
+
+    let mut expected_params = vec![];
+    let mut actual_params = vec![];
+
+    for p in params {
+        p.write(&mut expected_params);
+    }
+
+    for p in header.params.iter() {
+        p.write(&mut actual_params);
+    }
+
+    if expected_params != actual_params {
+        return Err(Error::ParameterMismatch);
+    }
+
+    let num_elems = reader.usize()?;
+    let width = S::BITS as usize / 8;
+
+    let elems = reader
+        .bytes(
+            num_elems
+                .checked_mul(width)
+                .ok_or(Error::MalformedData("too many elements"))?,
+        )?
+        .chunks(width)
+        .map(|x| {
+            let mut buf = [0u8; 8];
+            buf[..width].copy_from_slice(x);
+
+            S::try_from(u64::from_le_bytes(buf))
+                .map_err(|_| Error::MalformedData("element out of range"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if !reader.is_empty() {
+        return Err(Error::MalformedData("trailing bytes"));
+    }
+
+    let (seed, layout) = match (header.seed, layout) {
+        (None, _) => {
+            if elems.len() != len {
+                return Err(Error::MalformedData("wrong number of elements"));
+            }
+
+            return Ok(elems);
+        }
+        (Some(seed), Some(layout)) => (seed, layout),
+        (Some(_), None) => {
+            return Err(Error::MalformedData(
+                "entity doesn't support seeded compression",
+            ))
+        }
+    };
+
+    let num_ciphertexts = len / layout.ciphertext_len();
+
+    if elems.len() != num_ciphertexts * layout.body_len() {
+        return Err(Error::MalformedData("wrong number of elements"));
+    }
+
+    let mut rng = ChaCha20Rng::from_seed(seed);
+    let mut data = vec![Torus::<S>::zero(); len];
+
+    for (ct, body) in data
+        .chunks_mut(layout.ciphertext_len())
+        .zip(elems.chunks(layout.body_len()))
+    {
+        let (mask, b) = ct.split_at_mut(layout.mask_len());
+
+        fill_mask(&mut rng, mask);
+
+        for (b, x) in b.iter_mut().zip(body) {
+            *b = Torus::from(*x);
+        }
+    }
+
+    Ok(data.iter().map(|x| x.inner()).collect())
+}
+
+/// Re-randomizes the masks of the LWE ciphertexts in `data` from a fresh seed and
+/// returns the seed along with the resulting bodies.
+fn compress_lwe<S: TorusOps>(
+    data: &[Torus<S>],
+    sk: &LweSecretKeyRef<S>,
+    params: &LweDef,
+) -> ([u8; SEED_LEN], Vec<S>) {
+    let layout = MaskLayout::Lwe(*params);
+    let seed = thread_rng().gen::<[u8; SEED_LEN]>();
+    let mut rng = ChaCha20Rng::from_seed(seed);
+
+    let mut mask = vec![Torus::<S>::zero(); layout.mask_len()];
+
+    let bodies = data
+        .chunks(layout.ciphertext_len())
+        .map(|ct| {
+            let phase = sk.decrypt_without_decode(LweCiphertextRef::from_slice(ct), params);
+
+            fill_mask(&mut rng, &mut mask);
+
+            let mut b = phase;
+
+            for (a_i, s_i) in mask.iter().zip(sk.s().iter()) {
+                b += a_i * s_i;
+            }
+
+            b.inner()
+        })
+        .collect();
+
+    (seed, bodies)
+}
+
+/// Re-randomizes the masks of the GLWE ciphertexts in `data` from a fresh seed and
+/// returns the seed along with the resulting bodies.
+fn compress_glwe<S: TorusOps>(
+    data: &[Torus<S>],
+    sk: &GlweSecretKeyRef<S>,
+    params: &GlweDef,
+) -> ([u8; SEED_LEN], Vec<S>) {
+    let layout = MaskLayout::Glwe(*params);
+    let seed = thread_rng().gen::<[u8; SEED_LEN]>();
+    let mut rng = ChaCha20Rng::from_seed(seed);
+
+    let degree = params.dim.polynomial_degree.0;
+    let mut bodies = Vec::with_capacity(data.len() / layout.ciphertext_len() * degree);
+    let mut remasked = GlweCiphertext::<S>::new(params);
+
+    for ct in data.chunks(layout.ciphertext_len()) {
+        let mut phase = Polynomial::zero(degree);
+
+        decrypt_glwe_ciphertext(&mut phase, GlweCiphertextRef::from_slice(ct), sk, params);
+
+        let (mask, _) = remasked.as_mut_slice().split_at_mut(layout.mask_len());
+        fill_mask(&mut rng, mask);
+
+        // b = A * S + phase
+        for (a_i, s_i) in remasked.a(params).zip(sk.s(params)) {
+            polynomial_external_mad(&mut phase, a_i, s_i);
+        }
+
+        bodies.extend(phase.coeffs().iter().map(|x| x.inner()));
+    }
+
+    (seed, bodies)
+}
+
+fn torus_inner<S: TorusOps>(data: &[Torus<S>]) -> Vec<S> {
+    data.iter().map(|x| x.inner()).collect()
+}
+
+fn to_torus<S: TorusOps>(data: Vec<S>) -> Vec<Torus<S>> {
+    data.into_iter().map(Torus::from).collect()
+}
+
+impl<S: TorusOps> WireFormat for LweCiphertext<S> {
+    type Params = LweDef;
+
+    const KIND: EntityKind = EntityKind::LweCiphertext;
+
+    fn to_bytes(&self, params: &Self::Params) -> Vec<u8> {
+        self.assert_valid(params);
+
+        write_entity(
+            Self::KIND,
+            &[ParamRecord::Lwe(*params)],
+            None,
+            &torus_inner(self.as_slice()),
+        )
+    }
+
+    fn from_bytes(bytes: &[u8], params: &Self::Params) -> Result<Self> {
+        let data = read_entity(
+            bytes,
+            Self::KIND,
+            &[ParamRecord::Lwe(*params)],
+            LweCiphertextRef::<S>::size(params.dim),
+            Some(MaskLayout::Lwe(*params)),
+        )?;
+
+        Ok(LweCiphertextRef::from_slice(&to_torus(data)).to_owned())
+    }
+}
+
+impl<S: TorusOps> SeededWireFormat for LweCiphertext<S> {
+    type SecretKey = LweSecretKeyRef<S>;
+
+    fn to_compressed_bytes(&self, sk: &Self::SecretKey, params: &Self::Params) -> Vec<u8> {
+        self.assert_valid(params);
+        sk.assert_valid(params);
+
+        let (seed, bodies) = compress_lwe(self.as_slice(), sk, params);
+
+        write_entity(
+            Self::KIND,
+            &[ParamRecord::Lwe(*params)],
+            Some(&seed),
+            &bodies,
+        )
+    }
+}
+
+impl<S: TorusOps> WireFormat for GlweCiphertext<S> {
+    type Params = GlweDef;
+
+    const KIND: EntityKind = EntityKind::GlweCiphertext;
+
+    fn to_bytes(&self, params: &Self::Params) -> Vec<u8> {
+        self.assert_valid(params);
+
+        write_entity(
+            Self::KIND,
+            &[ParamRecord::Glwe(*params)],
+            None,
+            &torus_inner(self.as_slice()),
+        )
+    }
+
+    fn from_bytes(bytes: &[u8], params: &Self::Params) -> Result<Self> {
+        let data = read_entity(
+            bytes,
+            Self::KIND,
+            &[ParamRecord::Glwe(*params)],
+            GlweCiphertextRef::<S>::size(params.dim),
+            Some(MaskLayout::Glwe(*params)),
+        )?;
+
+        Ok(GlweCiphertextRef::from_slice(&to_torus(data)).to_owned())
+    }
+}
+
+impl<S: TorusOps> SeededWireFormat for GlweCiphertext<S> {
+    type SecretKey = GlweSecretKeyRef<S>;
+
+    fn to_compressed_bytes(&self, sk: &Self::SecretKey, params: &Self::Params) -> Vec<u8> {
+        self.assert_valid(params);
+        sk.assert_valid(params);
+
+        let (seed, bodies) = compress_glwe(self.as_slice(), sk, params);
+
+        write_entity(
+            Self::KIND,
+            &[ParamRecord::Glwe(*params)],
+            Some(&seed),
+            &bodies,
+        )
+    }
+}
+
+impl<S: TorusOps> WireFormat for GgswCiphertext<S> {
+    type Params = (GlweDef, RadixDecomposition);
+
+    const KIND: EntityKind = EntityKind::GgswCiphertext;
+
+    fn to_bytes(&self, params: &Self::Params) -> Vec<u8> {
+        let (glwe, radix) = params;
+        self.assert_valid(glwe, radix);
+
+        write_entity(
+            Self::KIND,
+            &[ParamRecord::Glwe(*glwe), ParamRecord::Radix(*radix)],
+            None,
+            &torus_inner(self.as_slice()),
+        )
+    }
+
+    fn from_bytes(bytes: &[u8], params: &Self::Params) -> Result<Self> {
+        let (glwe, radix) = params;
+
+        let data = read_entity(
+            bytes,
+            Self::KIND,
+            &[ParamRecord::Glwe(*glwe), ParamRecord::Radix(*radix)],
+            GgswCiphertextRef::<S>::size((glwe.dim, radix.count)),
+            Some(MaskLayout::Glwe(*glwe)),
+        )?;
+
+        Ok(GgswCiphertextRef::from_slice(&to_torus(data)).to_owned())
+    }
+}
+
+impl<S: TorusOps> SeededWireFormat for GgswCiphertext<S> {
+    type SecretKey = GlweSecretKeyRef<S>;
+
+    fn to_compressed_bytes(&self, sk: &Self::SecretKey, params: &Self::Params) -> Vec<u8> {
+        let (glwe, radix) = params;
+        self.assert_valid(glwe, radix);
+        sk.assert_valid(glwe);
+
+        let (seed, bodies) = compress_glwe(self.as_slice(), sk, glwe);
+
+        write_entity(
+            Self::KIND,
+            &[ParamRecord::Glwe(*glwe), ParamRecord::Radix(*radix)],
+            Some(&seed),
+            &bodies,
+        )
+    }
+}
+
+impl<S: TorusOps> WireFormat for LweSecretKey<S> {
+    type Params = LweDef;
+
+    const KIND: EntityKind = EntityKind::LweSecretKey;
+
+    fn to_bytes(&self, params: &Self::Params) -> Vec<u8> {
+        self.assert_valid(params);
+
+        write_entity(
+            Self::KIND,
+            &[ParamRecord::Lwe(*params)],
+            None,
+            self.as_slice(),
+        )
+    }
+
+    fn from_bytes(bytes: &[u8], params: &Self::Params) -> Result<Self> {
+        let data = read_entity::<S>(
+            bytes,
+            Self::KIND,
+            &[ParamRecord::Lwe(*params)],
+            LweSecretKeyRef::<S>::size(params.dim),
+            None,
+        )?;
+
+        Ok(LweSecretKeyRef::from_slice(&data).to_owned())
+    }
+}
+
+impl<S: TorusOps> WireFormat for GlweSecretKey<S> {
+    type Params = GlweDef;
+
+    const KIND: EntityKind = EntityKind::GlweSecretKey;
+
+    fn to_bytes(&self, params: &Self::Params) -> Vec<u8> {
+        self.assert_valid(params);
+
+        write_entity(
+            Self::KIND,
+            &[ParamRecord::Glwe(*params)],
+            None,
+            self.as_slice(),
+        )
+    }
+
+    fn from_bytes(bytes: &[u8], params: &Self::Params) -> Result<Self> {
+        let data = read_entity::<S>(
+            bytes,
+            Self::KIND,
+            &[ParamRecord::Glwe(*params)],
+            GlweSecretKeyRef::<S>::size(params.dim),
+            None,
+        )?;
+
+        Ok(GlweSecretKeyRef::from_slice(&data).to_owned())
+    }
+}
+
+impl<S: TorusOps> WireFormat for LwePublicKey<S> {
+    type Params = LweDef;
+
+    const KIND: EntityKind = EntityKind::LwePublicKey;
+
+    fn to_bytes(&self, params: &Self::Params) -> Vec<u8> {
+        self.assert_valid(params);
+
+        write_entity(
+            Self::KIND,
+            &[ParamRecord::Lwe(*params)],
+            None,
+            &torus_inner(self.as_slice()),
+        )
+    }
+
+    fn from_bytes(bytes: &[u8], params: &Self::Params) -> Result<Self> {
+        let data = read_entity(
+            bytes,
+            Self::KIND,
+            &[ParamRecord::Lwe(*params)],
+            LwePublicKeyRef::<S>::size(params.dim),
+            Some(MaskLayout::Lwe(*params)),
+        )?;
+
+        Ok(LwePublicKeyRef::from_slice(&to_torus(data)).to_owned())
+    }
+}
+
+impl<S: TorusOps> SeededWireFormat for LwePublicKey<S> {
+    type SecretKey = LweSecretKeyRef<S>;
+
+    fn to_compressed_bytes(&self, sk: &Self::SecretKey, params: &Self::Params) -> Vec<u8> {
+        self.assert_valid(params);
+        sk.assert_valid(params);
+
+        let (seed, bodies) = compress_lwe(self.as_slice(), sk, params);
+
+        write_entity(
+            Self::KIND,
+            &[ParamRecord::Lwe(*params)],
+            Some(&seed),
+            &bodies,
+        )
+    }
+}
+
+impl<S: TorusOps> WireFormat for LweKeyswitchKey<S> {
+    /// The original LWE parameters, the new LWE parameters and the radix
+    /// decomposition.
+    type Params = (LweDef, LweDef, RadixDecomposition);
+
+    const KIND: EntityKind = EntityKind::LweKeyswitchKey;
+
+    fn to_bytes(&self, params: &Self::Params) -> Vec<u8> {
+        let (from_lwe, to_lwe, radix) = params;
+        self.assert_valid(from_lwe, to_lwe, radix);
+
+        write_entity(
+            Self::KIND,
+            &[
+                ParamRecord::Lwe(*from_lwe),
+                ParamRecord::Lwe(*to_lwe),
+                ParamRecord::Radix(*radix),
+            ],
+            None,
+            &torus_inner(self.as_slice()),
+        )
+    }
+
+    fn from_bytes(bytes: &[u8], params: &Self::Params) -> Result<Self> {
+        let (from_lwe, to_lwe, radix) = params;
+
+        let data = read_entity(
+            bytes,
+            Self::KIND,
+            &[
+                ParamRecord::Lwe(*from_lwe),
+                ParamRecord::Lwe(*to_lwe),
+                ParamRecord::Radix(*radix),
+            ],
+            LweKeyswitchKeyRef::<S>::size((from_lwe.dim, to_lwe.dim, radix.count)),
+            Some(MaskLayout::Lwe(*to_lwe)),
+        )?;
+
+        Ok(LweKeyswitchKeyRef::from_slice(&to_torus(data)).to_owned())
+    }
+}
+
+impl<S: TorusOps> SeededWireFormat for LweKeyswitchKey<S> {
+    /// The new key, under which the keyswitch key is encrypted.
+    type SecretKey = LweSecretKeyRef<S>;
+
+    fn to_compressed_bytes(&self, sk: &Self::SecretKey, params: &Self::Params) -> Vec<u8> {
+        let (from_lwe, to_lwe, radix) = params;
+        self.assert_valid(from_lwe, to_lwe, radix);
+        sk.assert_valid(to_lwe);
+
+        let (seed, bodies) = compress_lwe(self.as_slice(), sk, to_lwe);
+
+        write_entity(
+            Self::KIND,
+            &[
+                ParamRecord::Lwe(*from_lwe),
+                ParamRecord::Lwe(*to_lwe),
+                ParamRecord::Radix(*radix),
+            ],
+            Some(&seed),
+            &bodies,
+        )
+    }
+}
+
+impl<S: TorusOps> WireFormat for BootstrapKey<S> {
+    /// The LWE parameters of the encrypted key, the GLWE parameters of the
+    /// encrypting key and the radix decomposition.
+    type Params = (LweDef, GlweDef, RadixDecomposition);
+
+    const KIND: EntityKind = EntityKind::BootstrapKey;
+
+    fn to_bytes(&self, params: &Self::Params) -> Vec<u8> {
+        let (lwe, glwe, radix) = params;
+        self.assert_valid(lwe, glwe, radix);
+
+        write_entity(
+            Self::KIND,
+            &[
+                ParamRecord::Lwe(*lwe),
+                ParamRecord::Glwe(*glwe),
+                ParamRecord::Radix(*radix),
+            ],
+            None,
+            &torus_inner(self.as_slice()),
+        )
+    }
+
+    fn from_bytes(bytes: &[u8], params: &Self::Params) -> Result<Self> {
+        let (lwe, glwe, radix) = params;
+
+        let data = read_entity(
+            bytes,
+            Self::KIND,
+            &[
+                ParamRecord::Lwe(*lwe),
+                ParamRecord::Glwe(*glwe),
+                ParamRecord::Radix(*radix),
+            ],
+            BootstrapKeyRef::<S>::size((lwe.dim, glwe.dim, radix.count)),
+            Some(MaskLayout::Glwe(*glwe)),
+        )?;
+
+        Ok(BootstrapKeyRef::from_slice(&to_torus(data)).to_owned())
+    }
+}
+
+impl<S: TorusOps> SeededWireFormat for BootstrapKey<S> {
+    type SecretKey = GlweSecretKeyRef<S>;
+
+    fn to_compressed_bytes(&self, sk: &Self::SecretKey, params: &Self::Params) -> Vec<u8> {
+        let (lwe, glwe, radix) = params;
+        self.assert_valid(lwe, glwe, radix);
+        sk.assert_valid(glwe);
+
+        let (seed, bodies) = compress_glwe(self.as_slice(), sk, glwe);
+
+        write_entity(
+            Self::KIND,
+            &[
+                ParamRecord::Lwe(*lwe),
+                ParamRecord::Glwe(*glwe),
+                ParamRecord::Radix(*radix),
+            ],
+            Some(&seed),
+            &bodies,
+        )
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.data.len() < len {
+            return Err(Error::MalformedData("unexpected end of data"));
+        }
+
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.bytes(8)?.try_into().unwrap()))
+    }
+
+    fn usize(&mut self) -> Result<usize> {
+        usize::try_from(self.u64()?).map_err(|_| Error::MalformedData("value exceeds usize"))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        entities::{GlweCiphertextRef, Polynomial},
+        high_level::*,
+        ops::encryption::decrypt_glwe_ciphertext,
+        PlaintextBits,
+    };
+
+    use super::*;
+
+    #[test]
+    fn can_roundtrip_lwe_ciphertext() {
+        let params = TEST_LWE_DEF_1;
+        let bits = PlaintextBits(4);
+
+        let sk = keygen::generate_binary_lwe_sk(&params);
+        let ct = encryption::encrypt_lwe_secret(5, &sk, &params, bits);
+
+        let bytes = ct.to_bytes(&params);
+        let header = read_header(&bytes).unwrap();
+
+        assert_eq!(header.version, WIRE_FORMAT_VERSION);
+        assert_eq!(header.kind, EntityKind::LweCiphertext);
+        assert_eq!(header.torus_bits, 64);
+        assert!(header.seed.is_none());
+
+        let ct2 = LweCiphertext::<u64>::from_bytes(&bytes, &params).unwrap();
+
+        assert_eq!(ct.as_slice(), ct2.as_slice());
+    }
+
+    #[test]
+    fn can_roundtrip_keys() {
+        let lwe = TEST_LWE_DEF_1;
+        let glwe = TEST_GLWE_DEF_1;
+
+        let lwe_sk = keygen::generate_binary_lwe_sk(&lwe);
+        let glwe_sk = keygen::generate_binary_glwe_sk(&glwe);
+        let pk = keygen::generate_lwe_pk(&lwe_sk, &lwe);
+
+        let lwe_sk2 = LweSecretKey::<u64>::from_bytes(&lwe_sk.to_bytes(&lwe), &lwe).unwrap();
+        let glwe_sk2 = GlweSecretKey::<u64>::from_bytes(&glwe_sk.to_bytes(&glwe), &glwe).unwrap();
+        let pk2 = LwePublicKey::<u64>::from_bytes(&pk.to_bytes(&lwe), &lwe).unwrap();
+
+        assert_eq!(lwe_sk.as_slice(), lwe_sk2.as_slice());
+        assert_eq!(glwe_sk.as_slice(), glwe_sk2.as_slice());
+        assert_eq!(pk.as_slice(), pk2.as_slice());
+    }
+
+    #[test]
+    fn can_roundtrip_u32_torus() {
+        let params = TEST_LWE_DEF_1;
+
+        let sk = LweSecretKey::<u32>::generate_binary(&params);
+        let (ct, _) = sk.encrypt(3, &params, PlaintextBits(2));
+
+        let bytes = ct.to_bytes(&params);
+
+        assert_eq!(read_header(&bytes).unwrap().torus_bits, 32);
+
+        let ct2 = LweCiphertext::<u32>::from_bytes(&bytes, &params).unwrap();
+        assert_eq!(ct.as_slice(), ct2.as_slice());
+
+        assert_eq!(
+            LweCiphertext::<u64>::from_bytes(&bytes, &params).unwrap_err(),
+            Error::TorusWidthMismatch {
+                expected: 64,
+                actual: 32
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_mismatched_params() {
+        let params = TEST_LWE_DEF_1;
+
+        let sk = keygen::generate_binary_lwe_sk(&params);
+        let ct = encryption::encrypt_lwe_secret(1, &sk, &params, PlaintextBits(1));
+        let bytes = ct.to_bytes(&params);
+
+        assert_eq!(
+            LweCiphertext::<u64>::from_bytes(&bytes, &TEST_LWE_DEF_2).unwrap_err(),
+            Error::ParameterMismatch
+        );
+        assert_eq!(
+            LweCiphertext::<u64>::from_bytes(&bytes, &TEST_LWE_DEF_3).unwrap_err(),
+            Error::ParameterMismatch
+        );
+        assert_eq!(
+            LwePublicKey::<u64>::from_bytes(&bytes, &params).unwrap_err(),
+            Error::EntityKindMismatch {
+                expected: EntityKind::LwePublicKey,
+                actual: EntityKind::LweCiphertext
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_data() {
+        let params = TEST_LWE_DEF_1;
+
+        let sk = keygen::generate_binary_lwe_sk(&params);
+        let ct = encryption::encrypt_lwe_secret(1, &sk, &params, PlaintextBits(1));
+        let bytes = ct.to_bytes(&params);
+
+        let mut bad_version = bytes.clone();
+        bad_version[4] = 0xFF;
+
+        assert_eq!(
+            LweCiphertext::<u64>::from_bytes(&bad_version, &params).unwrap_err(),
+            Error::UnsupportedVersion(0x00FF)
+        );
+
+        assert!(matches!(
+            LweCiphertext::<u64>::from_bytes(&bytes[..bytes.len() - 1], &params),
+            Err(Error::MalformedData(_))
+        ));
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b'X';
+
+        assert!(matches!(
+            read_header(&bad_magic),
+            Err(Error::MalformedData(_))
+        ));
+    }
+
+    #[test]
+    fn can_roundtrip_compressed_lwe() {
+        let params = TEST_LWE_DEF_1;
+        let bits = PlaintextBits(4);
+
+        let sk = keygen::generate_binary_lwe_sk(&params);
+        let ct = encryption::encrypt_lwe_secret(7, &sk, &params, bits);
+
+        let compressed = ct.to_compressed_bytes(&sk, &params);
+
+        assert!(compressed.len() < ct.to_bytes(&params).len() / 4);
+        assert!(read_header(&compressed).unwrap().seed.is_some());
+
+        let ct2 = LweCiphertext::<u64>::from_bytes(&compressed, &params).unwrap();
+
+        // Re-randomizing the mask preserves the phase.
+        assert_eq!(
+            sk.decrypt_without_decode(&ct, &params),
+            sk.decrypt_without_decode(&ct2, &params)
+        );
+        assert_eq!(encryption::decrypt_lwe(&ct2, &sk, &params, bits), 7);
+    }
+
+    #[test]
+    fn can_roundtrip_compressed_keyswitch_key() {
+        let from_lwe = TEST_LWE_DEF_1;
+        let to_lwe = TEST_LWE_DEF_2;
+        let radix = TEST_RADIX;
+
+        let from_sk = keygen::generate_binary_lwe_sk(&from_lwe);
+        let to_sk = keygen::generate_binary_lwe_sk(&to_lwe);
+
+        let ksk = keygen::generate_ksk(&from_sk, &to_sk, &from_lwe, &to_lwe, &radix);
+        let params = (from_lwe, to_lwe, radix);
+
+        let compressed = ksk.to_compressed_bytes(&to_sk, &params);
+        let ksk2 = LweKeyswitchKey::<u64>::from_bytes(&compressed, &params).unwrap();
+
+        for (a, b) in ksk
+            .as_slice()
+            .chunks(to_lwe.dim.0 + 1)
+            .zip(ksk2.as_slice().chunks(to_lwe.dim.0 + 1))
+        {
+            assert_eq!(
+                to_sk.decrypt_without_decode(LweCiphertextRef::from_slice(a), &to_lwe),
+                to_sk.decrypt_without_decode(LweCiphertextRef::from_slice(b), &to_lwe)
+            );
+        }
+    }
+
+    #[test]
+    fn can_roundtrip_compressed_bootstrap_key() {
+        let lwe = TEST_LWE_DEF_1;
+        let glwe = TEST_GLWE_DEF_1;
+        let radix = TEST_RADIX;
+
+        let lwe_sk = keygen::generate_binary_lwe_sk(&lwe);
+        let glwe_sk = keygen::generate_binary_glwe_sk(&glwe);
+
+        let bsk = keygen::generate_bootstrapping_key(&lwe_sk, &glwe_sk, &lwe, &glwe, &radix);
+        let params = (lwe, glwe, radix);
+
+        let uncompressed = bsk.to_bytes(&params);
+        let compressed = bsk.to_compressed_bytes(&glwe_sk, &params);
+
+        assert!(compressed.len() < uncompressed.len() / 2);
+
+        let bsk2 = BootstrapKey::<u64>::from_bytes(&compressed, &params).unwrap();
+
+        assert_eq!(
+            BootstrapKey::<u64>::from_bytes(&uncompressed, &params)
+                .unwrap()
+                .as_slice(),
+            bsk.as_slice()
+        );
+
+        let degree = glwe.dim.polynomial_degree.0;
+        let ct_len = GlweCiphertextRef::<u64>::size(glwe.dim);
+
+        for (a, b) in bsk
+            .as_slice()
+            .chunks(ct_len)
+            .zip(bsk2.as_slice().chunks(ct_len))
+        {
+            let mut phase_a = Polynomial::zero(degree);
+            let mut phase_b = Polynomial::zero(degree);
+
+            decrypt_glwe_ciphertext(
+                &mut phase_a,
+                GlweCiphertextRef::from_slice(a),
+                &glwe_sk,
+                &glwe,
+            );
+            decrypt_glwe_ciphertext(
+                &mut phase_b,
+                GlweCiphertextRef::from_slice(b),
+                &glwe_sk,
+                &glwe,
+            );
+
+            assert_eq!(phase_a, phase_b);
+        }
+
+        assert_eq!(
+            BootstrapKey::<u64>::from_bytes(&compressed, &(lwe, TEST_GLWE_DEF_2, radix))
+                .unwrap_err(),
+            Error::ParameterMismatch
+        );
+    }
+
+    #[test]
+    fn secret_keys_reject_compressed_data() {
+        let params = TEST_LWE_DEF_1;
+
+        let sk = keygen::generate_binary_lwe_sk(&params);
+        let ct = encryption::encrypt_lwe_secret(1, &sk, &params, PlaintextBits(1));
+
+        let mut compressed = ct.to_compressed_bytes(&sk, &params);
+        compressed[6] = EntityKind::LweSecretKey as u8;
+
+        assert!(matches!(
+            LweSecretKey::<u64>::from_bytes(&compressed, &params),
+            Err(Error::MalformedData(_))
+        ));
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use logproof::{
     crypto::CryptoHash,
     linear_algebra::{Matrix, PolynomialMatrix},
@@ -12,9 +14,12 @@ use sunscreen_math::{
 };
 
 use crate::{
-    entities::{LweCiphertext, LwePublicKey, LweSecretKey, TlwePublicEncRandomness},
+    entities::{
+        GlweCiphertext, GlweSecretKey, LweCiphertext, LweCiphertextRef, LwePublicKey, LweSecretKey,
+        Polynomial as TorusPolynomial, TlwePublicEncRandomness,
+    },
     math::{Torus, TorusOps},
-    LweDef, PlaintextBits,
+    GlweDef, LweDef, PlaintextBits,
 };
 
 /// Proof statements for the SDLP proof system when applied to TFHE.
@@ -40,10 +45,49 @@ pub enum ProofStatement<'a, 'b, S: TorusOps + TorusZq> {
         /// The public key being used to encrypt the message.
         public_key: &'b LwePublicKey<S>,
     },
+
+    /// A GLWE private key encryption statement.
+    ///
+    /// # Remarks
+    /// The `i`th coefficient of the plaintext polynomial encodes the message with ID
+    /// `message_ids[i]`. Coefficients past `message_ids.len()` must encrypt zero.
+    ///
+    /// Every GLWE statement in a proof must use the same [`GlweDef`].
+    GlweEncryption {
+        /// The message IDs encoded in each coefficient of the plaintext.
+        message_ids: &'a [usize],
+
+        /// The ciphertext being encrypted.
+        ciphertext: &'a GlweCiphertext<S>,
+
+        /// The parameters under which `ciphertext` was encrypted.
+        params: &'b GlweDef,
+    },
+
+    /// An LWE decryption statement asserting `ciphertext` decrypts to the
+    /// public `message` under the secret key behind `public_key`.
+    ///
+    /// # Remarks
+    /// Each decryption statement also proves that every encryption of zero in
+    /// `public_key` decrypts to zero under the same key, which ties the decryption
+    /// key to `public_key`. This adds `N` rows to the proof.
+    LweDecryption {
+        /// The ciphertext being decrypted.
+        ciphertext: &'a LweCiphertext<S>,
+
+        /// The (unencoded) plaintext to which `ciphertext` decrypts.
+        message: S,
+
+        /// The public key corresponding to the decryption key.
+        public_key: &'b LwePublicKey<S>,
+    },
 }
 
 /// Witness information for the SDLP proof system when applied to TFHE.
 /// This is the private information used when generating a proof.
+///
+/// # Remarks
+/// The `i`th witness must correspond to the `i`th [`ProofStatement`].
 #[derive(Debug)]
 pub enum Witness<'a, 'b, S: TorusOps + TorusZq> {
     /// A private key encryption witness.
@@ -60,6 +104,21 @@ pub enum Witness<'a, 'b, S: TorusOps + TorusZq> {
         /// The randomness used in the encryption.
         randomness: &'b TlwePublicEncRandomness<S>,
     },
+
+    /// A GLWE private key encryption witness.
+    GlweEncryption {
+        /// The noise polynomial used in the encryption.
+        randomness: &'b TorusPolynomial<Torus<S>>,
+
+        /// The private key used in the encryption.
+        private_key: &'a GlweSecretKey<S>,
+    },
+
+    /// An LWE decryption witness.
+    LweDecryption {
+        /// The private key under which the ciphertext decrypts.
+        private_key: &'a LweSecretKey<S>,
+    },
 }
 
 /// Generate LogProofProverKnowledge for the SDLP proof system.
@@ -72,7 +131,7 @@ pub fn generate_tfhe_sdlp_prover_knowledge<S: TorusOps + TorusZq>(
 ) -> LogProofProverKnowledge<S::Zq> {
     let vk = generate_tfhe_sdlp_verifier_knowledge(statements, lwe, plaintext_bits);
 
-    let s = compute_s(statements, witness, messages, lwe);
+    let s = compute_s(statements, witness, messages, lwe, plaintext_bits);
 
     LogProofProverKnowledge { vk, s }
 }
@@ -117,11 +176,11 @@ impl TorusZq for u64 {
 }
 
 /// Computes the public information needed to prove and verify public and private key
-/// TLWE encryptions.
+/// TLWE encryptions, private key GLWE encryptions and TLWE decryptions.
 ///
 /// # Remarks
-/// Using only private key encryption results in significantly faster runtime since we
-/// can characterize `Z_q[X]/f` with `f = X + 1`.
+/// Using only private key encryption and decryption results in significantly faster
+/// runtime since we can characterize `Z_q[X]/f` with `f = X + 1`.
 ///
 /// # Details
 /// Let `N` be the TLWE lattice dimension.
@@ -137,9 +196,10 @@ impl TorusZq for u64 {
 /// * `f = X^D + 1`
 ///
 /// For proving encryptions of TLWE ciphertexts, we have:
-/// * `m` is the number of proof statements.
+/// * `m` is the number of proof statements plus `N` for each decryption statement.
 /// * `n = 1`.
-/// * `k = num_messages + num_public_keys * (N + 1) + num_private_keys * N + num_private_encs + num_public_encs * (N + 1)`.
+/// * `k = num_messages + num_public_keys * (N + 1) + num_private_keys * N + num_private_encs + num_public_encs * (N + 1)
+///   + num_glwe_encs * (K + 1) + num_decryptions * N`, where `K` is the GLWE size.
 /// * See [quotient ring modulus `f`](#quotient-ring-modulus-f).
 ///
 /// The matrix `A` is arranged into rows, one per proof statement whose column structure depends
@@ -152,6 +212,15 @@ impl TorusZq for u64 {
 ///         V           V                  V                           V              V
 ///  [ 0 .. 1 .. 0     0 .. 0       0 .. a_0, a_1, .. a_N .. 0    0 .. 1 .. 0       0 .. 0]
 /// ```
+/// ### Decryption statements
+/// Decryption statements share their column arrangement with private key statements, save
+/// that the message is public and thus has no column.
+///
+/// Each decryption statement is followed (after all the statement rows) by `N` rows, one per
+/// encryption of zero `p_j` in its public key. These rows place the `a` values of `p_j` in the
+/// decryption statement's private key columns and a `1` in a noise column of their own. As
+/// they reuse the decryption key's columns, the key must be the one behind the public key.
+///
 /// ### Public key statements
 /// Recall that a TLWE ciphertext consists of (a_1, ... a_N, b), where `a_i, b` in the discrete
 /// torus `T`.
@@ -167,6 +236,12 @@ impl TorusZq for u64 {
 /// ```
 /// For these entries, we reinterpret each `p_i` as a polynomial `mod (X^N + 1)`.
 ///
+/// ### GLWE statements
+/// Recall that a GLWE ciphertext consists of polynomials (a_1, ... a_K, b). Each GLWE
+/// statement row places the `a_i` polynomials in the columns of a private GLWE key copy and a
+/// `1` in its own noise column. The message with ID `message_ids[j]` receives the monomial
+/// `X^j` in its column, as it lands in the `j`th coefficient of the plaintext.
+///
 /// ## T's structure
 /// While SDLP support T as a matrix, we only need a vector of `m` rows.
 ///
@@ -176,18 +251,35 @@ impl TorusZq for u64 {
 /// ### Private key statements
 /// The row's polynomial is simply `b * X^0`.
 ///
+/// ### Decryption statements
+/// The row's polynomial is `(b - m) * X^0`, where `m` is the encoded public message. The
+/// rows for the public key's encryptions of zero contain `b * X^0` of each `p_j`.
+///
+/// The decryption noise is bounded by `delta / 2`, where `delta` is the encoding factor, so
+/// `ciphertext` rounds to `m`.
+///
 /// ### Public key statements
 /// The rows's polynomial is `a_0 * X^0, a_1 * X^1, ... a_N * X^{N - 1} b * X^N`
 ///
+/// ### GLWE statements
+/// The row's polynomial is the ciphertext's `b` polynomial.
+///
 /// ## Quotient ring modulus `f`
-/// When `statements` contains only secret key encryption statements, `f = X + 1`. Otherwise,
-/// `f = X^{N + 1} + 1`.
+/// When `statements` contains GLWE statements, `f = X^P + 1` where `P` is the GLWE polynomial
+/// degree. Otherwise, when `statements` contains only secret key encryption and decryption
+/// statements, `f = X + 1`. Otherwise, `f = X^{N + 1} + 1`.
+///
+/// # Panics
+/// If the GLWE statements don't all share the same [`GlweDef`] dimensions.
+/// If `statements` contains both GLWE and public key statements and the GLWE polynomial
+/// degree is less than `N + 1`.
 pub fn generate_tfhe_sdlp_verifier_knowledge<S: TorusOps + TorusZq>(
     statements: &[ProofStatement<S>],
     lwe: &LweDef,
     plaintext_bits: PlaintextBits,
 ) -> VerifierKnowledge<S::Zq> {
-    // If we need to prove any public encryption statements, then `f = X^{lwe_dimension + 1} + 1`.
+    // If we need to prove any GLWE statements, then `f = X^{polynomial_degree} + 1`. If we
+    // need to prove any public encryption statements, then `f = X^{lwe_dimension + 1} + 1`.
     // If not, we can use the more efficient X + 1.
     let mut f_coeffs = vec![S::Zq::from(<S as Zero>::zero()); f_degree(statements, lwe) + 1];
     f_coeffs[0] = S::Zq::from(S::one());
@@ -198,7 +290,7 @@ pub fn generate_tfhe_sdlp_verifier_knowledge<S: TorusOps + TorusZq>(
     let f = Polynomial::new(&f_coeffs);
 
     let a = compute_a(statements, lwe, plaintext_bits);
-    let t = compute_t(statements, lwe);
+    let t = compute_t(statements, lwe, plaintext_bits);
     let bounds = compute_bounds(statements, lwe, plaintext_bits);
 
     VerifierKnowledge::new(a, t, f, bounds)
@@ -249,12 +341,21 @@ fn compute_bounds<S: TorusOps + TorusZq>(
     }
 
     // Private s and e
-    for i in 0..num_private(statements) {
+    let private_statements = statements.iter().filter(|x| {
+        matches!(
+            x,
+            ProofStatement::PrivateKeyEncryption { .. } | ProofStatement::LweDecryption { .. }
+        )
+    });
+
+    for (i, statement) in private_statements.enumerate() {
+        let is_decryption = matches!(statement, ProofStatement::LweDecryption { .. });
+
         for j in 0..lwe_dimension {
             let mut b = Bounds(vec![0; num_coeffs]);
 
             // Values of s are binary
-            b.0[0] = plaintext_bits.0;
+            b.0[0] = if is_decryption { 1 } else { plaintext_bits.0 };
             debug_assert_eq!(
                 bounds[(offsets.private_a + j + i * lwe_dimension, 0)].0,
                 &[]
@@ -264,26 +365,106 @@ fn compute_bounds<S: TorusOps + TorusZq>(
 
         let mut b = Bounds(vec![0; num_coeffs]);
 
-        // e is normal distributed over the torus.
-        // TODO: This bound is too high. Get a tighter bound.
-        b.0[0] = 62 - plaintext_bits.0;
+        b.0[0] = if is_decryption {
+            // The ciphertext only decrypts to the message if the noise is less than
+            // half the encoding factor.
+            S::BITS - plaintext_bits.0 - 1
+        } else {
+            // e is normal distributed over the torus.
+            // TODO: This bound is too high. Get a tighter bound.
+            62 - plaintext_bits.0
+        };
         debug_assert_eq!(bounds[(offsets.private_e + i, 0)].0, &[]);
         bounds[(offsets.private_e + i, 0)] = b;
     }
 
+    // Noise in the public keys' encryptions of zero for decryption statements.
+    for i in 0..num_decryptions(statements) * lwe_dimension {
+        let mut b = Bounds(vec![0; num_coeffs]);
+        b.0[0] = fresh_noise_bound::<S>(lwe);
+
+        debug_assert_eq!(bounds[(offsets.decryption_e + i, 0)].0, &[]);
+        bounds[(offsets.decryption_e + i, 0)] = b;
+    }
+
+    // GLWE s and e
+    let glwe_size = glwe_def(statements).map(|x| x.dim.size.0).unwrap_or(0);
+
+    for i in 0..num_glwe(statements) {
+        for j in 0..glwe_size {
+            // Every coefficient in each s polynomial is binary.
+            let b = Bounds(vec![1; num_coeffs]);
+
+            debug_assert_eq!(bounds[(offsets.glwe_s + j + i * glwe_size, 0)].0, &[]);
+            bounds[(offsets.glwe_s + j + i * glwe_size, 0)] = b;
+        }
+
+        // e's coefficients are normal distributed over the torus.
+        // TODO: This bound is too high. Get a tighter bound.
+        let b = Bounds(vec![62 - plaintext_bits.0; num_coeffs]);
+
+        debug_assert_eq!(bounds[(offsets.glwe_e + i, 0)].0, &[]);
+        bounds[(offsets.glwe_e + i, 0)] = b;
+    }
+
     bounds
 }
 
+/// Returns the number of bits needed to bound the magnitude of fresh encryption noise under
+/// `lwe`, which we take to be 16 standard deviations.
+fn fresh_noise_bound<S: TorusOps>(lwe: &LweDef) -> u32 {
+    let std = lwe.std.0 * 2f64.powi(S::BITS as i32);
+    let bits = (16.0 * std).log2().ceil().max(1.0) as u32;
+
+    bits.min(S::BITS - 1)
+}
+
 fn f_degree<S: TorusOps + TorusZq>(statements: &[ProofStatement<S>], lwe: &LweDef) -> usize {
     let lwe_dimension = lwe.dim.0;
 
-    if num_public(statements) > 0 {
+    if let Some(glwe) = glwe_def(statements) {
+        let degree = glwe.dim.polynomial_degree.0;
+
+        // Public key statements contain polynomials of degree `lwe_dimension`, which must
+        // not wrap around `f`.
+        assert!(
+            num_public(statements) == 0 || lwe_dimension < degree,
+            "GLWE polynomial degree must exceed the LWE dimension to prove public key statements."
+        );
+
+        degree
+    } else if num_public(statements) > 0 {
         lwe_dimension + 1
     } else {
         1
     }
 }
 
+/// Returns the [`GlweDef`] shared by every GLWE statement, if any.
+fn glwe_def<'a, S: TorusOps + TorusZq>(
+    statements: &[ProofStatement<'_, 'a, S>],
+) -> Option<&'a GlweDef> {
+    let mut defs = statements.iter().filter_map(|x| match x {
+        ProofStatement::GlweEncryption { params, .. } => Some(*params),
+        _ => None,
+    });
+
+    let first = defs.next()?;
+
+    for d in defs {
+        assert_eq!(
+            d.dim.polynomial_degree.0, first.dim.polynomial_degree.0,
+            "All GLWE statements must use the same polynomial degree."
+        );
+        assert_eq!(
+            d.dim.size.0, first.dim.size.0,
+            "All GLWE statements must use the same GLWE size."
+        );
+    }
+
+    Some(first)
+}
+
 fn encoding_factor<S: TorusZq + TorusOps>(plaintext_bits: PlaintextBits) -> S::Zq {
     let x = S::from_u64(0x1u64 << (S::BITS - plaintext_bits.0));
     S::Zq::from(x)
@@ -294,6 +475,9 @@ struct IdxOffsets {
     public_e: usize,
     private_a: usize,
     private_e: usize,
+    glwe_s: usize,
+    glwe_e: usize,
+    decryption_e: usize,
 }
 
 #[inline]
@@ -302,16 +486,24 @@ fn compute_a_column_offsets<S: TorusOps + TorusZq>(
     lwe: &LweDef,
 ) -> IdxOffsets {
     let lwe_dimension = lwe.dim.0;
+    let glwe_size = glwe_def(statements).map(|x| x.dim.size.0).unwrap_or(0);
+
     let public_keys = num_messages(statements);
     let public_e = public_keys + num_public(statements) * lwe_dimension;
     let private_a = public_e + num_public(statements);
     let private_e = private_a + num_private(statements) * lwe_dimension;
+    let glwe_s = private_e + num_private(statements);
+    let glwe_e = glwe_s + num_glwe(statements) * glwe_size;
+    let decryption_e = glwe_e + num_glwe(statements);
 
     IdxOffsets {
         public_keys,
         public_e,
         private_a,
         private_e,
+        glwe_s,
+        glwe_e,
+        decryption_e,
     }
 }
 
@@ -333,81 +525,177 @@ fn compute_a<S: TorusZq + TorusOps>(
 
     let mut cur_public = 0;
     let mut cur_private = 0;
+    let mut cur_glwe = 0;
+    let mut cur_decryption = 0;
 
     for (i, s) in statements.iter().enumerate() {
-        let (is_public, public_key, message_id, ciphertext) = match s {
-            ProofStatement::PrivateKeyEncryption {
-                ciphertext,
-                message_id,
-            } => (false, None, *message_id, *ciphertext),
+        match s {
             ProofStatement::PublicKeyEncryption {
                 public_key,
-                ciphertext,
                 message_id,
-            } => (true, Some(public_key), *message_id, *ciphertext),
-        };
+                ciphertext: _,
+            } => {
+                // Insert the message coefficient. For public encryptions, it goes in the
+                // d-1 coefficient.
+                let mut coeffs = vec![S::Zq::from(<S as Zero>::zero()); lwe_dimension + 1];
+                coeffs[lwe_dimension] = msg_encode.clone();
+
+                debug_assert_eq!(a[(i, *message_id)], Polynomial::zero());
+                a[(i, *message_id)] = Polynomial::new(&coeffs);
+
+                // Push the public key
+                for (j, z) in public_key.enc_zeros(lwe).enumerate() {
+                    let (z_a, z_b) = z.a_b(lwe);
+                    let mut coeffs = z_a
+                        .iter()
+                        .map(|x| S::Zq::from(x.inner()))
+                        .collect::<Vec<_>>();
+                    coeffs.push(S::Zq::from(z_b.inner()));
+
+                    let public_key_idx = offsets.public_keys + cur_public * lwe_dimension + j;
+
+                    debug_assert_eq!(a[(i, public_key_idx)], Polynomial::zero());
+                    a[(i, public_key_idx)] = Polynomial::new(&coeffs);
+                }
 
-        // Insert the message coefficient. For private encryptions, this goes in
-        // the constant coefficient. For public, it goes in the d-1 coefficient.
-        let coeffs = if !is_public {
-            vec![msg_encode.clone()]
-        } else {
-            let mut coeffs = vec![S::Zq::from(<S as Zero>::zero()); lwe_dimension + 1];
-            coeffs[lwe_dimension] = msg_encode.clone();
-            coeffs
-        };
+                // Push the randomness
+                debug_assert_eq!(a[(i, offsets.public_e + cur_public)], Polynomial::zero());
+                a[(i, offsets.public_e + cur_public)] = Polynomial::one();
+                cur_public += 1;
+            }
+            ProofStatement::PrivateKeyEncryption {
+                message_id,
+                ciphertext,
+            } => {
+                // Insert the message coefficient. For private encryptions, this goes in
+                // the constant coefficient.
+                debug_assert_eq!(a[(i, *message_id)], Polynomial::zero());
+                a[(i, *message_id)] = Polynomial::new(std::slice::from_ref(&msg_encode));
+
+                let e_idx = offsets.private_e + cur_private;
+                insert_private_key_columns(
+                    &mut a,
+                    i,
+                    ciphertext,
+                    cur_private,
+                    e_idx,
+                    &offsets,
+                    lwe,
+                );
+                cur_private += 1;
+            }
+            ProofStatement::LweDecryption {
+                ciphertext,
+                message: _,
+                public_key,
+            } => {
+                // The message is public, so it moves into T rather than occupying a column.
+                let e_idx = offsets.private_e + cur_private;
+                insert_private_key_columns(
+                    &mut a,
+                    i,
+                    ciphertext,
+                    cur_private,
+                    e_idx,
+                    &offsets,
+                    lwe,
+                );
 
-        debug_assert_eq!(a[(i, message_id)], Polynomial::zero());
-        a[(i, message_id)] = Polynomial::new(&coeffs);
+                // Bind the key to the public key by decrypting each of its encryptions of
+                // zero with the same key columns.
+                for (j, z) in public_key.enc_zeros(lwe).enumerate() {
+                    let row = statements.len() + cur_decryption * lwe_dimension + j;
+                    let e_idx = offsets.decryption_e + cur_decryption * lwe_dimension + j;
 
-        if is_public {
-            // Push the public key
-            let pk = public_key.unwrap();
+                    insert_private_key_columns(&mut a, row, z, cur_private, e_idx, &offsets, lwe);
+                }
 
-            for (j, z) in pk.enc_zeros(lwe).enumerate() {
-                let (z_a, z_b) = z.a_b(lwe);
-                let mut coeffs = z_a
-                    .iter()
-                    .map(|x| S::Zq::from(x.inner()))
-                    .collect::<Vec<_>>();
-                coeffs.push(S::Zq::from(z_b.inner()));
+                cur_private += 1;
+                cur_decryption += 1;
+            }
+            ProofStatement::GlweEncryption {
+                message_ids,
+                ciphertext,
+                params,
+            } => {
+                let degree = params.dim.polynomial_degree.0;
+                let glwe_size = params.dim.size.0;
 
-                let public_key_idx = offsets.public_keys + cur_public * lwe_dimension + j;
+                // The message in coefficient j contributes `delta * X^j`. Multiple
+                // coefficients may encode the same message, so accumulate by ID.
+                let mut message_coeffs = BTreeMap::new();
 
-                debug_assert_eq!(a[(i, public_key_idx)], Polynomial::zero());
-                a[(i, public_key_idx)] = Polynomial::new(&coeffs);
-            }
+                for (j, id) in message_ids.iter().enumerate() {
+                    let coeffs = message_coeffs
+                        .entry(*id)
+                        .or_insert_with(|| vec![S::Zq::from(<S as Zero>::zero()); degree]);
 
-            // Push the randomness
-            debug_assert_eq!(a[(i, offsets.public_e + cur_public)], Polynomial::zero());
-            a[(i, offsets.public_e + cur_public)] = Polynomial::one();
-            cur_public += 1;
-        } else {
-            let (c_a, _c_b) = ciphertext.a_b(lwe);
+                    coeffs[j] = coeffs[j].clone() + &msg_encode;
+                }
 
-            // Place the a values of the cipertext in the matrix.
-            for (j, a_j) in c_a.iter().enumerate() {
-                let private_key_idx = offsets.private_a + j + cur_private * lwe_dimension;
+                for (id, coeffs) in message_coeffs {
+                    debug_assert_eq!(a[(i, id)], Polynomial::zero());
+                    a[(i, id)] = Polynomial::new(&coeffs);
+                }
 
-                debug_assert_eq!(a[(i, private_key_idx)], Polynomial::zero());
-                a[(i, private_key_idx)] = Polynomial::new(&[S::Zq::from(a_j.inner())]);
-            }
+                // Place the a polynomials of the ciphertext in the matrix.
+                for (j, a_j) in ciphertext.a(params).enumerate() {
+                    let private_key_idx = offsets.glwe_s + j + cur_glwe * glwe_size;
 
-            debug_assert_eq!(a[(i, offsets.private_e)], Polynomial::zero());
+                    let coeffs = a_j
+                        .coeffs()
+                        .iter()
+                        .map(|x| S::Zq::from(x.inner()))
+                        .collect::<Vec<_>>();
 
-            a[(i, offsets.private_e + i)] = Polynomial::one();
-            cur_private += 1;
+                    debug_assert_eq!(a[(i, private_key_idx)], Polynomial::zero());
+                    a[(i, private_key_idx)] = Polynomial::new(&coeffs);
+                }
+
+                debug_assert_eq!(a[(i, offsets.glwe_e + cur_glwe)], Polynomial::zero());
+                a[(i, offsets.glwe_e + cur_glwe)] = Polynomial::one();
+                cur_glwe += 1;
+            }
         }
     }
 
     a
 }
 
+/// Places the a values of an LWE ciphertext in row `row` of the `A` matrix and a `1` in
+/// its noise column `e_idx`.
+fn insert_private_key_columns<S: TorusZq + TorusOps>(
+    a: &mut Matrix<Polynomial<S::Zq>>,
+    row: usize,
+    ciphertext: &LweCiphertextRef<S>,
+    cur_private: usize,
+    e_idx: usize,
+    offsets: &IdxOffsets,
+    lwe: &LweDef,
+) {
+    let lwe_dimension = lwe.dim.0;
+    let (c_a, _c_b) = ciphertext.a_b(lwe);
+
+    // Place the a values of the cipertext in the matrix.
+    for (j, a_j) in c_a.iter().enumerate() {
+        let private_key_idx = offsets.private_a + j + cur_private * lwe_dimension;
+
+        debug_assert_eq!(a[(row, private_key_idx)], Polynomial::zero());
+        a[(row, private_key_idx)] = Polynomial::new(&[S::Zq::from(a_j.inner())]);
+    }
+
+    debug_assert_eq!(a[(row, e_idx)], Polynomial::zero());
+    a[(row, e_idx)] = Polynomial::one();
+}
+
 fn compute_t<S: TorusOps + TorusZq>(
     statements: &[ProofStatement<S>],
     lwe: &LweDef,
+    plaintext_bits: PlaintextBits,
 ) -> PolynomialMatrix<S::Zq> {
-    let mut t = PolynomialMatrix::new(statements.len(), 1);
+    let (rows, _) = proof_matrix_dim(statements, lwe.dim.0);
+    let mut t = PolynomialMatrix::new(rows, 1);
+    let mut cur_decryption = 0;
 
     for (i, s) in statements.iter().enumerate() {
         match s {
@@ -434,6 +722,43 @@ fn compute_t<S: TorusOps + TorusZq>(
 
                 coeffs.push(S::Zq::from(c_b.inner()));
 
+                debug_assert_eq!(t[(i, 0)], Polynomial::zero());
+                t[(i, 0)] = Polynomial::new(&coeffs);
+            }
+            ProofStatement::LweDecryption {
+                ciphertext: c,
+                message,
+                public_key,
+            } => {
+                let (_, c_b) = c.a_b(lwe);
+
+                let msg = encoding_factor::<S>(plaintext_bits) * S::Zq::from(*message);
+
+                debug_assert_eq!(t[(i, 0)], Polynomial::zero());
+                t[(i, 0)] = Polynomial::new(&[S::Zq::from(c_b.inner()) - msg]);
+
+                for (j, z) in public_key.enc_zeros(lwe).enumerate() {
+                    let row = statements.len() + cur_decryption * lwe.dim.0 + j;
+                    let (_, z_b) = z.a_b(lwe);
+
+                    debug_assert_eq!(t[(row, 0)], Polynomial::zero());
+                    t[(row, 0)] = Polynomial::new(&[S::Zq::from(z_b.inner())]);
+                }
+
+                cur_decryption += 1;
+            }
+            ProofStatement::GlweEncryption {
+                ciphertext: c,
+                params,
+                message_ids: _,
+            } => {
+                let coeffs = c
+                    .b(params)
+                    .coeffs()
+                    .iter()
+                    .map(|x| S::Zq::from(x.inner()))
+                    .collect::<Vec<_>>();
+
                 debug_assert_eq!(t[(i, 0)], Polynomial::zero());
                 t[(i, 0)] = Polynomial::new(&coeffs);
             }
@@ -449,10 +774,12 @@ fn compute_s<S: TorusOps + TorusZq>(
     witness: &[Witness<S>],
     messages: &[Torus<S>],
     lwe: &LweDef,
+    plaintext_bits: PlaintextBits,
 ) -> PolynomialMatrix<S::Zq> {
     assert_eq!(statements.len(), witness.len());
 
     let lwe_dimension = lwe.dim.0;
+    let glwe_size = glwe_def(statements).map(|x| x.dim.size.0).unwrap_or(0);
 
     // If the a matrix is rows x cols, the S witness must have 'cols' rows.
     let (_, cols) = proof_matrix_dim(statements, lwe_dimension);
@@ -468,73 +795,160 @@ fn compute_s<S: TorusOps + TorusZq>(
         s[(i, 0)] = Polynomial::new(&[S::Zq::from(m.inner())]);
     }
 
-    let public_entries = witness.iter().filter_map(|x| match x {
-        Witness::PublicKeyEncryption { randomness } => Some(randomness),
-        _ => None,
-    });
+    let mut cur_public = 0;
+    let mut cur_private = 0;
+    let mut cur_glwe = 0;
+    let mut cur_decryption = 0;
+
+    for (statement, w) in statements.iter().zip(witness.iter()) {
+        match (statement, w) {
+            // Put public 'r' and 'e' randomness into the witness
+            (
+                ProofStatement::PublicKeyEncryption { .. },
+                Witness::PublicKeyEncryption { randomness: rnd },
+            ) => {
+                for (j, r) in rnd.r.iter().enumerate() {
+                    let public_key_index = offsets.public_keys + j + cur_public * lwe_dimension;
+
+                    debug_assert_eq!(s[(public_key_index, 0)], Polynomial::zero());
+                    s[(public_key_index, 0)] = Polynomial::new(&[S::Zq::from(*r)]);
+                }
 
-    // Put public 'r' and 'e' randomness into the witness
-    for (i, rnd) in public_entries.enumerate() {
-        for (j, r) in rnd.r.iter().enumerate() {
-            let public_key_index = offsets.public_keys + j + i * lwe_dimension;
+                let mut coeffs = rnd
+                    .e
+                    .a_b(lwe)
+                    .0
+                    .iter()
+                    .map(|x| S::Zq::from(x.inner()))
+                    .collect::<Vec<_>>();
+                coeffs.push(S::Zq::from(rnd.e.a_b(lwe).1.inner()));
 
-            debug_assert_eq!(s[(public_key_index, 0)], Polynomial::zero());
-            s[(public_key_index, 0)] = Polynomial::new(&[S::Zq::from(*r)]);
-        }
+                let public_randomness_index = offsets.public_e + cur_public;
 
-        let mut coeffs = rnd
-            .e
-            .a_b(lwe)
-            .0
-            .iter()
-            .map(|x| S::Zq::from(x.inner()))
-            .collect::<Vec<_>>();
-        coeffs.push(S::Zq::from(rnd.e.a_b(lwe).1.inner()));
+                debug_assert_eq!(s[(public_randomness_index, 0)], Polynomial::zero());
+                s[(public_randomness_index, 0)] = Polynomial::new(&coeffs);
+
+                cur_public += 1;
+            }
+            // Put the secret keys and 'e' into the witness.
+            (
+                ProofStatement::PrivateKeyEncryption { .. },
+                Witness::PrivateKeyEncryption {
+                    randomness,
+                    private_key,
+                },
+            ) => {
+                insert_private_key_witness(
+                    &mut s,
+                    private_key,
+                    randomness,
+                    cur_private,
+                    &offsets,
+                    lwe,
+                );
+                cur_private += 1;
+            }
+            (
+                ProofStatement::LweDecryption {
+                    ciphertext,
+                    message,
+                    public_key,
+                },
+                Witness::LweDecryption { private_key },
+            ) => {
+                // The noise is whatever remains after removing the message from the
+                // decrypted value.
+                let e = private_key.decrypt_without_decode(ciphertext, lwe)
+                    - Torus::encode(*message, plaintext_bits);
+
+                insert_private_key_witness(&mut s, private_key, &e, cur_private, &offsets, lwe);
+
+                // Decrypting an encryption of zero leaves just its noise.
+                for (j, z) in public_key.enc_zeros(lwe).enumerate() {
+                    let e = private_key.decrypt_without_decode(z, lwe);
+                    let e_idx = offsets.decryption_e + cur_decryption * lwe_dimension + j;
+
+                    debug_assert_eq!(s[(e_idx, 0)], Polynomial::zero());
+                    s[(e_idx, 0)] = Polynomial::new(&[S::Zq::from(e.inner())]);
+                }
 
-        let public_randomness_index = offsets.public_e + i;
+                cur_private += 1;
+                cur_decryption += 1;
+            }
+            (
+                ProofStatement::GlweEncryption { params, .. },
+                Witness::GlweEncryption {
+                    randomness,
+                    private_key,
+                },
+            ) => {
+                for (j, s_j) in private_key.s(params).enumerate() {
+                    let private_key_index = offsets.glwe_s + j + cur_glwe * glwe_size;
+
+                    let coeffs = s_j
+                        .coeffs()
+                        .iter()
+                        .map(|x| S::Zq::from(*x))
+                        .collect::<Vec<_>>();
+
+                    debug_assert_eq!(s[(private_key_index, 0)], Polynomial::zero());
+                    s[(private_key_index, 0)] = Polynomial::new(&coeffs);
+                }
 
-        debug_assert_eq!(s[(public_randomness_index, 0)], Polynomial::zero());
-        s[(public_randomness_index, 0)] = Polynomial::new(&coeffs);
-    }
+                let coeffs = randomness
+                    .coeffs()
+                    .iter()
+                    .map(|x| S::Zq::from(x.inner()))
+                    .collect::<Vec<_>>();
 
-    let private_keys = witness.iter().filter_map(|x| match x {
-        Witness::PrivateKeyEncryption {
-            randomness,
-            private_key,
-        } => Some((private_key, randomness)),
-        _ => None,
-    });
+                let glwe_randomness_index = offsets.glwe_e + cur_glwe;
 
-    // Put the secret keys into the witness.
-    for (i, (sk, e)) in private_keys.enumerate() {
-        for (j, sk) in sk.s().iter().enumerate() {
-            let private_key_index = offsets.private_a + j + i * lwe_dimension;
+                debug_assert_eq!(s[(glwe_randomness_index, 0)], Polynomial::zero());
+                s[(glwe_randomness_index, 0)] = Polynomial::new(&coeffs);
 
-            debug_assert_eq!(s[(private_key_index, 0)], Polynomial::zero());
-            s[(private_key_index, 0)] = Polynomial::new(&[S::Zq::from(*sk)]);
+                cur_glwe += 1;
+            }
+            _ => panic!("Witness does not match its proof statement."),
         }
+    }
+
+    s
+}
+
+/// Places an LWE secret key and noise in the `S` witness.
+fn insert_private_key_witness<S: TorusOps + TorusZq>(
+    s: &mut PolynomialMatrix<S::Zq>,
+    private_key: &LweSecretKey<S>,
+    e: &Torus<S>,
+    cur_private: usize,
+    offsets: &IdxOffsets,
+    lwe: &LweDef,
+) {
+    let lwe_dimension = lwe.dim.0;
 
-        let private_randomness_index = offsets.private_e + i;
+    for (j, sk) in private_key.s().iter().enumerate() {
+        let private_key_index = offsets.private_a + j + cur_private * lwe_dimension;
 
-        debug_assert_eq!(s[(private_randomness_index, 0)], Polynomial::zero());
-        s[(private_randomness_index, 0)] = Polynomial::new(&[S::Zq::from(e.inner())]);
+        debug_assert_eq!(s[(private_key_index, 0)], Polynomial::zero());
+        s[(private_key_index, 0)] = Polynomial::new(&[S::Zq::from(*sk)]);
     }
 
-    s
+    let private_randomness_index = offsets.private_e + cur_private;
+
+    debug_assert_eq!(s[(private_randomness_index, 0)], Polynomial::zero());
+    s[(private_randomness_index, 0)] = Polynomial::new(&[S::Zq::from(e.inner())]);
 }
 
+/// Returns the number of statements that require an LWE private key, namely
+/// private key encryptions and decryptions.
 #[inline(always)]
 fn num_private<S: TorusOps + TorusZq>(statements: &[ProofStatement<S>]) -> usize {
-    // Public key statements require 2 rows while private key statements require only one.
     statements
         .iter()
         .filter(|x| {
             matches!(
                 x,
-                ProofStatement::PrivateKeyEncryption {
-                    ciphertext: _,
-                    message_id: _
-                }
+                ProofStatement::PrivateKeyEncryption { .. } | ProofStatement::LweDecryption { .. }
             )
         })
         .count()
@@ -542,7 +956,26 @@ fn num_private<S: TorusOps + TorusZq>(statements: &[ProofStatement<S>]) -> usize
 
 #[inline(always)]
 fn num_public<S: TorusOps + TorusZq>(statements: &[ProofStatement<S>]) -> usize {
-    statements.len() - num_private(statements)
+    statements
+        .iter()
+        .filter(|x| matches!(x, ProofStatement::PublicKeyEncryption { .. }))
+        .count()
+}
+
+#[inline(always)]
+fn num_decryptions<S: TorusOps + TorusZq>(statements: &[ProofStatement<S>]) -> usize {
+    statements
+        .iter()
+        .filter(|x| matches!(x, ProofStatement::LweDecryption { .. }))
+        .count()
+}
+
+#[inline(always)]
+fn num_glwe<S: TorusOps + TorusZq>(statements: &[ProofStatement<S>]) -> usize {
+    statements
+        .iter()
+        .filter(|x| matches!(x, ProofStatement::GlweEncryption { .. }))
+        .count()
 }
 
 /// Returns a tuple of the `(rows, cols)` in SDLP's `A` matrix.
@@ -551,33 +984,41 @@ fn proof_matrix_dim<S: TorusOps + TorusZq>(
     statements: &[ProofStatement<S>],
     lwe_dimension: usize,
 ) -> (usize, usize) {
-    let num_rows = statements.len();
+    // Each decryption statement adds a row per encryption of zero in its public key.
+    let num_rows = statements.len() + num_decryptions(statements) * lwe_dimension;
+    let glwe_size = glwe_def(statements).map(|x| x.dim.size.0).unwrap_or(0);
 
     let num_cols = num_messages(statements) // message terms
         + num_public(statements) * lwe_dimension // public key terms
         + num_private(statements) * lwe_dimension // a terms
-        + num_rows; // Public + private e term '1' coeffs
+        + num_glwe(statements) * glwe_size // GLWE a terms
+        + num_rows; // Public, private, GLWE and public key zero e term '1' coeffs
 
     (num_rows, num_cols)
 }
 
+/// Returns the number of distinct message columns, which is one more than the largest
+/// message ID referenced by any statement.
 #[inline]
 fn num_messages<S: TorusOps + TorusZq>(statements: &[ProofStatement<S>]) -> usize {
-    statements.iter().fold(0usize, |max, x| {
-        let message_id = match x {
+    statements
+        .iter()
+        .flat_map(|x| match x {
             ProofStatement::PublicKeyEncryption {
                 public_key: _,
                 ciphertext: _,
                 message_id,
-            } => message_id,
+            } => std::slice::from_ref(message_id),
             ProofStatement::PrivateKeyEncryption {
                 ciphertext: _,
                 message_id,
-            } => message_id,
-        };
-
-        usize::max(max, *message_id)
-    }) + 1
+            } => std::slice::from_ref(message_id),
+            ProofStatement::GlweEncryption { message_ids, .. } => message_ids,
+            ProofStatement::LweDecryption { .. } => &[],
+        })
+        .max()
+        .map(|x| x + 1)
+        .unwrap_or(0)
 }
 
 #[cfg(test)]
@@ -587,9 +1028,11 @@ mod tests {
     use rand::{thread_rng, RngCore};
 
     use crate::{
+        entities::Polynomial as TorusPolynomial,
         high_level::*,
         zkp::{num_private, num_public},
-        LweDef, LweDimension, LWE_512_80,
+        GlweDimension, GlweSize, LweDef, LweDimension, PolynomialDegree, GLWE_1_1024_80,
+        LWE_512_80,
     };
 
     use super::*;
@@ -891,6 +1334,264 @@ mod tests {
         prove_and_verify::<u64>(&pk);
     }
 
+    fn small_glwe() -> GlweDef {
+        GlweDef {
+            dim: GlweDimension {
+                polynomial_degree: PolynomialDegree(8),
+                size: GlweSize(2),
+            },
+            ..GLWE_1_1024_80
+        }
+    }
+
+    #[test]
+    fn can_compute_a_column_offsets_with_glwe_and_decryption() {
+        let lwe = LweDef {
+            dim: LweDimension(4),
+            ..LWE_512_80
+        };
+        let glwe = small_glwe();
+
+        let ct = LweCiphertext::<u64>::zero(&lwe);
+        let glwe_ct = GlweCiphertext::<u64>::new(&glwe);
+        let sk = keygen::generate_binary_lwe_sk(&lwe);
+        let pk = keygen::generate_lwe_pk(&sk, &lwe);
+
+        let statements = [
+            ProofStatement::GlweEncryption {
+                message_ids: &[0, 2],
+                ciphertext: &glwe_ct,
+                params: &glwe,
+            },
+            ProofStatement::LweDecryption {
+                ciphertext: &ct,
+                message: 1,
+                public_key: &pk,
+            },
+            ProofStatement::PrivateKeyEncryption {
+                ciphertext: &ct,
+                message_id: 0,
+            },
+        ];
+
+        let idx = compute_a_column_offsets(&statements, &lwe);
+
+        assert_eq!(num_messages(&statements), 3);
+        assert_eq!(num_private(&statements), 2);
+        assert_eq!(num_glwe(&statements), 1);
+        assert_eq!(f_degree(&statements, &lwe), 8);
+
+        assert_eq!(idx.private_e, idx.private_a + 2 * lwe.dim.0);
+        assert_eq!(idx.glwe_s, idx.private_e + 2);
+        assert_eq!(idx.glwe_e, idx.glwe_s + glwe.dim.size.0);
+        assert_eq!(idx.decryption_e, idx.glwe_e + 1);
+
+        let (rows, cols) = proof_matrix_dim(&statements, lwe.dim.0);
+
+        assert_eq!(rows, statements.len() + lwe.dim.0);
+        assert_eq!(cols, idx.decryption_e + lwe.dim.0);
+    }
+
+    #[test]
+    fn one_glwe_secret_key() {
+        let lwe = LweDef {
+            dim: LweDimension(4),
+            ..LWE_512_80
+        };
+        let glwe = small_glwe();
+        let bits = PlaintextBits(1);
+
+        let sk = keygen::generate_binary_glwe_sk(&glwe);
+
+        let pt = TorusPolynomial::new(&[1, 0, 1, 1, 0, 0, 0, 0]);
+        let (ct, rng) = encryption::encrypt_glwe_and_return_randomness(&pt, &sk, &glwe, bits);
+
+        let pk = generate_tfhe_sdlp_prover_knowledge(
+            &[ProofStatement::GlweEncryption {
+                message_ids: &[1, 0, 1, 1],
+                ciphertext: &ct,
+                params: &glwe,
+            }],
+            &[Torus::from(0), Torus::from(1)],
+            &[Witness::GlweEncryption {
+                randomness: &rng,
+                private_key: &sk,
+            }],
+            &lwe,
+            bits,
+        );
+
+        prove_and_verify::<u64>(&pk);
+    }
+
+    #[test]
+    fn glwe_and_lwe_share_messages() {
+        let lwe = LweDef {
+            dim: LweDimension(4),
+            ..LWE_512_80
+        };
+        let glwe = small_glwe();
+        let bits = PlaintextBits(1);
+
+        let glwe_sk = keygen::generate_binary_glwe_sk(&glwe);
+        let lwe_sk = keygen::generate_binary_lwe_sk(&lwe);
+        let lwe_pk = keygen::generate_lwe_pk(&lwe_sk, &lwe);
+
+        let pt = TorusPolynomial::new(&[1, 0, 0, 0, 0, 0, 0, 0]);
+        let (glwe_ct, glwe_rng) =
+            encryption::encrypt_glwe_and_return_randomness(&pt, &glwe_sk, &glwe, bits);
+        let (ct_priv, rng_priv) =
+            encryption::encrypt_lwe_secret_and_return_randomness(0, &lwe_sk, &lwe, bits);
+        let (ct_pub, rng_pub) =
+            encryption::encrypt_lwe_and_return_randomness(1, &lwe_pk, &lwe, bits);
+
+        let pk = generate_tfhe_sdlp_prover_knowledge(
+            &[
+                ProofStatement::PublicKeyEncryption {
+                    message_id: 0,
+                    public_key: &lwe_pk,
+                    ciphertext: &ct_pub,
+                },
+                ProofStatement::GlweEncryption {
+                    message_ids: &[0, 1],
+                    ciphertext: &glwe_ct,
+                    params: &glwe,
+                },
+                ProofStatement::PrivateKeyEncryption {
+                    message_id: 1,
+                    ciphertext: &ct_priv,
+                },
+            ],
+            &[Torus::from(1), Torus::from(0)],
+            &[
+                Witness::PublicKeyEncryption {
+                    randomness: &rng_pub,
+                },
+                Witness::GlweEncryption {
+                    randomness: &glwe_rng,
+                    private_key: &glwe_sk,
+                },
+                Witness::PrivateKeyEncryption {
+                    randomness: rng_priv,
+                    private_key: &lwe_sk,
+                },
+            ],
+            &lwe,
+            bits,
+        );
+
+        prove_and_verify::<u64>(&pk);
+    }
+
+    #[test]
+    fn lwe_decryption() {
+        let params = LweDef {
+            dim: LweDimension(4),
+            ..LWE_512_80
+        };
+        let bits = PlaintextBits(1);
+
+        let sk = keygen::generate_binary_lwe_sk(&params);
+        let public_key = keygen::generate_lwe_pk(&sk, &params);
+
+        let ct0 = encryption::encrypt_lwe_secret(1, &sk, &params, bits);
+        let ct1 = encryption::encrypt_lwe(0, &public_key, &params, bits);
+
+        let pk = generate_tfhe_sdlp_prover_knowledge(
+            &[
+                ProofStatement::LweDecryption {
+                    ciphertext: &ct0,
+                    message: 1,
+                    public_key: &public_key,
+                },
+                ProofStatement::LweDecryption {
+                    ciphertext: &ct1,
+                    message: 0,
+                    public_key: &public_key,
+                },
+            ],
+            &[],
+            &[
+                Witness::LweDecryption { private_key: &sk },
+                Witness::LweDecryption { private_key: &sk },
+            ],
+            &params,
+            bits,
+        );
+
+        prove_and_verify::<u64>(&pk);
+    }
+
+    #[test]
+    fn lwe_decryption_key_must_match_public_key() {
+        let params = LweDef {
+            dim: LweDimension(4),
+            ..LWE_512_80
+        };
+        let bits = PlaintextBits(1);
+
+        let sk = keygen::generate_binary_lwe_sk(&params);
+        let public_key = keygen::generate_lwe_pk(&sk, &params);
+
+        // Find a key that differs from the one behind the public key.
+        let other_sk = loop {
+            let other_sk = keygen::generate_binary_lwe_sk(&params);
+
+            if other_sk.s() != sk.s() {
+                break other_sk;
+            }
+        };
+
+        let ct = encryption::encrypt_lwe_secret(1, &other_sk, &params, bits);
+
+        let pk = generate_tfhe_sdlp_prover_knowledge(
+            &[ProofStatement::LweDecryption {
+                ciphertext: &ct,
+                message: 1,
+                public_key: &public_key,
+            }],
+            &[],
+            &[Witness::LweDecryption {
+                private_key: &other_sk,
+            }],
+            &params,
+            bits,
+        );
+
+        // The noise columns make every row hold. With `f = X + 1`, every entry is a
+        // constant, so `AS` needs no reduction.
+        assert_eq!(&pk.vk.a * &pk.s, pk.vk.t);
+
+        // However, the public key's encryptions of zero don't decrypt to small noise under
+        // the other key, so the witness exceeds the bounds and no proof exists.
+        let max_noise = 1u64 << fresh_noise_bound::<u64>(&params);
+
+        let noise_in_bounds = public_key.enc_zeros(&params).all(|z| {
+            let e = other_sk.decrypt_without_decode(z, &params).inner();
+
+            e.min(e.wrapping_neg()) < max_noise
+        });
+
+        assert!(!noise_in_bounds);
+
+        // Proving with the mismatched key thus fails. Debug builds catch the out of bounds
+        // witness while creating the proof, while release builds produce a proof that
+        // doesn't verify.
+        let result = std::panic::catch_unwind(|| {
+            let gen: LogProofGenerators = LogProofGenerators::new(pk.vk.l() as usize);
+            let u = InnerProductVerifierKnowledge::get_u();
+            let mut p_t = Transcript::new(b"test");
+
+            let proof = LogProof::create(&mut p_t, &pk, &gen.g, &gen.h, &u);
+
+            let mut v_t = Transcript::new(b"test");
+
+            proof.verify(&mut v_t, &pk.vk, &gen.g, &gen.h, &u)
+        });
+
+        assert!(!matches!(result, Ok(Ok(_))));
+    }
+
     #[ignore]
     #[test]
     fn complex_examples() {
@@ -850,7 +850,7 @@ mod tests {
         let mut graph = ExecutableZkpProgram::new();
 
         let mut add_node = |op: BackendOperation, edges: &[(NodeIndex, EdgeInfo)]| {
-            let n = graph.add_node(NodeInfo { operation: op });
+            let n = graph.add_node(NodeInfo::new(op));
 
             for (source, edge) in edges {
                 graph.add_edge(*source, n, *edge);
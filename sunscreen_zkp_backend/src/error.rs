@@ -1,6 +1,6 @@
 use petgraph::stable_graph::NodeIndex;
 use static_assertions::const_assert;
-use sunscreen_compiler_common::GraphQueryError;
+use sunscreen_compiler_common::{GraphQueryError, SourceLocation};
 use thiserror::Error;
 
 #[derive(Clone, Debug, Error, PartialEq, Eq)]
@@ -51,11 +51,13 @@ pub enum Error {
      */
     MalformedZkpProgram(Box<String>),
 
-    #[error("A constraint could not be satisfied.")]
+    #[error("A constraint could not be satisfied{}.", at_source(&.0 .1))]
     /**
-     * A constraint could not be satisfied.
+     * A constraint could not be satisfied. Contains the constraint's node
+     * and, if known, where in the program's source the constraint was
+     * created.
      */
-    UnsatisfiableConstraint(NodeIndex),
+    UnsatisfiableConstraint(Box<(NodeIndex, Option<SourceLocation>)>),
 }
 
 impl Error {
@@ -86,6 +88,17 @@ impl Error {
     pub fn inputs_mismatch(msg: &str) -> Self {
         Self::InputsMismatch(Box::new(msg.to_owned()))
     }
+
+    /**
+     * Create an [`Error::UnsatisfiableConstraint`].
+     */
+    pub fn unsatisfiable_constraint(id: NodeIndex, source: Option<SourceLocation>) -> Self {
+        Self::UnsatisfiableConstraint(Box::new((id, source)))
+    }
+}
+
+fn at_source(source: &Option<SourceLocation>) -> String {
+    source.map(|x| format!(" at {x}")).unwrap_or_default()
 }
 
 impl From<bulletproofs::r1cs::R1CSError> for Error {
@@ -1,5 +1,5 @@
 use crate::BigInt;
-use sunscreen_compiler_common::{CompilationResult, Operation as OperationTrait};
+use sunscreen_compiler_common::{CompilationResult, ExportOperation, Operation as OperationTrait};
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub enum Operation {
@@ -46,6 +46,27 @@ impl OperationTrait for Operation {
     }
 }
 
+impl ExportOperation for Operation {
+    fn label(&self) -> String {
+        match self {
+            // Don't leak the prover's hidden values into exported graphs.
+            Operation::HiddenInput(_) => "HiddenInput".to_owned(),
+            _ => format!("{self:?}"),
+        }
+    }
+
+    fn data_type(&self) -> Option<String> {
+        match self {
+            Operation::Constraint(_) => None,
+            _ => Some("Field".to_owned()),
+        }
+    }
+
+    fn is_multiplication(&self) -> bool {
+        matches!(self, Operation::Mul)
+    }
+}
+
 /**
  * A ZKP program that has been JIT'd and is ready for use in a ZKP backend.
  */
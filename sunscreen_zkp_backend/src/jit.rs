@@ -8,7 +8,8 @@ use petgraph::{stable_graph::NodeIndex, visit::EdgeRef, Direction, Graph};
 use sunscreen_compiler_common::{
     forward_traverse, forward_traverse_mut,
     transforms::{GraphTransforms, Transform},
-    CompilationResult, EdgeInfo, GraphQueryError, NodeInfo, Operation as OperationTrait,
+    CompilationResult, EdgeInfo, ExportOperation, GraphQueryError, NodeInfo,
+    Operation as OperationTrait,
 };
 
 #[derive(Clone)]
@@ -174,6 +175,19 @@ impl OperationTrait for Operation {
     }
 }
 
+impl ExportOperation for Operation {
+    fn data_type(&self) -> Option<String> {
+        match self {
+            Self::Constraint(_) | Self::InvokeGadget(_) => None,
+            _ => Some("Field".to_owned()),
+        }
+    }
+
+    fn is_multiplication(&self) -> bool {
+        matches!(self, Operation::Mul)
+    }
+}
+
 /**
  * A ZKP program that has been through frontend compilation, but not yet
  * JIT'd.
@@ -331,7 +345,9 @@ where
                 for parent in parents {
                     let actual = node_outputs[&parent].clone().zkp_into();
                     if actual != x {
-                        return Err(Error::UnsatisfiableConstraint(id));
+                        let source = query.get_node(id).unwrap().source;
+
+                        return Err(Error::unsatisfiable_constraint(id, source));
                     }
                 }
             }
@@ -561,12 +577,15 @@ where
     forward_traverse_mut(prog, |query, id| {
         let mut transforms = GraphTransforms::new();
 
-        if let Operation::PublicInput(x) = query.get_node(id).unwrap().operation {
+        let node = query.get_node(id).unwrap();
+
+        if let Operation::PublicInput(x) = node.operation {
             let as_bigint: BigInt = public_inputs[x].clone().zkp_into();
 
-            let constraint = transforms.push(Transform::AddNode(NodeInfo {
-                operation: Operation::Constraint(as_bigint),
-            }));
+            let constraint = transforms.push(Transform::AddNode(NodeInfo::with_source(
+                Operation::Constraint(as_bigint),
+                node.source,
+            )));
             transforms.push(Transform::AddEdge(
                 id.into(),
                 constraint.into(),